@@ -6,8 +6,11 @@ use serde::{Deserialize, Serialize};
 use sp_core::{crypto::UncheckedInto, sr25519, Pair, Public};
 use sp_runtime::traits::{IdentifyAccount, Verify};
 use trappist_runtime::{
-	constants::currency::EXISTENTIAL_DEPOSIT, AccountId, AssetsConfig, AuraId, BalancesConfig,
-	CouncilConfig, GenesisConfig, SessionConfig, SessionKeys, Signature, SudoConfig, SystemConfig,
+	constants::currency::EXISTENTIAL_DEPOSIT,
+	xcm_config::{RelayTokenAssetId, RelayTokenMinBalance, TreasuryAccount},
+	AccountId, AssetsConfig, AuraId, BalancesConfig, ContractsAllowlistConfig, CouncilConfig,
+	GenesisConfig, SessionConfig, SessionKeys, Signature, SudoConfig, SystemConfig,
+	TechnicalCommitteeConfig,
 };
 
 const DEFAULT_PROTOCOL_ID: &str = "hop";
@@ -226,11 +229,32 @@ fn testnet_genesis(
 			// Assign network admin rights.
 			key: Some(root_key),
 		},
-		assets: AssetsConfig { assets: vec![], accounts: vec![], metadata: vec![] },
+		assets: AssetsConfig {
+			// the relay token, teleported to and from the relay chain
+			assets: vec![(
+				RelayTokenAssetId::get(),
+				TreasuryAccount::get(),
+				true,
+				RelayTokenMinBalance::get(),
+			)],
+			accounts: vec![],
+			metadata: vec![],
+		},
 		council: CouncilConfig {
 			members: invulnerables.iter().map(|x| x.0.clone()).collect::<Vec<_>>(),
 			phantom: Default::default(),
 		},
+		technical_committee: TechnicalCommitteeConfig {
+			members: invulnerables.iter().map(|x| x.0.clone()).collect::<Vec<_>>(),
+			phantom: Default::default(),
+		},
+		democracy: Default::default(),
+		treasury: Default::default(),
+		// dev chains allow any contract code
+		contracts_allowlist: ContractsAllowlistConfig {
+			enforced: false,
+			approved_code_hashes: vec![],
+		},
 	}
 }
 
@@ -339,11 +363,32 @@ fn trappist_genesis(
 			// Assign network admin rights.
 			key: Some(root_key),
 		},
-		assets: AssetsConfig { assets: vec![], accounts: vec![], metadata: vec![] },
+		assets: AssetsConfig {
+			// the relay token, teleported to and from the relay chain
+			assets: vec![(
+				RelayTokenAssetId::get(),
+				TreasuryAccount::get(),
+				true,
+				RelayTokenMinBalance::get(),
+			)],
+			accounts: vec![],
+			metadata: vec![],
+		},
 		council: CouncilConfig {
 			// We set the endowed accounts with balance as members of the council.
 			members: endowed_accounts.iter().map(|x| x.clone()).collect::<Vec<_>>(),
 			phantom: Default::default(),
 		},
+		technical_committee: TechnicalCommitteeConfig {
+			members: invulnerables.iter().map(|x| x.0.clone()).collect::<Vec<_>>(),
+			phantom: Default::default(),
+		},
+		democracy: Default::default(),
+		treasury: Default::default(),
+		// only code hashes approved by the council or root can be deployed on the shared testnet
+		contracts_allowlist: ContractsAllowlistConfig {
+			enforced: true,
+			approved_code_hashes: vec![],
+		},
 	}
 }
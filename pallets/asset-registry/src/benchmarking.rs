@@ -4,8 +4,11 @@ use super::*;
 
 #[allow(unused)]
 use crate::Pallet as AssetRegistry;
-use frame_benchmarking::benchmarks;
-use frame_support::{assert_ok, traits::fungibles::Inspect};
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::{
+	assert_ok,
+	traits::{fungibles::Inspect, tokens::nonfungibles},
+};
 use frame_system::RawOrigin;
 use xcm::opaque::latest::{
 	Junction::{GeneralIndex, PalletInstance, Parachain},
@@ -13,11 +16,30 @@ use xcm::opaque::latest::{
 };
 
 pub const LOCAL_ASSET_ID: u32 = 10;
+pub const LOCAL_COLLECTION_ID: u32 = 10;
+
+fn create_collection<T: Config>(collection_id: T::CollectionId)
+where
+	T::Uniques: nonfungibles::Create<<T as frame_system::Config>::AccountId>,
+{
+	if <T::Uniques as nonfungibles::Inspect<T::AccountId>>::collection_owner(&collection_id)
+		.is_none()
+	{
+		let caller: T::AccountId = whitelisted_caller();
+		assert_ok!(<T::Uniques as nonfungibles::Create<T::AccountId>>::create_collection(
+			&collection_id,
+			&caller,
+			&caller
+		));
+	}
+}
 
 benchmarks! {
 	where_clause {
 		where
 			T::Assets: Inspect<<T as frame_system::Config>::AccountId, AssetId = u32>,
+			T::Uniques: nonfungibles::Create<<T as frame_system::Config>::AccountId>,
+			T::CollectionId: From<u32>,
 	}
 
 	register_reserve_asset {
@@ -49,5 +71,54 @@ benchmarks! {
 		assert_eq!(AssetRegistry::<T>::asset_id_multilocation(LOCAL_ASSET_ID), None);
 	}
 
+	register_reserve_collection {
+		let collection_multi_location = MultiLocation {
+			parents: 1,
+			interior: Junctions::X3(Parachain(Default::default()), PalletInstance(Default::default()), GeneralIndex(Default::default()))
+		};
+
+		create_collection::<T>(LOCAL_COLLECTION_ID.into());
+
+	}: _(RawOrigin::Root, LOCAL_COLLECTION_ID.into(), collection_multi_location.clone())
+	verify {
+		let read_collection_multi_location = AssetRegistry::<T>::collection_id_multilocation(T::CollectionId::from(LOCAL_COLLECTION_ID))
+			.expect("error reading CollectionIdMultiLocation");
+		assert_eq!(read_collection_multi_location, collection_multi_location);
+	}
+
+	unregister_reserve_collection {
+		let collection_multi_location = MultiLocation {
+			parents: 1,
+			interior: Junctions::X3(Parachain(Default::default()), PalletInstance(Default::default()), GeneralIndex(Default::default()))
+		};
+
+		create_collection::<T>(LOCAL_COLLECTION_ID.into());
+		assert_ok!(AssetRegistry::<T>::register_reserve_collection(RawOrigin::Root.into(), LOCAL_COLLECTION_ID.into(), collection_multi_location.clone()));
+
+	}: _(RawOrigin::Root, LOCAL_COLLECTION_ID.into())
+	verify {
+		assert_eq!(AssetRegistry::<T>::collection_id_multilocation(T::CollectionId::from(LOCAL_COLLECTION_ID)), None);
+	}
+
+	add_trusted_teleporter {
+		let asset_multi_location = MultiLocation::parent();
+		let teleporter = MultiLocation::parent();
+
+	}: _(RawOrigin::Root, asset_multi_location.clone(), teleporter.clone())
+	verify {
+		assert!(TrustedTeleporters::<T>::contains_key(&asset_multi_location, &teleporter));
+	}
+
+	remove_trusted_teleporter {
+		let asset_multi_location = MultiLocation::parent();
+		let teleporter = MultiLocation::parent();
+
+		assert_ok!(AssetRegistry::<T>::add_trusted_teleporter(RawOrigin::Root.into(), asset_multi_location.clone(), teleporter.clone()));
+
+	}: _(RawOrigin::Root, asset_multi_location.clone(), teleporter.clone())
+	verify {
+		assert!(!TrustedTeleporters::<T>::contains_key(&asset_multi_location, &teleporter));
+	}
+
 	impl_benchmark_test_suite!(AssetRegistry, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -15,15 +15,65 @@ mod benchmarking;
 pub mod weights;
 pub use weights::*;
 
+use frame_support::weights::Weight;
+use xcm::latest::MultiLocation;
+
+/// Hook called after a reserve asset has been registered.
+pub trait OnAssetRegistered<AssetId> {
+	/// React to `asset_id` being registered as the local derivative of `asset_multi_location`.
+	fn on_asset_registered(asset_id: AssetId, asset_multi_location: MultiLocation);
+
+	/// The maximum weight consumed by `on_asset_registered`.
+	fn on_asset_registered_weight() -> Weight;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl<AssetId: Clone> OnAssetRegistered<AssetId> for Tuple {
+	fn on_asset_registered(asset_id: AssetId, asset_multi_location: MultiLocation) {
+		for_tuples!( #( Tuple::on_asset_registered(asset_id.clone(), asset_multi_location.clone()); )* );
+	}
+
+	fn on_asset_registered_weight() -> Weight {
+		let mut weight = Weight::zero();
+		for_tuples!( #( weight = weight.saturating_add(Tuple::on_asset_registered_weight()); )* );
+		weight
+	}
+}
+
+/// Hook called after a reserve asset has been unregistered.
+pub trait OnAssetUnregistered<AssetId> {
+	/// React to `asset_id` no longer being the local derivative of `asset_multi_location`.
+	fn on_asset_unregistered(asset_id: AssetId, asset_multi_location: MultiLocation);
+
+	/// The maximum weight consumed by `on_asset_unregistered`.
+	fn on_asset_unregistered_weight() -> Weight;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl<AssetId: Clone> OnAssetUnregistered<AssetId> for Tuple {
+	fn on_asset_unregistered(asset_id: AssetId, asset_multi_location: MultiLocation) {
+		for_tuples!( #( Tuple::on_asset_unregistered(asset_id.clone(), asset_multi_location.clone()); )* );
+	}
+
+	fn on_asset_unregistered_weight() -> Weight {
+		let mut weight = Weight::zero();
+		for_tuples!( #( weight = weight.saturating_add(Tuple::on_asset_unregistered_weight()); )* );
+		weight
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
-	use frame_support::{pallet_prelude::*, traits::tokens::fungibles::Inspect};
+	use frame_support::{
+		pallet_prelude::*,
+		traits::tokens::{fungibles::Inspect, nonfungibles},
+	};
 	use frame_system::pallet_prelude::*;
 
 	use xcm::latest::{
 		Junction::{GeneralIndex, PalletInstance, Parachain},
-		Junctions, MultiLocation,
+		Junctions,
 	};
 
 	#[pallet::pallet]
@@ -38,6 +88,15 @@ pub mod pallet {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		type ReserveAssetModifierOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 		type Assets: Inspect<Self::AccountId>;
+		/// Handler called after a reserve asset is registered.
+		type OnAssetRegistered: OnAssetRegistered<AssetIdOf<Self>>;
+		/// Handler called after a reserve asset is unregistered.
+		type OnAssetUnregistered: OnAssetUnregistered<AssetIdOf<Self>>;
+		/// Identifier of the non-fungible collections that can be registered.
+		type CollectionId: Member + Parameter + MaxEncodedLen + Copy;
+		type Uniques: nonfungibles::Inspect<Self::AccountId, CollectionId = Self::CollectionId>;
+		/// The origin that's allowed to add and remove trusted teleporters.
+		type TeleporterModifierOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 		type WeightInfo: WeightInfo;
 	}
 
@@ -51,11 +110,48 @@ pub mod pallet {
 	pub type AssetMultiLocationId<T: Config> =
 		StorageMap<_, Blake2_128Concat, MultiLocation, AssetIdOf<T>>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn collection_id_multilocation)]
+	pub type CollectionIdMultiLocation<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::CollectionId, MultiLocation>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn collection_multilocation_id)]
+	pub type CollectionMultiLocationId<T: Config> =
+		StorageMap<_, Blake2_128Concat, MultiLocation, T::CollectionId>;
+
+	/// Origins trusted to teleport the assets identified by their `MultiLocation`.
+	#[pallet::storage]
+	pub type TrustedTeleporters<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, MultiLocation, Blake2_128Concat, MultiLocation, ()>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		ReserveAssetRegistered { asset_id: AssetIdOf<T>, asset_multi_location: MultiLocation },
-		ReserveAssetUnregistered { asset_id: AssetIdOf<T>, asset_multi_location: MultiLocation },
+		ReserveAssetRegistered {
+			asset_id: AssetIdOf<T>,
+			asset_multi_location: MultiLocation,
+		},
+		ReserveAssetUnregistered {
+			asset_id: AssetIdOf<T>,
+			asset_multi_location: MultiLocation,
+		},
+		ReserveCollectionRegistered {
+			collection_id: T::CollectionId,
+			collection_multi_location: MultiLocation,
+		},
+		ReserveCollectionUnregistered {
+			collection_id: T::CollectionId,
+			collection_multi_location: MultiLocation,
+		},
+		TrustedTeleporterAdded {
+			asset_multi_location: MultiLocation,
+			teleporter: MultiLocation,
+		},
+		TrustedTeleporterRemoved {
+			asset_multi_location: MultiLocation,
+			teleporter: MultiLocation,
+		},
 	}
 
 	#[pallet::error]
@@ -68,12 +164,27 @@ pub mod pallet {
 		AssetIsNotRegistered,
 		/// Invalid MultiLocation
 		WrongMultiLocation,
+		/// The Collection ID is already registered
+		CollectionAlreadyRegistered,
+		/// The Collection ID does not exist
+		CollectionDoesNotExist,
+		/// The Collection ID is not registered
+		CollectionIsNotRegistered,
+		/// The MultiLocation is already registered for another Collection ID
+		CollectionMultiLocationAlreadyRegistered,
+		/// The teleporter is already trusted for the asset
+		TeleporterAlreadyTrusted,
+		/// The teleporter is not trusted for the asset
+		TeleporterIsNotTrusted,
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		#[pallet::call_index(0)]
-		#[pallet::weight(<T as pallet::Config>::WeightInfo::register_reserve_asset())]
+		#[pallet::weight(
+			<T as pallet::Config>::WeightInfo::register_reserve_asset()
+				.saturating_add(T::OnAssetRegistered::on_asset_registered_weight())
+		)]
 		pub fn register_reserve_asset(
 			origin: OriginFor<T>,
 			asset_id: AssetIdOf<T>,
@@ -91,14 +202,8 @@ pub mod pallet {
 			);
 
 			// verify MultiLocation is valid
-			let parents_multi_location_ok = { asset_multi_location.parents == 1 };
-			let junctions_multi_location_ok = matches!(
-				asset_multi_location.interior,
-				Junctions::X3(Parachain(_), PalletInstance(_), GeneralIndex(_))
-			);
-
 			ensure!(
-				parents_multi_location_ok && junctions_multi_location_ok,
+				Self::is_valid_reserve_location(&asset_multi_location),
 				Error::<T>::WrongMultiLocation
 			);
 
@@ -106,13 +211,18 @@ pub mod pallet {
 			AssetIdMultiLocation::<T>::insert(asset_id, &asset_multi_location);
 			AssetMultiLocationId::<T>::insert(&asset_multi_location, asset_id);
 
+			T::OnAssetRegistered::on_asset_registered(asset_id, asset_multi_location.clone());
+
 			Self::deposit_event(Event::ReserveAssetRegistered { asset_id, asset_multi_location });
 
 			Ok(())
 		}
 
 		#[pallet::call_index(1)]
-		#[pallet::weight(<T as pallet::Config>::WeightInfo::unregister_reserve_asset())]
+		#[pallet::weight(
+			<T as pallet::Config>::WeightInfo::unregister_reserve_asset()
+				.saturating_add(T::OnAssetUnregistered::on_asset_unregistered_weight())
+		)]
 		pub fn unregister_reserve_asset(
 			origin: OriginFor<T>,
 			asset_id: AssetIdOf<T>,
@@ -127,9 +237,123 @@ pub mod pallet {
 			AssetIdMultiLocation::<T>::remove(asset_id);
 			AssetMultiLocationId::<T>::remove(&asset_multi_location);
 
+			T::OnAssetUnregistered::on_asset_unregistered(asset_id, asset_multi_location.clone());
+
 			Self::deposit_event(Event::ReserveAssetUnregistered { asset_id, asset_multi_location });
 			Ok(())
 		}
+
+		#[pallet::call_index(2)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::register_reserve_collection())]
+		pub fn register_reserve_collection(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			collection_multi_location: MultiLocation,
+		) -> DispatchResult {
+			T::ReserveAssetModifierOrigin::ensure_origin(origin)?;
+
+			// verify collection exists on pallet-uniques
+			ensure!(Self::collection_exists(&collection_id), Error::<T>::CollectionDoesNotExist);
+
+			// verify collection is not yet registered
+			ensure!(
+				!CollectionIdMultiLocation::<T>::contains_key(collection_id),
+				Error::<T>::CollectionAlreadyRegistered
+			);
+
+			// verify MultiLocation is not yet registered
+			ensure!(
+				!CollectionMultiLocationId::<T>::contains_key(&collection_multi_location),
+				Error::<T>::CollectionMultiLocationAlreadyRegistered
+			);
+
+			// verify MultiLocation is valid
+			ensure!(
+				Self::is_valid_reserve_location(&collection_multi_location),
+				Error::<T>::WrongMultiLocation
+			);
+
+			// register collection
+			CollectionIdMultiLocation::<T>::insert(collection_id, &collection_multi_location);
+			CollectionMultiLocationId::<T>::insert(&collection_multi_location, collection_id);
+
+			Self::deposit_event(Event::ReserveCollectionRegistered {
+				collection_id,
+				collection_multi_location,
+			});
+
+			Ok(())
+		}
+
+		#[pallet::call_index(3)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::unregister_reserve_collection())]
+		pub fn unregister_reserve_collection(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+		) -> DispatchResult {
+			T::ReserveAssetModifierOrigin::ensure_origin(origin)?;
+
+			// verify collection is registered
+			let collection_multi_location = CollectionIdMultiLocation::<T>::get(collection_id)
+				.ok_or(Error::<T>::CollectionIsNotRegistered)?;
+
+			// unregister collection
+			CollectionIdMultiLocation::<T>::remove(collection_id);
+			CollectionMultiLocationId::<T>::remove(&collection_multi_location);
+
+			Self::deposit_event(Event::ReserveCollectionUnregistered {
+				collection_id,
+				collection_multi_location,
+			});
+			Ok(())
+		}
+
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::add_trusted_teleporter())]
+		pub fn add_trusted_teleporter(
+			origin: OriginFor<T>,
+			asset_multi_location: MultiLocation,
+			teleporter: MultiLocation,
+		) -> DispatchResult {
+			T::TeleporterModifierOrigin::ensure_origin(origin)?;
+
+			// verify teleporter is not yet trusted
+			ensure!(
+				!TrustedTeleporters::<T>::contains_key(&asset_multi_location, &teleporter),
+				Error::<T>::TeleporterAlreadyTrusted
+			);
+
+			// trust teleporter
+			TrustedTeleporters::<T>::insert(&asset_multi_location, &teleporter, ());
+
+			Self::deposit_event(Event::TrustedTeleporterAdded { asset_multi_location, teleporter });
+			Ok(())
+		}
+
+		#[pallet::call_index(5)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::remove_trusted_teleporter())]
+		pub fn remove_trusted_teleporter(
+			origin: OriginFor<T>,
+			asset_multi_location: MultiLocation,
+			teleporter: MultiLocation,
+		) -> DispatchResult {
+			T::TeleporterModifierOrigin::ensure_origin(origin)?;
+
+			// verify teleporter is trusted
+			ensure!(
+				TrustedTeleporters::<T>::contains_key(&asset_multi_location, &teleporter),
+				Error::<T>::TeleporterIsNotTrusted
+			);
+
+			// stop trusting teleporter
+			TrustedTeleporters::<T>::remove(&asset_multi_location, &teleporter);
+
+			Self::deposit_event(Event::TrustedTeleporterRemoved {
+				asset_multi_location,
+				teleporter,
+			});
+			Ok(())
+		}
 	}
 
 	impl<T: Config> xcm_primitives::AssetMultiLocationGetter<AssetIdOf<T>> for Pallet<T> {
@@ -142,10 +366,45 @@ pub mod pallet {
 		}
 	}
 
+	impl<T: Config> xcm_primitives::CollectionMultiLocationGetter<T::CollectionId> for Pallet<T> {
+		fn get_collection_multi_location(collection_id: T::CollectionId) -> Option<MultiLocation> {
+			CollectionIdMultiLocation::<T>::get(collection_id)
+		}
+
+		fn get_collection_id(collection_multi_location: MultiLocation) -> Option<T::CollectionId> {
+			CollectionMultiLocationId::<T>::get(collection_multi_location)
+		}
+	}
+
+	impl<T: Config> xcm_primitives::TrustedTeleporterGetter for Pallet<T> {
+		fn is_trusted_teleporter(
+			asset_multi_location: &MultiLocation,
+			teleporter: &MultiLocation,
+		) -> bool {
+			TrustedTeleporters::<T>::contains_key(asset_multi_location, teleporter)
+		}
+	}
+
 	impl<T: Config> Pallet<T> {
 		// check if the asset exists
 		fn asset_exists(asset_id: AssetIdOf<T>) -> bool {
 			T::Assets::asset_exists(asset_id)
 		}
+
+		// check if the collection exists
+		fn collection_exists(collection_id: &T::CollectionId) -> bool {
+			<T::Uniques as nonfungibles::Inspect<T::AccountId>>::collection_owner(collection_id)
+				.is_some()
+		}
+
+		// reserve locations are expected to be in the form of
+		// `(1, X3(Parachain(_), PalletInstance(_), GeneralIndex(_)))`
+		fn is_valid_reserve_location(multi_location: &MultiLocation) -> bool {
+			multi_location.parents == 1 &&
+				matches!(
+					multi_location.interior,
+					Junctions::X3(Parachain(_), PalletInstance(_), GeneralIndex(_))
+				)
+		}
 	}
 }
@@ -1,11 +1,15 @@
 use crate as pallet_asset_registry;
-use frame_support::traits::{AsEnsureOriginWithArg, ConstU16, ConstU64, GenesisBuild};
+use frame_support::{
+	traits::{AsEnsureOriginWithArg, ConstU16, ConstU64, GenesisBuild},
+	weights::Weight,
+};
 use frame_system as system;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, ConstU32, IdentityLookup},
 };
+use xcm::latest::MultiLocation;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -14,6 +18,32 @@ frame_support::parameter_types! {
 	pub const StatemineParaIdInfo: u32 = 1000u32;
 	pub const StatemineAssetsInstanceInfo: u8 = 50u8;
 	pub const StatemineAssetIdInfo: u128 = 1u128;
+	pub const StatemineUniquesInstanceInfo: u8 = 51u8;
+	pub const StatemineCollectionIdInfo: u128 = 1u128;
+	pub static RegisteredAssets: Vec<(u32, MultiLocation)> = vec![];
+	pub static UnregisteredAssets: Vec<(u32, MultiLocation)> = vec![];
+}
+
+pub struct RecordRegistered;
+impl pallet_asset_registry::OnAssetRegistered<u32> for RecordRegistered {
+	fn on_asset_registered(asset_id: u32, asset_multi_location: MultiLocation) {
+		RegisteredAssets::mutate(|v| v.push((asset_id, asset_multi_location)));
+	}
+
+	fn on_asset_registered_weight() -> Weight {
+		Weight::from_ref_time(1_000)
+	}
+}
+
+pub struct RecordUnregistered;
+impl pallet_asset_registry::OnAssetUnregistered<u32> for RecordUnregistered {
+	fn on_asset_unregistered(asset_id: u32, asset_multi_location: MultiLocation) {
+		UnregisteredAssets::mutate(|v| v.push((asset_id, asset_multi_location)));
+	}
+
+	fn on_asset_unregistered_weight() -> Weight {
+		Weight::from_ref_time(1_000)
+	}
 }
 
 // Configure a mock runtime to test the pallet.
@@ -27,6 +57,7 @@ frame_support::construct_runtime!(
 		AssetRegistry: pallet_asset_registry::{Pallet, Call, Storage, Event<T>},
 		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Uniques: pallet_uniques::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -61,6 +92,11 @@ impl pallet_asset_registry::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type ReserveAssetModifierOrigin = frame_system::EnsureRoot<Self::AccountId>;
 	type Assets = Assets;
+	type OnAssetRegistered = RecordRegistered;
+	type OnAssetUnregistered = RecordUnregistered;
+	type CollectionId = u32;
+	type Uniques = Uniques;
+	type TeleporterModifierOrigin = frame_system::EnsureRoot<Self::AccountId>;
 	type WeightInfo = pallet_asset_registry::weights::SubstrateWeight<Test>;
 }
 
@@ -99,7 +135,29 @@ impl pallet_assets::Config for Test {
 	type CallbackHandle = ();
 }
 
+impl pallet_uniques::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type CollectionId = u32;
+	type ItemId = u32;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<u64>;
+	type CreateOrigin = AsEnsureOriginWithArg<frame_system::EnsureSigned<u64>>;
+	type Locker = ();
+	type CollectionDeposit = ConstU64<2>;
+	type ItemDeposit = ConstU64<1>;
+	type MetadataDepositBase = ConstU64<1>;
+	type AttributeDepositBase = ConstU64<1>;
+	type DepositPerByte = ConstU64<1>;
+	type StringLimit = ConstU32<50>;
+	type KeyLimit = ConstU32<50>;
+	type ValueLimit = ConstU32<50>;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type Helper = ();
+}
+
 pub const LOCAL_ASSET_ID: u32 = 10;
+pub const LOCAL_COLLECTION_ID: u32 = 10;
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -120,5 +178,11 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 		],
 	};
 	config.assimilate_storage(&mut storage).unwrap();
-	storage.into()
+
+	let mut ext: sp_io::TestExternalities = storage.into();
+	ext.execute_with(|| {
+		Uniques::force_create(RuntimeOrigin::root(), LOCAL_COLLECTION_ID, 0, true)
+			.expect("error creating collection");
+	});
+	ext
 }
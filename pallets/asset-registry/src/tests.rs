@@ -1,6 +1,8 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{mock::*, Error, OnAssetRegistered, OnAssetUnregistered};
+use frame_support::{assert_noop, assert_ok, weights::Weight};
 use xcm::latest::prelude::*;
+use xcm_executor::traits::FilterAssetLocation;
+use xcm_primitives::IsTrustedTeleporter;
 
 #[test]
 fn register_reserve_asset_works() {
@@ -73,3 +75,247 @@ fn unregister_reserve_asset_works() {
 		);
 	});
 }
+
+#[test]
+fn asset_registry_hooks_are_called() {
+	new_test_ext().execute_with(|| {
+		let statemine_asset_multi_location = MultiLocation {
+			parents: 1,
+			interior: X3(
+				Parachain(StatemineParaIdInfo::get()),
+				PalletInstance(StatemineAssetsInstanceInfo::get()),
+				GeneralIndex(StatemineAssetIdInfo::get()),
+			),
+		};
+
+		assert_ok!(AssetRegistry::register_reserve_asset(
+			RuntimeOrigin::root(),
+			LOCAL_ASSET_ID,
+			statemine_asset_multi_location.clone(),
+		));
+		assert_eq!(
+			RegisteredAssets::get(),
+			vec![(LOCAL_ASSET_ID, statemine_asset_multi_location.clone())]
+		);
+		assert!(UnregisteredAssets::get().is_empty());
+
+		assert_ok!(AssetRegistry::unregister_reserve_asset(RuntimeOrigin::root(), LOCAL_ASSET_ID));
+		assert_eq!(
+			UnregisteredAssets::get(),
+			vec![(LOCAL_ASSET_ID, statemine_asset_multi_location)]
+		);
+	});
+}
+
+#[test]
+fn tuples_of_hooks_call_each_hook() {
+	new_test_ext().execute_with(|| {
+		type Registered = (RecordRegistered, RecordRegistered);
+		type Unregistered = (RecordUnregistered, RecordUnregistered);
+		let location = MultiLocation { parents: 1, interior: X1(Parachain(1000)) };
+
+		Registered::on_asset_registered(LOCAL_ASSET_ID, location.clone());
+		Unregistered::on_asset_unregistered(LOCAL_ASSET_ID, location.clone());
+
+		assert_eq!(
+			RegisteredAssets::get(),
+			vec![(LOCAL_ASSET_ID, location.clone()), (LOCAL_ASSET_ID, location.clone())]
+		);
+		assert_eq!(
+			UnregisteredAssets::get(),
+			vec![(LOCAL_ASSET_ID, location.clone()), (LOCAL_ASSET_ID, location)]
+		);
+		assert_eq!(Registered::on_asset_registered_weight(), Weight::from_ref_time(2_000));
+		assert_eq!(Unregistered::on_asset_unregistered_weight(), Weight::from_ref_time(2_000));
+		assert_eq!(<() as OnAssetRegistered<u32>>::on_asset_registered_weight(), Weight::zero());
+	});
+}
+
+#[test]
+fn register_reserve_collection_works() {
+	new_test_ext().execute_with(|| {
+		let statemine_collection_multi_location = MultiLocation {
+			parents: 1,
+			interior: X3(
+				Parachain(StatemineParaIdInfo::get()),
+				PalletInstance(StatemineUniquesInstanceInfo::get()),
+				GeneralIndex(StatemineCollectionIdInfo::get()),
+			),
+		};
+
+		assert_ok!(AssetRegistry::register_reserve_collection(
+			RuntimeOrigin::root(),
+			LOCAL_COLLECTION_ID,
+			statemine_collection_multi_location.clone(),
+		));
+
+		let read_collection_multi_location =
+			AssetRegistry::collection_id_multilocation(LOCAL_COLLECTION_ID)
+				.expect("error reading CollectionIdMultiLocation");
+		assert_eq!(read_collection_multi_location, statemine_collection_multi_location);
+
+		let read_collection_id =
+			AssetRegistry::collection_multilocation_id(&statemine_collection_multi_location)
+				.expect("error reading CollectionMultiLocationId");
+		assert_eq!(read_collection_id, LOCAL_COLLECTION_ID);
+
+		assert_noop!(
+			AssetRegistry::register_reserve_collection(
+				RuntimeOrigin::root(),
+				LOCAL_COLLECTION_ID,
+				statemine_collection_multi_location.clone(),
+			),
+			Error::<Test>::CollectionAlreadyRegistered
+		);
+
+		assert_noop!(
+			AssetRegistry::register_reserve_collection(
+				RuntimeOrigin::root(),
+				LOCAL_COLLECTION_ID + 1,
+				statemine_collection_multi_location,
+			),
+			Error::<Test>::CollectionDoesNotExist
+		);
+	});
+}
+
+#[test]
+fn register_reserve_collection_rejects_registered_multilocation() {
+	new_test_ext().execute_with(|| {
+		let statemine_collection_multi_location = MultiLocation {
+			parents: 1,
+			interior: X3(
+				Parachain(StatemineParaIdInfo::get()),
+				PalletInstance(StatemineUniquesInstanceInfo::get()),
+				GeneralIndex(StatemineCollectionIdInfo::get()),
+			),
+		};
+		assert_ok!(Uniques::force_create(RuntimeOrigin::root(), LOCAL_COLLECTION_ID + 1, 0, true));
+		assert_ok!(AssetRegistry::register_reserve_collection(
+			RuntimeOrigin::root(),
+			LOCAL_COLLECTION_ID,
+			statemine_collection_multi_location.clone(),
+		));
+
+		assert_noop!(
+			AssetRegistry::register_reserve_collection(
+				RuntimeOrigin::root(),
+				LOCAL_COLLECTION_ID + 1,
+				statemine_collection_multi_location.clone(),
+			),
+			Error::<Test>::CollectionMultiLocationAlreadyRegistered
+		);
+		assert_eq!(
+			AssetRegistry::collection_multilocation_id(&statemine_collection_multi_location),
+			Some(LOCAL_COLLECTION_ID)
+		);
+	});
+}
+
+#[test]
+fn unregister_reserve_collection_works() {
+	new_test_ext().execute_with(|| {
+		let statemine_collection_multi_location = MultiLocation {
+			parents: 1,
+			interior: X3(
+				Parachain(StatemineParaIdInfo::get()),
+				PalletInstance(StatemineUniquesInstanceInfo::get()),
+				GeneralIndex(StatemineCollectionIdInfo::get()),
+			),
+		};
+
+		assert_noop!(
+			AssetRegistry::unregister_reserve_collection(
+				RuntimeOrigin::root(),
+				LOCAL_COLLECTION_ID
+			),
+			Error::<Test>::CollectionIsNotRegistered
+		);
+
+		assert_ok!(AssetRegistry::register_reserve_collection(
+			RuntimeOrigin::root(),
+			LOCAL_COLLECTION_ID,
+			statemine_collection_multi_location.clone(),
+		));
+
+		assert_ok!(AssetRegistry::unregister_reserve_collection(
+			RuntimeOrigin::root(),
+			LOCAL_COLLECTION_ID
+		));
+
+		assert!(AssetRegistry::collection_id_multilocation(LOCAL_COLLECTION_ID).is_none());
+		assert!(AssetRegistry::collection_multilocation_id(statemine_collection_multi_location)
+			.is_none());
+	});
+}
+
+#[test]
+fn add_trusted_teleporter_works() {
+	new_test_ext().execute_with(|| {
+		let relay_token: MultiAsset = (Parent, 100).into();
+		let statemine = MultiLocation::new(1, X1(Parachain(StatemineParaIdInfo::get())));
+
+		assert!(!IsTrustedTeleporter::<AssetRegistry>::filter_asset_location(
+			&relay_token,
+			&statemine
+		));
+
+		assert_ok!(AssetRegistry::add_trusted_teleporter(
+			RuntimeOrigin::root(),
+			MultiLocation::parent(),
+			statemine.clone(),
+		));
+
+		assert!(IsTrustedTeleporter::<AssetRegistry>::filter_asset_location(
+			&relay_token,
+			&statemine
+		));
+		assert!(!IsTrustedTeleporter::<AssetRegistry>::filter_asset_location(
+			&relay_token,
+			&MultiLocation::parent()
+		));
+
+		assert_noop!(
+			AssetRegistry::add_trusted_teleporter(
+				RuntimeOrigin::root(),
+				MultiLocation::parent(),
+				statemine,
+			),
+			Error::<Test>::TeleporterAlreadyTrusted
+		);
+	});
+}
+
+#[test]
+fn remove_trusted_teleporter_works() {
+	new_test_ext().execute_with(|| {
+		let relay_token: MultiAsset = (Parent, 100).into();
+		let statemine = MultiLocation::new(1, X1(Parachain(StatemineParaIdInfo::get())));
+
+		assert_noop!(
+			AssetRegistry::remove_trusted_teleporter(
+				RuntimeOrigin::root(),
+				MultiLocation::parent(),
+				statemine.clone(),
+			),
+			Error::<Test>::TeleporterIsNotTrusted
+		);
+
+		assert_ok!(AssetRegistry::add_trusted_teleporter(
+			RuntimeOrigin::root(),
+			MultiLocation::parent(),
+			statemine.clone(),
+		));
+
+		assert_ok!(AssetRegistry::remove_trusted_teleporter(
+			RuntimeOrigin::root(),
+			MultiLocation::parent(),
+			statemine.clone(),
+		));
+
+		assert!(!IsTrustedTeleporter::<AssetRegistry>::filter_asset_location(
+			&relay_token,
+			&statemine
+		));
+	});
+}
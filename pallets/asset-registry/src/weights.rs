@@ -35,6 +35,10 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn register_reserve_asset() -> Weight;
 	fn unregister_reserve_asset() -> Weight;
+	fn register_reserve_collection() -> Weight;
+	fn unregister_reserve_collection() -> Weight;
+	fn add_trusted_teleporter() -> Weight;
+	fn remove_trusted_teleporter() -> Weight;
 }
 
 /// Weight functions for `pallet_asset_registry`.
@@ -55,6 +59,33 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(2))
 	}
+	// Storage: Uniques Class (r:1 w:0)
+	// Storage: AssetRegistry CollectionIdMultiLocation (r:1 w:1)
+	// Storage: AssetRegistry CollectionMultiLocationId (r:0 w:1)
+	fn register_reserve_collection() -> Weight {
+		Weight::from_ref_time(18_710_000)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: AssetRegistry CollectionIdMultiLocation (r:1 w:1)
+	// Storage: AssetRegistry CollectionMultiLocationId (r:0 w:1)
+	fn unregister_reserve_collection() -> Weight {
+		Weight::from_ref_time(16_570_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: AssetRegistry TrustedTeleporters (r:1 w:1)
+	fn add_trusted_teleporter() -> Weight {
+		Weight::from_ref_time(15_240_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: AssetRegistry TrustedTeleporters (r:1 w:1)
+	fn remove_trusted_teleporter() -> Weight {
+		Weight::from_ref_time(15_080_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
 
 impl WeightInfo for () {
@@ -73,4 +104,31 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1))
 			.saturating_add(RocksDbWeight::get().writes(2))
 	}
+	// Storage: Uniques Class (r:1 w:0)
+	// Storage: AssetRegistry CollectionIdMultiLocation (r:1 w:1)
+	// Storage: AssetRegistry CollectionMultiLocationId (r:0 w:1)
+	fn register_reserve_collection() -> Weight {
+		Weight::from_ref_time(18_710_000)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	// Storage: AssetRegistry CollectionIdMultiLocation (r:1 w:1)
+	// Storage: AssetRegistry CollectionMultiLocationId (r:0 w:1)
+	fn unregister_reserve_collection() -> Weight {
+		Weight::from_ref_time(16_570_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	// Storage: AssetRegistry TrustedTeleporters (r:1 w:1)
+	fn add_trusted_teleporter() -> Weight {
+		Weight::from_ref_time(15_240_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	// Storage: AssetRegistry TrustedTeleporters (r:1 w:1)
+	fn remove_trusted_teleporter() -> Weight {
+		Weight::from_ref_time(15_080_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
 }
@@ -0,0 +1,134 @@
+//! RPC querying the ratings of `pallet_chess_rating`.
+//!
+//! Time controls are given by the index of their `MatchStyle` variant, i.e. `0` for bullet, `1`
+//! for blitz, `2` for rapid and `3` for daily.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::{Codec, Decode};
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_chess_rating_runtime_api::ChessRatingApi as ChessRatingRuntimeApi;
+
+#[rpc(client, server)]
+pub trait ChessRatingApi<BlockHash, AccountId, BlockNumber> {
+	/// The rating of `who` for the time control `style`.
+	#[method(name = "chessRating_rating")]
+	fn rating(&self, who: AccountId, style: u8, at: Option<BlockHash>) -> RpcResult<u32>;
+
+	/// The last ratings of `who` for the time control `style`, and the block they were reached
+	/// at, oldest first.
+	#[method(name = "chessRating_history")]
+	fn history(
+		&self,
+		who: AccountId,
+		style: u8,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(BlockNumber, u32)>>;
+
+	/// The first `count` players of the leaderboard of the time control `style`, best first.
+	#[method(name = "chessRating_leaderboard")]
+	fn leaderboard(
+		&self,
+		style: u8,
+		count: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(AccountId, u32)>>;
+}
+
+/// Provides the ratings of `pallet_chess_rating`.
+pub struct ChessRating<C, Block, MatchStyle> {
+	client: Arc<C>,
+	_marker: PhantomData<(Block, MatchStyle)>,
+}
+
+impl<C, Block, MatchStyle> ChessRating<C, Block, MatchStyle> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// The runtime API call failed.
+const RUNTIME_ERROR: i32 = 1;
+/// The time control is unknown.
+const INVALID_STYLE: i32 = 2;
+
+fn runtime_error(error: impl ToString) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Unable to query the ratings.",
+		Some(error.to_string()),
+	))
+	.into()
+}
+
+/// The `MatchStyle` of index `style`.
+fn match_style<MatchStyle: Decode>(style: u8) -> RpcResult<MatchStyle> {
+	MatchStyle::decode(&mut &[style][..]).map_err(|_| {
+		CallError::Custom(ErrorObject::owned(
+			INVALID_STYLE,
+			"Unknown time control.",
+			Some(style.to_string()),
+		))
+		.into()
+	})
+}
+
+impl<C, Block, MatchStyle, AccountId, BlockNumber>
+	ChessRatingApiServer<<Block as BlockT>::Hash, AccountId, BlockNumber>
+	for ChessRating<C, Block, MatchStyle>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ChessRatingRuntimeApi<Block, AccountId, MatchStyle, BlockNumber>,
+	MatchStyle: Codec + Send + Sync + 'static,
+	AccountId: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	BlockNumber: Codec + Serialize + Send + Sync + 'static,
+{
+	fn rating(
+		&self,
+		who: AccountId,
+		style: u8,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<u32> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.rating(at, who, match_style(style)?)
+			.map_err(runtime_error)
+	}
+
+	fn history(
+		&self,
+		who: AccountId,
+		style: u8,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(BlockNumber, u32)>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.history(at, who, match_style(style)?)
+			.map_err(runtime_error)
+	}
+
+	fn leaderboard(
+		&self,
+		style: u8,
+		count: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(AccountId, u32)>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.leaderboard(at, match_style(style)?, count)
+			.map_err(runtime_error)
+	}
+}
@@ -0,0 +1,22 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// Queries the Elo ratings of `pallet_chess_rating`, by time control.
+	pub trait ChessRatingApi<AccountId, MatchStyle, BlockNumber> where
+		AccountId: Codec,
+		MatchStyle: Codec,
+		BlockNumber: Codec,
+	{
+		/// The rating of `who`, which is the initial rating if they haven't played a rated match.
+		fn rating(who: AccountId, style: MatchStyle) -> u32;
+
+		/// The last ratings of `who`, and the block they were reached at, oldest first.
+		fn history(who: AccountId, style: MatchStyle) -> Vec<(BlockNumber, u32)>;
+
+		/// The first `count` players of the leaderboard, best first.
+		fn leaderboard(style: MatchStyle, count: u32) -> Vec<(AccountId, u32)>;
+	}
+}
@@ -0,0 +1,581 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Elo ratings of `pallet_chess` players, per time control, and rated matchmaking.
+//!
+//! Rated matches are only made through matchmaking: players [`Pallet::seek`] an opponent for a
+//! time control and a bet, and are paired with the first seeker of the same bet whose rating is
+//! within `MaxRatingGap` of theirs. The second seeker creates the match against the first with
+//! `pallet_chess` and [`Pallet::start_match`]es it, and the first joins it with
+//! [`Pallet::join_match`].
+//!
+//! Seeks expire after `SeekTimeout` blocks, and so do pairings which the first seeker didn't join.
+//! Expired pairings can then be cleared by anyone with [`Pallet::clear_pairing`], or by the
+//! challenger at any time, e.g. once they aborted the match to get their stake back.
+//!
+//! Rated matches are checked in `on_idle`, as weight allows, and their players' ratings are
+//! updated once they are won or drawn. Matches which end otherwise, e.g. aborted, are unrated.
+//! The last `MaxHistory` ratings of each player are kept, and so are leaderboards of the best
+//! rated players of each time control, as ratings are updated. Both are served by the
+//! `ChessRatingApi` runtime API.
+//!
+//! Leaderboards are approximate: they keep up to `MaxLeaderboard` players, and players who drop
+//! off are only back on once their rating is updated again. A player on a full leaderboard whose
+//! rating drops stays on it, even below players who dropped off before.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+pub use weights::*;
+
+/// The expected score of a player rated `25 * i` points above their opponent, in per mille.
+const EXPECTED_SCORES: [i64; 17] =
+	[500, 536, 571, 606, 640, 673, 703, 733, 760, 785, 808, 830, 849, 867, 882, 896, 909];
+
+/// The expected score of a player rated `diff` points above their opponent, in per mille.
+///
+/// As in FIDE ratings, differences of more than 400 points count as 400.
+pub fn expected_score(diff: i64) -> i64 {
+	if diff < 0 {
+		return 1000 - expected_score(-diff);
+	}
+	let diff = diff.min(400);
+	let (index, rest) = ((diff / 25) as usize, diff % 25);
+	let low = EXPECTED_SCORES[index];
+	let high = EXPECTED_SCORES.get(index + 1).copied().unwrap_or(low);
+	low + (high - low) * rest / 25
+}
+
+/// The change of the rating of a player rated `rating` who scored `score` per mille against an
+/// opponent rated `opponent_rating`, rounded to the nearest point.
+pub fn rating_change(rating: u32, opponent_rating: u32, score: i64, k_factor: u32) -> i64 {
+	let change =
+		i64::from(k_factor) * (score - expected_score(rating as i64 - opponent_rating as i64));
+	(change + change.signum() * 500) / 1000
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{fungibles::Inspect, Contains, UnfilteredDispatchable},
+	};
+	use frame_system::pallet_prelude::*;
+	use pallet_chess::{MatchState, MatchStyle, WeightInfo as ChessWeightInfo};
+	use sp_runtime::traits::{Saturating, UniqueSaturatedInto};
+	use sp_std::vec::Vec;
+
+	pub type AssetIdOf<T> = <<T as pallet_chess::Config>::Assets as Inspect<
+		<T as frame_system::Config>::AccountId,
+	>>::AssetId;
+	pub type AssetBalanceOf<T> = <T as pallet_chess::Config>::AssetBalance;
+	pub type SeekOf<T> = Seek<
+		<T as frame_system::Config>::AccountId,
+		AssetIdOf<T>,
+		AssetBalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+	pub type PairingOf<T> = Pairing<
+		<T as frame_system::Config>::Hash,
+		AssetIdOf<T>,
+		AssetBalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct PlayerRating {
+		pub rating: u32,
+		/// The number of rated matches played.
+		pub games: u32,
+	}
+
+	/// A player waiting for an opponent.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Seek<AccountId, AssetId, Balance, BlockNumber> {
+		pub player: AccountId,
+		/// The rating of the player when they started seeking.
+		pub rating: u32,
+		pub bet_asset_id: AssetId,
+		pub bet_amount: Balance,
+		/// The block from which the seek is ignored.
+		pub expires_at: BlockNumber,
+	}
+
+	/// Players paired by matchmaking, whose opponent hasn't joined their rated match yet.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Pairing<Hash, AssetId, Balance, BlockNumber> {
+		pub style: MatchStyle,
+		pub bet_asset_id: AssetId,
+		pub bet_amount: Balance,
+		/// The match created by the challenger, once they started it.
+		pub match_id: Option<Hash>,
+		/// The block from which the match can no longer be started or joined.
+		pub expires_at: BlockNumber,
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_chess::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The rating of players who haven't played a rated match.
+		#[pallet::constant]
+		type InitialRating: Get<u32>;
+		/// The maximum change of rating of a match.
+		#[pallet::constant]
+		type KFactor: Get<u32>;
+		/// The maximum difference of rating between players paired by matchmaking.
+		#[pallet::constant]
+		type MaxRatingGap: Get<u32>;
+		/// The maximum number of players seeking an opponent for each time control.
+		#[pallet::constant]
+		type MaxSeeks: Get<u32>;
+		/// The maximum number of rated matches being played.
+		#[pallet::constant]
+		type MaxOngoingMatches: Get<u32>;
+		/// The number of past ratings kept for each player and time control.
+		#[pallet::constant]
+		type MaxHistory: Get<u32>;
+		/// The maximum number of players kept on the leaderboard of each time control.
+		#[pallet::constant]
+		type MaxLeaderboard: Get<u32>;
+		/// The number of blocks seeks are open for, and paired players have to join their match.
+		#[pallet::constant]
+		type SeekTimeout: Get<Self::BlockNumber>;
+		/// Filters the `pallet_chess` calls this pallet dispatches for players of rated matches,
+		/// which bypass `BaseCallFilter`.
+		type ChessCallFilter: Contains<pallet_chess::Call<Self>>;
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The ratings of players who played rated matches, by time control.
+	#[pallet::storage]
+	pub type Ratings<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, MatchStyle, Blake2_128Concat, T::AccountId, PlayerRating>;
+
+	/// The last `MaxHistory` ratings of players, and the block they were reached at.
+	#[pallet::storage]
+	#[pallet::getter(fn history)]
+	pub type History<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		MatchStyle,
+		BoundedVec<(T::BlockNumber, u32), T::MaxHistory>,
+		ValueQuery,
+	>;
+
+	/// The players seeking an opponent, by time control, in order of arrival.
+	#[pallet::storage]
+	#[pallet::getter(fn seeks)]
+	pub type Seeks<T: Config> =
+		StorageMap<_, Twox64Concat, MatchStyle, BoundedVec<SeekOf<T>, T::MaxSeeks>, ValueQuery>;
+
+	/// The players paired by matchmaking, by challenger and opponent, until the opponent joins
+	/// their match or the pairing is cleared.
+	#[pallet::storage]
+	pub type Pairings<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		PairingOf<T>,
+	>;
+
+	/// The rated matches being played.
+	#[pallet::storage]
+	#[pallet::getter(fn ongoing_matches)]
+	pub type OngoingMatches<T: Config> =
+		StorageValue<_, BoundedVec<T::Hash, T::MaxOngoingMatches>, ValueQuery>;
+
+	/// Up to `MaxLeaderboard` of the best rated players and their rating, by time control, best
+	/// first. See the pallet docs for how approximate it is.
+	#[pallet::storage]
+	pub type Leaderboards<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		MatchStyle,
+		BoundedVec<(T::AccountId, u32), T::MaxLeaderboard>,
+		ValueQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		SeekPosted {
+			who: T::AccountId,
+			style: MatchStyle,
+		},
+		SeekCancelled {
+			who: T::AccountId,
+			style: MatchStyle,
+		},
+		/// `challenger` is paired with `opponent`, and must create and start their rated match.
+		Paired {
+			challenger: T::AccountId,
+			opponent: T::AccountId,
+			style: MatchStyle,
+		},
+		PairingCleared {
+			challenger: T::AccountId,
+			opponent: T::AccountId,
+		},
+		/// The challenger of a pairing started the rated match `match_id`, which its opponent
+		/// must join.
+		RatedMatchCreated {
+			match_id: T::Hash,
+		},
+		RatedMatchStarted {
+			match_id: T::Hash,
+		},
+		/// The rated match `match_id` is no longer stored, and is left unrated.
+		MatchUnrated {
+			match_id: T::Hash,
+		},
+		RatingUpdated {
+			who: T::AccountId,
+			style: MatchStyle,
+			match_id: T::Hash,
+			rating: u32,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The player is already seeking an opponent for the time control
+		AlreadySeeking,
+		/// The player is not seeking an opponent for the time control
+		NotSeeking,
+		/// `MaxSeeks` players are already seeking an opponent for the time control
+		TooManySeeks,
+		/// The match does not exist
+		UnknownMatch,
+		/// The match was not started by matchmaking against the signer
+		NotPaired,
+		/// The match is not waiting for the paired opponent, or differs from the seeks paired
+		WrongMatch,
+		/// The challenger already started a match for the pairing
+		AlreadyStarted,
+		/// The opponent didn't join the match within `SeekTimeout` blocks
+		PairingExpired,
+		/// The pairing has not expired and the signer is not its challenger
+		PairingNotExpired,
+		/// `MaxOngoingMatches` rated matches are already being played
+		TooManyOngoingMatches,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			if !<T as pallet::Config>::WeightInfo::on_idle(0).all_lte(remaining_weight) {
+				return Weight::zero();
+			}
+			let mut ongoing = OngoingMatches::<T>::get();
+			let len = ongoing.len() as u32;
+			let mut checked = 0;
+			while checked < len &&
+				<T as pallet::Config>::WeightInfo::on_idle(checked + 1).all_lte(remaining_weight)
+			{
+				checked += 1;
+			}
+			if checked == 0 {
+				return <T as pallet::Config>::WeightInfo::on_idle(0);
+			}
+
+			// rotate the first match checked, so that all are when weight is short
+			let start: u32 = n.unique_saturated_into() % len;
+			let finished: Vec<T::Hash> = (start..start + checked)
+				.map(|i| ongoing[(i % len) as usize])
+				.filter(|match_id| Self::try_rate(*match_id))
+				.collect();
+			if !finished.is_empty() {
+				ongoing.retain(|match_id| !finished.contains(match_id));
+				OngoingMatches::<T>::put(ongoing);
+			}
+
+			<T as pallet::Config>::WeightInfo::on_idle(checked)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Seek an opponent for a rated match of time control `style`, betting `bet_amount` of
+		/// `bet_asset_id`.
+		///
+		/// If a player of a close enough rating is seeking the same bet, the signer is paired with
+		/// them, and must create the match against them and [`Pallet::start_match`] it within
+		/// `SeekTimeout` blocks. Otherwise, the signer waits for an opponent for `SeekTimeout`
+		/// blocks.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::seek())]
+		pub fn seek(
+			origin: OriginFor<T>,
+			style: MatchStyle,
+			bet_asset_id: AssetIdOf<T>,
+			bet_amount: AssetBalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let rating = Self::rating(&who, &style);
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut seeks = Seeks::<T>::get(&style);
+			seeks.retain(|seek| seek.expires_at > now);
+			let expires_at = now.saturating_add(T::SeekTimeout::get());
+			ensure!(!seeks.iter().any(|seek| seek.player == who), Error::<T>::AlreadySeeking);
+
+			let opponent = seeks.iter().position(|seek| {
+				seek.bet_asset_id == bet_asset_id &&
+					seek.bet_amount == bet_amount &&
+					seek.rating.abs_diff(rating) <= T::MaxRatingGap::get()
+			});
+			match opponent {
+				Some(index) => {
+					let opponent = seeks.remove(index).player;
+					let pairing = Pairing {
+						style: style.clone(),
+						bet_asset_id,
+						bet_amount,
+						match_id: None,
+						expires_at,
+					};
+					Pairings::<T>::insert(&who, &opponent, pairing);
+					Seeks::<T>::insert(&style, seeks);
+					Self::deposit_event(Event::Paired { challenger: who, opponent, style });
+				},
+				None => {
+					let seek =
+						Seek { player: who.clone(), rating, bet_asset_id, bet_amount, expires_at };
+					seeks.try_push(seek).map_err(|_| Error::<T>::TooManySeeks)?;
+					Seeks::<T>::insert(&style, seeks);
+					Self::deposit_event(Event::SeekPosted { who, style });
+				},
+			}
+			Ok(())
+		}
+
+		/// Stop seeking an opponent for time control `style`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::cancel_seek())]
+		pub fn cancel_seek(origin: OriginFor<T>, style: MatchStyle) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Seeks::<T>::try_mutate(&style, |seeks| {
+				let index = seeks
+					.iter()
+					.position(|seek| seek.player == who)
+					.ok_or(Error::<T>::NotSeeking)?;
+				seeks.remove(index);
+				Ok::<_, Error<T>>(())
+			})?;
+
+			Self::deposit_event(Event::SeekCancelled { who, style });
+			Ok(())
+		}
+
+		/// Join the rated match `match_id`, started against the signer by matchmaking less than
+		/// `SeekTimeout` blocks ago, staking its bet.
+		#[pallet::call_index(2)]
+		#[pallet::weight(
+			<T as pallet::Config>::WeightInfo::join_match()
+				.saturating_add(<T as pallet_chess::Config>::WeightInfo::join_match())
+		)]
+		pub fn join_match(origin: OriginFor<T>, match_id: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let chess_match =
+				pallet_chess::Matches::<T>::get(match_id).ok_or(Error::<T>::UnknownMatch)?;
+			ensure!(chess_match.opponent == who, Error::<T>::NotPaired);
+			let pairing = Pairings::<T>::take(&chess_match.challenger, &who)
+				.filter(|pairing| pairing.match_id == Some(match_id))
+				.ok_or(Error::<T>::NotPaired)?;
+			ensure!(
+				pairing.expires_at > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::PairingExpired
+			);
+			OngoingMatches::<T>::try_append(match_id)
+				.map_err(|_| Error::<T>::TooManyOngoingMatches)?;
+
+			let join_match = pallet_chess::Call::<T>::join_match { match_id };
+			ensure!(
+				T::ChessCallFilter::contains(&join_match),
+				frame_system::Error::<T>::CallFiltered
+			);
+			join_match
+				.dispatch_bypass_filter(frame_system::RawOrigin::Signed(who).into())
+				.map_err(|e| e.error)?;
+
+			Self::deposit_event(Event::RatedMatchStarted { match_id });
+			Ok(())
+		}
+
+		/// Clear the pairing of `challenger` and `opponent`, if it expired or the signer is
+		/// `challenger`.
+		///
+		/// The match is not aborted: its challenger aborts it with `pallet_chess` to get their
+		/// stake back.
+		#[pallet::call_index(3)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::clear_pairing())]
+		pub fn clear_pairing(
+			origin: OriginFor<T>,
+			challenger: T::AccountId,
+			opponent: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let pairing =
+				Pairings::<T>::get(&challenger, &opponent).ok_or(Error::<T>::NotPaired)?;
+			ensure!(
+				who == challenger ||
+					pairing.expires_at <= frame_system::Pallet::<T>::block_number(),
+				Error::<T>::PairingNotExpired
+			);
+			Pairings::<T>::remove(&challenger, &opponent);
+
+			Self::deposit_event(Event::PairingCleared { challenger, opponent });
+			Ok(())
+		}
+
+		/// Start the rated match `match_id`, created by the signer with `pallet_chess` against
+		/// the player they are paired with, with the time control and bet of their seeks.
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::start_match())]
+		pub fn start_match(origin: OriginFor<T>, match_id: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let chess_match =
+				pallet_chess::Matches::<T>::get(match_id).ok_or(Error::<T>::UnknownMatch)?;
+			ensure!(chess_match.challenger == who, Error::<T>::NotPaired);
+			Pairings::<T>::try_mutate(&who, &chess_match.opponent, |pairing| {
+				let pairing = pairing.as_mut().ok_or(Error::<T>::NotPaired)?;
+				ensure!(pairing.match_id.is_none(), Error::<T>::AlreadyStarted);
+				ensure!(
+					pairing.expires_at > frame_system::Pallet::<T>::block_number(),
+					Error::<T>::PairingExpired
+				);
+				ensure!(
+					chess_match.style == pairing.style &&
+						chess_match.bet_asset_id == pairing.bet_asset_id &&
+						chess_match.bet_amount == pairing.bet_amount &&
+						matches!(chess_match.state, MatchState::AwaitingOpponent),
+					Error::<T>::WrongMatch
+				);
+				pairing.match_id = Some(match_id);
+				Ok::<_, Error<T>>(())
+			})?;
+
+			Self::deposit_event(Event::RatedMatchCreated { match_id });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The rating of `who` for time control `style`.
+		pub fn rating(who: &T::AccountId, style: &MatchStyle) -> u32 {
+			Self::player_rating(who, style).rating
+		}
+
+		fn player_rating(who: &T::AccountId, style: &MatchStyle) -> PlayerRating {
+			Ratings::<T>::get(style, who)
+				.unwrap_or(PlayerRating { rating: T::InitialRating::get(), games: 0 })
+		}
+
+		/// The first `count` players of the leaderboard of time control `style`, best first.
+		pub fn leaderboard(style: &MatchStyle, count: u32) -> Vec<(T::AccountId, u32)> {
+			let mut players = Leaderboards::<T>::get(style).into_inner();
+			players.truncate(count as usize);
+			players
+		}
+
+		/// Rate the match `match_id` if it ended, and return whether it did.
+		fn try_rate(match_id: T::Hash) -> bool {
+			let chess_match = match pallet_chess::Matches::<T>::get(match_id) {
+				Some(chess_match) => chess_match,
+				None => {
+					// the result is unknown
+					Self::deposit_event(Event::MatchUnrated { match_id });
+					return true;
+				},
+			};
+			let score = match chess_match.state {
+				MatchState::Won(ref winner) if *winner == chess_match.challenger => 1000,
+				MatchState::Won(_) => 0,
+				MatchState::Drawn => 500,
+				_ => return false,
+			};
+			Self::rate(
+				match_id,
+				&chess_match.style,
+				&chess_match.challenger,
+				&chess_match.opponent,
+				score,
+			);
+			true
+		}
+
+		/// Update the ratings of `challenger` and `opponent`, `challenger` having scored `score`
+		/// per mille.
+		fn rate(
+			match_id: T::Hash,
+			style: &MatchStyle,
+			challenger: &T::AccountId,
+			opponent: &T::AccountId,
+			score: i64,
+		) {
+			let challenger_rating = Self::player_rating(challenger, style);
+			let opponent_rating = Self::player_rating(opponent, style);
+			let change = rating_change(
+				challenger_rating.rating,
+				opponent_rating.rating,
+				score,
+				T::KFactor::get(),
+			);
+			Self::update_rating(match_id, style, challenger, challenger_rating, change);
+			Self::update_rating(match_id, style, opponent, opponent_rating, -change);
+		}
+
+		fn update_rating(
+			match_id: T::Hash,
+			style: &MatchStyle,
+			who: &T::AccountId,
+			mut player_rating: PlayerRating,
+			change: i64,
+		) {
+			player_rating.rating = (i64::from(player_rating.rating) + change).max(0) as u32;
+			player_rating.games.saturating_inc();
+			let rating = player_rating.rating;
+			Ratings::<T>::insert(style, who, player_rating);
+
+			History::<T>::mutate(who, style, |history| {
+				if !history.is_empty() && history.len() as u32 >= T::MaxHistory::get() {
+					history.remove(0);
+				}
+				let _ = history.try_push((frame_system::Pallet::<T>::block_number(), rating));
+			});
+
+			Leaderboards::<T>::mutate(style, |leaderboard| {
+				leaderboard.retain(|(player, _)| player != who);
+				let index = leaderboard
+					.iter()
+					.position(|(_, player_rating)| *player_rating < rating)
+					.unwrap_or(leaderboard.len());
+				// the last player drops off when it is full
+				let _ = leaderboard.force_insert_keep_left(index, (who.clone(), rating));
+			});
+
+			Self::deposit_event(Event::RatingUpdated {
+				who: who.clone(),
+				style: style.clone(),
+				match_id,
+				rating,
+			});
+		}
+	}
+}
@@ -0,0 +1,159 @@
+use crate as pallet_chess_rating;
+use frame_support::{
+	assert_ok, parameter_types,
+	traits::{AsEnsureOriginWithArg, ConstU128, ConstU16, ConstU64, ConstU8, Contains},
+	PalletId,
+};
+use frame_system::{EnsureRoot, EnsureSigned};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, ConstU32, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub const ALICE: u64 = 1;
+pub const BOB: u64 = 2;
+pub const CHARLIE: u64 = 3;
+pub const UNITS: u128 = 1_000_000_000_000;
+
+pub const XUSD: u32 = 1;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		Assets: pallet_assets,
+		Chess: pallet_chess,
+		ChessRating: pallet_chess_rating::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u128>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type Balance = u128;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU128<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+}
+
+impl pallet_assets::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = u128;
+	type AssetId = u32;
+	type AssetIdParameter = u32;
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<u64>>;
+	type ForceOrigin = EnsureRoot<u64>;
+	type AssetDeposit = ConstU128<1>;
+	type MetadataDepositBase = ConstU128<1>;
+	type MetadataDepositPerByte = ConstU128<1>;
+	type ApprovalDeposit = ConstU128<1>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+	type AssetAccountDeposit = ConstU128<1>;
+	type RemoveItemsLimit = ConstU32<1000>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+	type CallbackHandle = ();
+}
+
+parameter_types! {
+	pub const ChessPalletId: PalletId = PalletId(*b"subchess");
+	pub static ChessCallsPaused: bool = false;
+}
+
+impl pallet_chess::Config for Test {
+	type PalletId = ChessPalletId;
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_chess::weights::SubstrateWeight<Test>;
+	type Assets = Assets;
+	type AssetBalance = u128;
+	type BulletPeriod = ConstU64<10>;
+	type BlitzPeriod = ConstU64<50>;
+	type RapidPeriod = ConstU64<150>;
+	type DailyPeriod = ConstU64<14_400>;
+	type IncentiveShare = ConstU8<10>;
+}
+
+impl pallet_chess_rating::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type InitialRating = ConstU32<1200>;
+	type KFactor = ConstU32<32>;
+	type MaxRatingGap = ConstU32<200>;
+	type MaxSeeks = ConstU32<2>;
+	type MaxOngoingMatches = ConstU32<4>;
+	type MaxHistory = ConstU32<2>;
+	type MaxLeaderboard = ConstU32<2>;
+	type SeekTimeout = ConstU64<10>;
+	type ChessCallFilter = ChessCallFilter;
+	type WeightInfo = ();
+}
+
+pub struct ChessCallFilter;
+impl Contains<pallet_chess::Call<Test>> for ChessCallFilter {
+	fn contains(_: &pallet_chess::Call<Test>) -> bool {
+		!ChessCallsPaused::get()
+	}
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(ALICE, UNITS), (BOB, UNITS), (CHARLIE, UNITS)],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+
+	let mut ext: sp_io::TestExternalities = storage.into();
+	// events are not deposited on the genesis block
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), XUSD, ALICE, true, 1));
+		for who in [ALICE, BOB, CHARLIE] {
+			assert_ok!(Assets::mint(RuntimeOrigin::signed(ALICE), XUSD, who, 1_000 * UNITS));
+		}
+	});
+	ext
+}
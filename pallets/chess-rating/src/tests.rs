@@ -0,0 +1,489 @@
+use crate::{
+	expected_score, mock::*, rating_change, Error, Event, Leaderboards, OngoingMatches, Pairing,
+	Pairings, PlayerRating, Ratings,
+};
+use frame_support::{assert_noop, assert_ok, traits::Hooks, weights::Weight};
+use pallet_chess::{MatchState, MatchStyle};
+use sp_core::H256;
+
+const BET: u128 = 10 * UNITS;
+
+fn seek(who: u64) -> Result<(), sp_runtime::DispatchError> {
+	ChessRating::seek(RuntimeOrigin::signed(who), MatchStyle::Blitz, XUSD, BET)
+}
+
+fn set_rating(who: u64, rating: u32) {
+	Ratings::<Test>::insert(MatchStyle::Blitz, who, PlayerRating { rating, games: 10 });
+}
+
+/// Create the match of `challenger` against `opponent` for their pairing, and start it.
+fn create_paired_match(challenger: u64, opponent: u64) -> H256 {
+	assert_ok!(Chess::create_match(
+		RuntimeOrigin::signed(challenger),
+		opponent,
+		MatchStyle::Blitz,
+		XUSD,
+		BET
+	));
+	let match_id = awaiting_match(challenger, opponent);
+	assert_ok!(ChessRating::start_match(RuntimeOrigin::signed(challenger), match_id));
+	match_id
+}
+
+/// The match of `challenger` against `opponent` waiting for `opponent`.
+fn awaiting_match(challenger: u64, opponent: u64) -> H256 {
+	pallet_chess::Matches::<Test>::iter()
+		.find(|(_, chess_match)| {
+			chess_match.challenger == challenger &&
+				chess_match.opponent == opponent &&
+				matches!(chess_match.state, MatchState::AwaitingOpponent)
+		})
+		.unwrap()
+		.0
+}
+
+/// Pair `ALICE` and `BOB`, `BOB` being the challenger, and start the match.
+fn start_rated_match() -> H256 {
+	assert_ok!(seek(ALICE));
+	assert_ok!(seek(BOB));
+	let match_id = create_paired_match(BOB, ALICE);
+	assert_ok!(ChessRating::join_match(RuntimeOrigin::signed(ALICE), match_id));
+	match_id
+}
+
+fn end_match(match_id: H256, state: MatchState<u64>) {
+	pallet_chess::Matches::<Test>::mutate(match_id, |chess_match| {
+		chess_match.as_mut().unwrap().state = state;
+	});
+}
+
+fn idle(weight: Weight) -> Weight {
+	ChessRating::on_idle(System::block_number(), weight)
+}
+
+#[test]
+fn expected_scores_are_symmetric() {
+	assert_eq!(expected_score(0), 500);
+	assert_eq!(expected_score(100), 640);
+	assert_eq!(expected_score(-100), 360);
+	// interpolated
+	assert_eq!(expected_score(110), 653);
+	// capped at 400 points
+	assert_eq!(expected_score(800), 909);
+	assert_eq!(expected_score(-800), 91);
+
+	assert_eq!(rating_change(1200, 1200, 1000, 32), 16);
+	assert_eq!(rating_change(1200, 1200, 0, 32), -16);
+	assert_eq!(rating_change(1200, 1200, 500, 32), 0);
+	assert_eq!(rating_change(1300, 1200, 500, 32), -4);
+	assert_eq!(rating_change(1200, 1300, 500, 32), 4);
+}
+
+#[test]
+fn seek_waits_for_an_opponent() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(seek(ALICE));
+
+		let seeks = ChessRating::seeks(MatchStyle::Blitz);
+		assert_eq!(seeks.len(), 1);
+		assert_eq!(seeks[0].player, ALICE);
+		assert_eq!(seeks[0].rating, 1200);
+		System::assert_last_event(
+			Event::SeekPosted { who: ALICE, style: MatchStyle::Blitz }.into(),
+		);
+
+		assert_noop!(seek(ALICE), Error::<Test>::AlreadySeeking);
+	});
+}
+
+#[test]
+fn seek_pairs_with_a_close_rating() {
+	new_test_ext().execute_with(|| {
+		set_rating(BOB, 1500);
+		assert_ok!(seek(ALICE));
+		// too far apart
+		assert_ok!(seek(BOB));
+		assert_eq!(ChessRating::seeks(MatchStyle::Blitz).len(), 2);
+		// another bet
+		assert_ok!(ChessRating::seek(RuntimeOrigin::signed(CHARLIE), MatchStyle::Blitz, XUSD, 1));
+		assert_noop!(seek(CHARLIE), Error::<Test>::AlreadySeeking);
+		assert_ok!(ChessRating::cancel_seek(RuntimeOrigin::signed(CHARLIE), MatchStyle::Blitz));
+
+		assert_ok!(seek(CHARLIE));
+
+		let seeks = ChessRating::seeks(MatchStyle::Blitz);
+		assert_eq!(seeks.len(), 1);
+		assert_eq!(seeks[0].player, BOB);
+		assert_eq!(
+			Pairings::<Test>::get(CHARLIE, ALICE),
+			Some(Pairing {
+				style: MatchStyle::Blitz,
+				bet_asset_id: XUSD,
+				bet_amount: BET,
+				match_id: None,
+				expires_at: 11
+			})
+		);
+		// the challenger creates the match
+		assert!(pallet_chess::Matches::<Test>::iter_keys().next().is_none());
+		System::assert_last_event(
+			Event::Paired { challenger: CHARLIE, opponent: ALICE, style: MatchStyle::Blitz }.into(),
+		);
+	});
+}
+
+#[test]
+fn seeks_are_limited() {
+	new_test_ext().execute_with(|| {
+		for who in [ALICE, BOB] {
+			assert_ok!(ChessRating::seek(
+				RuntimeOrigin::signed(who),
+				MatchStyle::Blitz,
+				XUSD,
+				who.into()
+			));
+		}
+		assert_noop!(seek(CHARLIE), Error::<Test>::TooManySeeks);
+		assert_noop!(
+			ChessRating::cancel_seek(RuntimeOrigin::signed(CHARLIE), MatchStyle::Blitz),
+			Error::<Test>::NotSeeking
+		);
+	});
+}
+
+#[test]
+fn seeks_expire() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(seek(ALICE));
+		assert_eq!(ChessRating::seeks(MatchStyle::Blitz)[0].expires_at, 11);
+
+		System::set_block_number(11);
+		assert_ok!(seek(BOB));
+
+		let seeks = ChessRating::seeks(MatchStyle::Blitz);
+		assert_eq!(seeks.len(), 1);
+		assert_eq!(seeks[0].player, BOB);
+		assert!(Pairings::<Test>::iter().next().is_none());
+	});
+}
+
+#[test]
+fn join_match_requires_pairing() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ChessRating::join_match(RuntimeOrigin::signed(ALICE), H256::zero()),
+			Error::<Test>::UnknownMatch
+		);
+		// not created by matchmaking
+		assert_ok!(Chess::create_match(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			MatchStyle::Blitz,
+			XUSD,
+			BET
+		));
+		let match_id = pallet_chess::Matches::<Test>::iter_keys().next().unwrap();
+		assert_noop!(
+			ChessRating::join_match(RuntimeOrigin::signed(ALICE), match_id),
+			Error::<Test>::NotPaired
+		);
+		assert_ok!(Chess::abort_match(RuntimeOrigin::signed(BOB), match_id));
+
+		assert_ok!(seek(ALICE));
+		assert_ok!(seek(BOB));
+		// another match between the paired players, not started
+		assert_ok!(Chess::create_match(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			MatchStyle::Blitz,
+			XUSD,
+			2 * BET
+		));
+		let other_match = awaiting_match(BOB, ALICE);
+		assert_noop!(
+			ChessRating::join_match(RuntimeOrigin::signed(ALICE), other_match),
+			Error::<Test>::NotPaired
+		);
+		assert_ok!(Chess::abort_match(RuntimeOrigin::signed(BOB), other_match));
+
+		let match_id = create_paired_match(BOB, ALICE);
+		assert_noop!(
+			ChessRating::join_match(RuntimeOrigin::signed(CHARLIE), match_id),
+			Error::<Test>::NotPaired
+		);
+
+		assert_ok!(ChessRating::join_match(RuntimeOrigin::signed(ALICE), match_id));
+
+		assert_eq!(ChessRating::ongoing_matches().into_inner(), vec![match_id]);
+		assert!(Pairings::<Test>::get(BOB, ALICE).is_none());
+		assert_eq!(Assets::balance(XUSD, ALICE), 1_000 * UNITS - BET);
+		System::assert_last_event(Event::RatedMatchStarted { match_id }.into());
+	});
+}
+
+#[test]
+fn start_match_requires_the_paired_match() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ChessRating::start_match(RuntimeOrigin::signed(BOB), H256::zero()),
+			Error::<Test>::UnknownMatch
+		);
+		assert_ok!(Chess::create_match(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			MatchStyle::Blitz,
+			XUSD,
+			2 * BET
+		));
+		let other_match = awaiting_match(BOB, ALICE);
+		// not paired
+		assert_noop!(
+			ChessRating::start_match(RuntimeOrigin::signed(BOB), other_match),
+			Error::<Test>::NotPaired
+		);
+
+		assert_ok!(seek(ALICE));
+		assert_ok!(seek(BOB));
+		// created by another player
+		assert_noop!(
+			ChessRating::start_match(RuntimeOrigin::signed(ALICE), other_match),
+			Error::<Test>::NotPaired
+		);
+		// another bet
+		assert_noop!(
+			ChessRating::start_match(RuntimeOrigin::signed(BOB), other_match),
+			Error::<Test>::WrongMatch
+		);
+		assert_ok!(Chess::abort_match(RuntimeOrigin::signed(BOB), other_match));
+
+		let match_id = create_paired_match(BOB, ALICE);
+
+		assert_eq!(Pairings::<Test>::get(BOB, ALICE).unwrap().match_id, Some(match_id));
+		System::assert_last_event(Event::RatedMatchCreated { match_id }.into());
+		assert_noop!(
+			ChessRating::start_match(RuntimeOrigin::signed(BOB), match_id),
+			Error::<Test>::AlreadyStarted
+		);
+	});
+}
+
+#[test]
+fn expired_pairings_cannot_be_started() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(seek(ALICE));
+		assert_ok!(seek(BOB));
+		assert_ok!(Chess::create_match(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			MatchStyle::Blitz,
+			XUSD,
+			BET
+		));
+
+		System::set_block_number(11);
+		assert_noop!(
+			ChessRating::start_match(RuntimeOrigin::signed(BOB), awaiting_match(BOB, ALICE)),
+			Error::<Test>::PairingExpired
+		);
+	});
+}
+
+#[test]
+fn expired_pairings_are_cleared() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(seek(ALICE));
+		assert_ok!(seek(BOB));
+		let match_id = create_paired_match(BOB, ALICE);
+
+		assert_noop!(
+			ChessRating::clear_pairing(RuntimeOrigin::signed(CHARLIE), BOB, ALICE),
+			Error::<Test>::PairingNotExpired
+		);
+
+		System::set_block_number(11);
+		assert_noop!(
+			ChessRating::join_match(RuntimeOrigin::signed(ALICE), match_id),
+			Error::<Test>::PairingExpired
+		);
+		assert_ok!(ChessRating::clear_pairing(RuntimeOrigin::signed(CHARLIE), BOB, ALICE));
+
+		assert!(Pairings::<Test>::get(BOB, ALICE).is_none());
+		System::assert_last_event(
+			Event::PairingCleared { challenger: BOB, opponent: ALICE }.into(),
+		);
+		assert_noop!(
+			ChessRating::clear_pairing(RuntimeOrigin::signed(CHARLIE), BOB, ALICE),
+			Error::<Test>::NotPaired
+		);
+
+		// the challenger gets their stake back
+		assert_ok!(Chess::abort_match(RuntimeOrigin::signed(BOB), match_id));
+		assert_eq!(Assets::balance(XUSD, BOB), 1_000 * UNITS);
+	});
+}
+
+#[test]
+fn aborted_pairings_are_cleared_by_their_challenger() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(seek(ALICE));
+		assert_ok!(seek(BOB));
+		let match_id = create_paired_match(BOB, ALICE);
+
+		assert_ok!(Chess::abort_match(RuntimeOrigin::signed(BOB), match_id));
+		assert_ok!(ChessRating::clear_pairing(RuntimeOrigin::signed(BOB), BOB, ALICE));
+
+		assert!(Pairings::<Test>::get(BOB, ALICE).is_none());
+		assert_noop!(
+			ChessRating::join_match(RuntimeOrigin::signed(ALICE), match_id),
+			Error::<Test>::UnknownMatch
+		);
+	});
+}
+
+#[test]
+fn won_matches_are_rated() {
+	new_test_ext().execute_with(|| {
+		let match_id = start_rated_match();
+
+		// still being played
+		idle(Weight::MAX);
+		assert_eq!(ChessRating::ongoing_matches().len(), 1);
+
+		end_match(match_id, MatchState::Won(ALICE));
+		idle(Weight::MAX);
+
+		assert!(ChessRating::ongoing_matches().is_empty());
+		assert_eq!(ChessRating::rating(&ALICE, &MatchStyle::Blitz), 1216);
+		assert_eq!(ChessRating::rating(&BOB, &MatchStyle::Blitz), 1184);
+		assert_eq!(
+			Ratings::<Test>::get(MatchStyle::Blitz, ALICE),
+			Some(PlayerRating { rating: 1216, games: 1 })
+		);
+		// other time controls are not affected
+		assert_eq!(ChessRating::rating(&ALICE, &MatchStyle::Bullet), 1200);
+		assert_eq!(ChessRating::history(ALICE, MatchStyle::Blitz).into_inner(), vec![(1, 1216)]);
+		System::assert_has_event(
+			Event::RatingUpdated { who: BOB, style: MatchStyle::Blitz, match_id, rating: 1184 }
+				.into(),
+		);
+		assert_eq!(
+			ChessRating::leaderboard(&MatchStyle::Blitz, 10),
+			vec![(ALICE, 1216), (BOB, 1184)]
+		);
+		assert_eq!(ChessRating::leaderboard(&MatchStyle::Blitz, 1), vec![(ALICE, 1216)]);
+	});
+}
+
+#[test]
+fn played_matches_are_rated() {
+	new_test_ext().execute_with(|| {
+		let match_id = start_rated_match();
+
+		// the fool's mate, `BOB` playing white
+		for (who, mv) in [(BOB, "f2f3"), (ALICE, "e7e5"), (BOB, "g2g4"), (ALICE, "d8h4")] {
+			assert_ok!(Chess::make_move(RuntimeOrigin::signed(who), match_id, mv.into()));
+		}
+		idle(Weight::MAX);
+
+		assert!(ChessRating::ongoing_matches().is_empty());
+		assert_eq!(ChessRating::rating(&ALICE, &MatchStyle::Blitz), 1216);
+		assert_eq!(ChessRating::rating(&BOB, &MatchStyle::Blitz), 1184);
+	});
+}
+
+#[test]
+fn drawn_matches_are_rated() {
+	new_test_ext().execute_with(|| {
+		set_rating(ALICE, 1300);
+		let match_id = start_rated_match();
+
+		end_match(match_id, MatchState::Drawn);
+		idle(Weight::MAX);
+
+		assert_eq!(ChessRating::rating(&ALICE, &MatchStyle::Blitz), 1296);
+		assert_eq!(ChessRating::rating(&BOB, &MatchStyle::Blitz), 1204);
+	});
+}
+
+#[test]
+fn leaderboards_are_limited() {
+	new_test_ext().execute_with(|| {
+		let match_id = start_rated_match();
+		end_match(match_id, MatchState::Won(ALICE));
+		idle(Weight::MAX);
+
+		// `CHARLIE` challenges `BOB`
+		assert_ok!(seek(BOB));
+		assert_ok!(seek(CHARLIE));
+		let match_id = create_paired_match(CHARLIE, BOB);
+		assert_ok!(ChessRating::join_match(RuntimeOrigin::signed(BOB), match_id));
+		end_match(match_id, MatchState::Won(CHARLIE));
+		idle(Weight::MAX);
+
+		assert_eq!(ChessRating::rating(&BOB, &MatchStyle::Blitz), 1169);
+		assert_eq!(
+			Leaderboards::<Test>::get(MatchStyle::Blitz).into_inner(),
+			vec![(ALICE, 1216), (CHARLIE, 1215)]
+		);
+		assert_eq!(
+			ChessRating::leaderboard(&MatchStyle::Blitz, 10),
+			vec![(ALICE, 1216), (CHARLIE, 1215)]
+		);
+	});
+}
+
+#[test]
+fn history_is_limited() {
+	new_test_ext().execute_with(|| {
+		for n in 1..=3 {
+			System::set_block_number(n);
+			let match_id = start_rated_match();
+			end_match(match_id, MatchState::Won(BOB));
+			idle(Weight::MAX);
+			pallet_chess::Matches::<Test>::remove(match_id);
+		}
+
+		assert_eq!(
+			ChessRating::history(BOB, MatchStyle::Blitz).into_inner(),
+			vec![(2, 1231), (3, 1244)]
+		);
+		assert_eq!(Ratings::<Test>::get(MatchStyle::Blitz, BOB).unwrap().games, 3);
+	});
+}
+
+#[test]
+fn matches_are_rated_as_weight_allows() {
+	new_test_ext().execute_with(|| {
+		let match_id = start_rated_match();
+		end_match(match_id, MatchState::Won(BOB));
+
+		assert_eq!(idle(Weight::zero()), Weight::zero());
+		let weight = <() as crate::WeightInfo>::on_idle(0);
+		assert_eq!(idle(weight), weight);
+		assert_eq!(ChessRating::ongoing_matches().len(), 1);
+
+		// no longer stored
+		OngoingMatches::<Test>::mutate(|ongoing| ongoing.try_push(H256::zero()).unwrap());
+		let weight = <() as crate::WeightInfo>::on_idle(2);
+		assert_eq!(idle(weight), weight);
+
+		assert!(ChessRating::ongoing_matches().is_empty());
+		System::assert_has_event(Event::MatchUnrated { match_id: H256::zero() }.into());
+		assert_eq!(ChessRating::rating(&BOB, &MatchStyle::Blitz), 1216);
+	});
+}
+
+#[test]
+fn chess_calls_are_filtered() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(seek(ALICE));
+		assert_ok!(seek(BOB));
+		let match_id = create_paired_match(BOB, ALICE);
+
+		ChessCallsPaused::set(true);
+		assert_noop!(
+			ChessRating::join_match(RuntimeOrigin::signed(ALICE), match_id),
+			frame_system::Error::<Test>::CallFiltered
+		);
+	});
+}
@@ -0,0 +1,126 @@
+//! Weights for `pallet_chess_rating`
+//!
+//! Estimated from the storage accesses of each call and of the matches rated in `on_idle`. The
+//! weights of `pallet_chess` calls are added by the pallet.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+pub trait WeightInfo {
+	fn seek() -> Weight;
+	fn cancel_seek() -> Weight;
+	fn join_match() -> Weight;
+	fn clear_pairing() -> Weight;
+	fn start_match() -> Weight;
+	fn on_idle(n: u32, ) -> Weight;
+}
+
+/// Weight functions for `pallet_chess_rating`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: crate::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: ChessRating Ratings (r:1 w:0)
+	// Storage: ChessRating Seeks (r:1 w:1)
+	// Storage: ChessRating Pairings (r:0 w:1)
+	fn seek() -> Weight {
+		Weight::from_ref_time(25_000_000)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: ChessRating Seeks (r:1 w:1)
+	fn cancel_seek() -> Weight {
+		Weight::from_ref_time(15_000_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: Chess Matches (r:1 w:0)
+	// Storage: ChessRating Pairings (r:1 w:1)
+	// Storage: ChessRating OngoingMatches (r:1 w:1)
+	fn join_match() -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: ChessRating Pairings (r:1 w:1)
+	fn clear_pairing() -> Weight {
+		Weight::from_ref_time(15_000_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: Chess Matches (r:1 w:0)
+	// Storage: ChessRating Pairings (r:1 w:1)
+	fn start_match() -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: ChessRating OngoingMatches (r:1 w:1)
+	// Storage: Chess Matches (r:n w:0)
+	// Storage: ChessRating Ratings (r:2n w:2n)
+	// Storage: ChessRating History (r:2n w:2n)
+	// Storage: ChessRating Leaderboards (r:2n w:2n)
+	/// The range of component `n` is `[0, MaxOngoingMatches]`.
+	fn on_idle(n: u32, ) -> Weight {
+		Weight::from_ref_time(5_000_000)
+			.saturating_add(Weight::from_ref_time(40_000_000).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().reads((7_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(n.into())))
+	}
+}
+
+impl WeightInfo for () {
+	// Storage: ChessRating Ratings (r:1 w:0)
+	// Storage: ChessRating Seeks (r:1 w:1)
+	// Storage: ChessRating Pairings (r:0 w:1)
+	fn seek() -> Weight {
+		Weight::from_ref_time(25_000_000)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	// Storage: ChessRating Seeks (r:1 w:1)
+	fn cancel_seek() -> Weight {
+		Weight::from_ref_time(15_000_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	// Storage: Chess Matches (r:1 w:0)
+	// Storage: ChessRating Pairings (r:1 w:1)
+	// Storage: ChessRating OngoingMatches (r:1 w:1)
+	fn join_match() -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	// Storage: ChessRating Pairings (r:1 w:1)
+	fn clear_pairing() -> Weight {
+		Weight::from_ref_time(15_000_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	// Storage: Chess Matches (r:1 w:0)
+	// Storage: ChessRating Pairings (r:1 w:1)
+	fn start_match() -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	// Storage: ChessRating OngoingMatches (r:1 w:1)
+	// Storage: Chess Matches (r:n w:0)
+	// Storage: ChessRating Ratings (r:2n w:2n)
+	// Storage: ChessRating History (r:2n w:2n)
+	// Storage: ChessRating Leaderboards (r:2n w:2n)
+	/// The range of component `n` is `[0, MaxOngoingMatches]`.
+	fn on_idle(n: u32, ) -> Weight {
+		Weight::from_ref_time(5_000_000)
+			.saturating_add(Weight::from_ref_time(40_000_000).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+			.saturating_add(RocksDbWeight::get().reads((7_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(n.into())))
+	}
+}
@@ -0,0 +1,779 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Swiss and knockout tournaments of `pallet_chess` matches, with prize pools.
+//!
+//! Organizers create tournaments with an entry fee in any asset, which players pay into the prize
+//! pool when they register. The pool is held by an account derived from the `PalletId` of
+//! `pallet_chess`, and distributed to the best ranked players at the end of the tournament.
+//!
+//! Tournaments are driven by [`Pallet::advance`], which anyone can call: once registration is
+//! over, it pairs the players for the first round, and once all the games of a round are finished,
+//! it scores the round and pairs the next one, or distributes the prizes. Paired players play
+//! their game in a match white creates in `pallet_chess` and starts with [`Pallet::start_match`],
+//! and which black joins with [`Pallet::join_match`]. Its result is read from `pallet_chess` when
+//! the round is scored, and games whose match white aborted before black joined are forfeited by
+//! white.
+//!
+//! Rounds last `round_duration` blocks, after which stalled rounds can be advanced: games which
+//! white didn't start are forfeited by white, games which black didn't join are forfeited by
+//! black, and games still being played are drawn. As the
+//! janitors of abandoned matches of `pallet_chess`, callers advancing stalled rounds get an
+//! `IncentiveShare` of the entry fee of each player who forfeited, from the prize pool.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{
+			fungibles::{Inspect, Transfer},
+			Contains, UnfilteredDispatchable,
+		},
+	};
+	use frame_system::pallet_prelude::*;
+	use pallet_chess::{MatchState, MatchStyle, WeightInfo as ChessWeightInfo};
+	use sp_runtime::{
+		traits::{AccountIdConversion, Saturating, Zero},
+		Percent,
+	};
+	use sp_std::{cmp::Reverse, vec::Vec};
+
+	pub type TournamentId = u32;
+	pub type AssetIdOf<T> = <<T as pallet_chess::Config>::Assets as Inspect<
+		<T as frame_system::Config>::AccountId,
+	>>::AssetId;
+	pub type BalanceOf<T> =
+		<<T as Config>::Assets as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type TournamentOf<T> = Tournament<
+		<T as frame_system::Config>::AccountId,
+		AssetIdOf<T>,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	/// Points are counted in half-points, so that draws score 1.
+	const WIN: u32 = 2;
+	const DRAW: u32 = 1;
+
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum Format {
+		/// Players are paired with players of the same score they haven't played yet, for a
+		/// fixed number of rounds.
+		Swiss { rounds: u32 },
+		/// Players who lose are eliminated, until one is left. Drawn games are won by the player
+		/// who registered first.
+		Knockout,
+	}
+
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum TournamentState<BlockNumber> {
+		/// Players can register until `starts_at`.
+		Registration {
+			starts_at: BlockNumber,
+		},
+		/// The games of round `number` are played until `deadline`.
+		Round {
+			number: u32,
+			deadline: BlockNumber,
+		},
+		Finished,
+	}
+
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Tournament<AccountId, AssetId, Balance, BlockNumber> {
+		pub organizer: AccountId,
+		pub format: Format,
+		pub style: MatchStyle,
+		pub entry_fee_asset: AssetId,
+		pub entry_fee: Balance,
+		pub max_players: u32,
+		pub round_duration: BlockNumber,
+		/// The entry fees paid, less the janitor rewards.
+		pub pool: Balance,
+		pub state: TournamentState<BlockNumber>,
+	}
+
+	/// A registered player, whose index in the participants of the tournament is their seed.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Participant<AccountId> {
+		pub who: AccountId,
+		/// The score of the player, in half-points.
+		pub points: u32,
+		pub had_bye: bool,
+		/// The round the player was knocked out in.
+		pub eliminated_in: Option<u32>,
+	}
+
+	/// A game of the current round, between the participants of index `white` and `black`.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Pairing<Hash> {
+		pub white: u32,
+		/// `None` for a bye, which scores a win.
+		pub black: Option<u32>,
+		/// The match, once `white` started it.
+		pub match_id: Option<Hash>,
+		/// Whether `black` joined the match.
+		pub joined: bool,
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_chess::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The assets in which entry fees are paid.
+		type Assets: Transfer<Self::AccountId, AssetId = AssetIdOf<Self>>;
+		/// The maximum number of players of a tournament.
+		#[pallet::constant]
+		type MaxPlayers: Get<u32>;
+		/// The maximum number of rounds of a tournament.
+		#[pallet::constant]
+		type MaxRounds: Get<u32>;
+		/// The maximum number of prizes of a tournament.
+		#[pallet::constant]
+		type MaxPrizes: Get<u32>;
+		/// The shortest duration of a round.
+		#[pallet::constant]
+		type MinRoundDuration: Get<Self::BlockNumber>;
+		/// Filters the `pallet_chess` calls this pallet dispatches for players of tournament games,
+		/// which bypass `BaseCallFilter`.
+		type ChessCallFilter: Contains<pallet_chess::Call<Self>>;
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The ID of the next tournament.
+	#[pallet::storage]
+	pub type NextTournamentId<T: Config> = StorageValue<_, TournamentId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn tournaments)]
+	pub type Tournaments<T: Config> = StorageMap<_, Twox64Concat, TournamentId, TournamentOf<T>>;
+
+	/// The share of the prize pool of each rank, best first.
+	#[pallet::storage]
+	#[pallet::getter(fn prizes)]
+	pub type Prizes<T: Config> =
+		StorageMap<_, Twox64Concat, TournamentId, BoundedVec<Percent, T::MaxPrizes>, ValueQuery>;
+
+	/// The players of tournaments, in order of registration.
+	#[pallet::storage]
+	#[pallet::getter(fn participants)]
+	pub type Participants<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		TournamentId,
+		BoundedVec<Participant<T::AccountId>, T::MaxPlayers>,
+		ValueQuery,
+	>;
+
+	/// The participants each participant played, by index.
+	#[pallet::storage]
+	pub type Opponents<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		TournamentId,
+		Twox64Concat,
+		u32,
+		BoundedVec<u32, T::MaxRounds>,
+		ValueQuery,
+	>;
+
+	/// The games of the current round of tournaments.
+	#[pallet::storage]
+	#[pallet::getter(fn pairings)]
+	pub type Pairings<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		TournamentId,
+		BoundedVec<Pairing<T::Hash>, T::MaxPlayers>,
+		ValueQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		TournamentCreated {
+			id: TournamentId,
+			organizer: T::AccountId,
+		},
+		PlayerRegistered {
+			id: TournamentId,
+			who: T::AccountId,
+		},
+		/// The tournament was cancelled and the entry fees refunded.
+		TournamentCancelled {
+			id: TournamentId,
+		},
+		RoundStarted {
+			id: TournamentId,
+			round: u32,
+		},
+		GameStarted {
+			id: TournamentId,
+			match_id: T::Hash,
+		},
+		GameJoined {
+			id: TournamentId,
+			match_id: T::Hash,
+		},
+		/// `janitor` advanced a stalled round.
+		JanitorRewarded {
+			id: TournamentId,
+			janitor: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		PrizePaid {
+			id: TournamentId,
+			who: T::AccountId,
+			rank: u32,
+			amount: BalanceOf<T>,
+		},
+		TournamentFinished {
+			id: TournamentId,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The tournament does not exist
+		UnknownTournament,
+		/// The signer is not the organizer of the tournament
+		NotOrganizer,
+		/// The number of rounds is zero or more than `MaxRounds`
+		InvalidRounds,
+		/// The maximum number of players is less than two or more than `MaxPlayers`
+		InvalidMaxPlayers,
+		/// The prizes don't add up to the whole pool
+		InvalidPrizes,
+		/// The round duration is shorter than `MinRoundDuration`
+		RoundTooShort,
+		/// The registration would end before it starts
+		StartInPast,
+		/// The registration is over
+		RegistrationClosed,
+		/// The player is already registered
+		AlreadyRegistered,
+		/// The tournament has `max_players` players
+		TournamentFull,
+		/// The registration is not over yet
+		TooEarly,
+		/// The tournament is not playing a round
+		NotInRound,
+		/// The signer has no game to start or join in the current round
+		NotPaired,
+		/// The signer already started or joined their game
+		AlreadyStarted,
+		/// White has not started the game of the signer
+		NotStarted,
+		/// The match does not exist
+		UnknownMatch,
+		/// The match is not an open match without a bet between the players of the game of the
+		/// signer
+		WrongMatch,
+		/// Games of the round are being played, and its deadline is not reached
+		RoundNotFinished,
+		/// The tournament is finished
+		TournamentFinished,
+		/// No more tournament IDs are available
+		NoAvailableTournamentId,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create a tournament of time control `style`, which players can register for until
+		/// block `starts_at` by paying `entry_fee` of `entry_fee_asset`.
+		///
+		/// The prize pool is distributed to the best ranked players according to `prizes`, in
+		/// percent of the pool, best first.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::create())]
+		#[allow(clippy::too_many_arguments)]
+		pub fn create(
+			origin: OriginFor<T>,
+			format: Format,
+			style: MatchStyle,
+			entry_fee_asset: AssetIdOf<T>,
+			entry_fee: BalanceOf<T>,
+			max_players: u32,
+			starts_at: T::BlockNumber,
+			round_duration: T::BlockNumber,
+			prizes: BoundedVec<Percent, T::MaxPrizes>,
+		) -> DispatchResult {
+			let organizer = ensure_signed(origin)?;
+
+			ensure!(
+				(2..=T::MaxPlayers::get()).contains(&max_players),
+				Error::<T>::InvalidMaxPlayers
+			);
+			let rounds = match format {
+				Format::Swiss { rounds } => rounds,
+				// the number of rounds of a full bracket
+				Format::Knockout => u32::BITS - (max_players - 1).leading_zeros(),
+			};
+			ensure!((1..=T::MaxRounds::get()).contains(&rounds), Error::<T>::InvalidRounds);
+			ensure!(
+				prizes.iter().map(|prize| u32::from(prize.deconstruct())).sum::<u32>() == 100,
+				Error::<T>::InvalidPrizes
+			);
+			ensure!(round_duration >= T::MinRoundDuration::get(), Error::<T>::RoundTooShort);
+			ensure!(starts_at > frame_system::Pallet::<T>::block_number(), Error::<T>::StartInPast);
+
+			let id = NextTournamentId::<T>::get();
+			let next_id = id.checked_add(1).ok_or(Error::<T>::NoAvailableTournamentId)?;
+			let tournament = Tournament {
+				organizer: organizer.clone(),
+				format,
+				style,
+				entry_fee_asset,
+				entry_fee,
+				max_players,
+				round_duration,
+				pool: Zero::zero(),
+				state: TournamentState::Registration { starts_at },
+			};
+			Tournaments::<T>::insert(id, tournament);
+			Prizes::<T>::insert(id, prizes);
+			NextTournamentId::<T>::put(next_id);
+
+			Self::deposit_event(Event::TournamentCreated { id, organizer });
+			Ok(())
+		}
+
+		/// Register for the tournament `id`, paying its entry fee into the prize pool.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::register())]
+		pub fn register(origin: OriginFor<T>, id: TournamentId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut tournament = Tournaments::<T>::get(id).ok_or(Error::<T>::UnknownTournament)?;
+			match tournament.state {
+				TournamentState::Registration { starts_at }
+					if frame_system::Pallet::<T>::block_number() < starts_at => {},
+				_ => return Err(Error::<T>::RegistrationClosed.into()),
+			}
+			let mut participants = Participants::<T>::get(id);
+			ensure!(
+				!participants.iter().any(|participant| participant.who == who),
+				Error::<T>::AlreadyRegistered
+			);
+			ensure!(
+				(participants.len() as u32) < tournament.max_players,
+				Error::<T>::TournamentFull
+			);
+
+			<T as Config>::Assets::transfer(
+				tournament.entry_fee_asset,
+				&who,
+				&Self::pool_account(id),
+				tournament.entry_fee,
+				true,
+			)?;
+			tournament.pool.saturating_accrue(tournament.entry_fee);
+			participants
+				.try_push(Participant {
+					who: who.clone(),
+					points: 0,
+					had_bye: false,
+					eliminated_in: None,
+				})
+				.map_err(|_| Error::<T>::TournamentFull)?;
+			Participants::<T>::insert(id, participants);
+			Tournaments::<T>::insert(id, tournament);
+
+			Self::deposit_event(Event::PlayerRegistered { id, who });
+			Ok(())
+		}
+
+		/// Cancel the tournament `id` before it starts, and refund the entry fees.
+		#[pallet::call_index(2)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::cancel(T::MaxPlayers::get()))]
+		pub fn cancel(origin: OriginFor<T>, id: TournamentId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let tournament = Tournaments::<T>::get(id).ok_or(Error::<T>::UnknownTournament)?;
+			ensure!(tournament.organizer == who, Error::<T>::NotOrganizer);
+			ensure!(
+				matches!(tournament.state, TournamentState::Registration { .. }),
+				Error::<T>::RegistrationClosed
+			);
+
+			let players = Self::refund(id, &tournament)?;
+			Ok(Some(<T as pallet::Config>::WeightInfo::cancel(players)).into())
+		}
+
+		/// Advance the tournament `id`: start its first round once the registration is over,
+		/// or score the current round once its games are finished and start the next one, or
+		/// distribute the prizes.
+		///
+		/// Rounds can be advanced after their deadline even if their games are not finished, in
+		/// which case the signer gets an `IncentiveShare` of the entry fee of each player who
+		/// forfeited.
+		#[pallet::call_index(3)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::advance(T::MaxPlayers::get()))]
+		pub fn advance(origin: OriginFor<T>, id: TournamentId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let mut tournament = Tournaments::<T>::get(id).ok_or(Error::<T>::UnknownTournament)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut participants = Participants::<T>::get(id).into_inner();
+			let players = participants.len() as u32;
+
+			let number = match tournament.state {
+				TournamentState::Registration { starts_at } => {
+					ensure!(now >= starts_at, Error::<T>::TooEarly);
+					if players < 2 {
+						Self::refund(id, &tournament)?;
+						return Ok(Some(<T as pallet::Config>::WeightInfo::cancel(players)).into());
+					}
+					0
+				},
+				TournamentState::Round { number, deadline } => {
+					let stalled = now >= deadline;
+					let forfeits =
+						Self::score_round(id, number, &tournament, &mut participants, stalled)?;
+					if stalled && forfeits > 0 {
+						Self::reward_janitor(id, &mut tournament, who, forfeits)?;
+					}
+					number
+				},
+				TournamentState::Finished => return Err(Error::<T>::TournamentFinished.into()),
+			};
+
+			let active = participants.iter().filter(|p| p.eliminated_in.is_none()).count();
+			let finished = match tournament.format {
+				Format::Swiss { rounds } => number == rounds,
+				Format::Knockout => number > 0 && active <= 1,
+			};
+			if finished {
+				Self::distribute_prizes(id, &mut tournament, &participants)?;
+				Pairings::<T>::remove(id);
+				let _ = Opponents::<T>::clear_prefix(id, T::MaxPlayers::get(), None);
+				tournament.state = TournamentState::Finished;
+				Self::deposit_event(Event::TournamentFinished { id });
+			} else {
+				let round = number + 1;
+				Self::pair(id, &tournament.format, &mut participants);
+				tournament.state = TournamentState::Round {
+					number: round,
+					deadline: now.saturating_add(tournament.round_duration),
+				};
+				Self::deposit_event(Event::RoundStarted { id, round });
+			}
+			Participants::<T>::insert(id, BoundedVec::truncate_from(participants));
+			Tournaments::<T>::insert(id, tournament);
+
+			Ok(Some(<T as pallet::Config>::WeightInfo::advance(players)).into())
+		}
+
+		/// Start the game of the signer in the current round of the tournament `id`, in which
+		/// they play white, with the match `match_id`.
+		///
+		/// The signer creates the match against their opponent in `pallet_chess` beforehand,
+		/// without a bet. If they abort it before their opponent joins it, they forfeit the game.
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::start_match())]
+		pub fn start_match(
+			origin: OriginFor<T>,
+			id: TournamentId,
+			match_id: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let tournament = Tournaments::<T>::get(id).ok_or(Error::<T>::UnknownTournament)?;
+			ensure!(
+				matches!(tournament.state, TournamentState::Round { .. }),
+				Error::<T>::NotInRound
+			);
+			let participants = Participants::<T>::get(id);
+			let mut pairings = Pairings::<T>::get(id);
+			let pairing = pairings
+				.iter_mut()
+				.find(|pairing| participants[pairing.white as usize].who == who)
+				.ok_or(Error::<T>::NotPaired)?;
+			let black = pairing.black.ok_or(Error::<T>::NotPaired)?;
+			ensure!(pairing.match_id.is_none(), Error::<T>::AlreadyStarted);
+			let chess_match =
+				pallet_chess::Matches::<T>::get(match_id).ok_or(Error::<T>::UnknownMatch)?;
+			ensure!(
+				chess_match.challenger == who &&
+					chess_match.opponent == participants[black as usize].who &&
+					chess_match.style == tournament.style &&
+					chess_match.bet_amount.is_zero() &&
+					matches!(chess_match.state, MatchState::AwaitingOpponent),
+				Error::<T>::WrongMatch
+			);
+			pairing.match_id = Some(match_id);
+			Pairings::<T>::insert(id, pairings);
+
+			Self::deposit_event(Event::GameStarted { id, match_id });
+			Ok(())
+		}
+
+		/// Join the match started by the opponent of the signer in the current round of the
+		/// tournament `id`.
+		#[pallet::call_index(5)]
+		#[pallet::weight(
+			<T as pallet::Config>::WeightInfo::join_match()
+				.saturating_add(<T as pallet_chess::Config>::WeightInfo::join_match())
+		)]
+		pub fn join_match(origin: OriginFor<T>, id: TournamentId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let tournament = Tournaments::<T>::get(id).ok_or(Error::<T>::UnknownTournament)?;
+			ensure!(
+				matches!(tournament.state, TournamentState::Round { .. }),
+				Error::<T>::NotInRound
+			);
+			let participants = Participants::<T>::get(id);
+			let mut pairings = Pairings::<T>::get(id);
+			let pairing = pairings
+				.iter_mut()
+				.find(|pairing| {
+					pairing.black.map(|black| &participants[black as usize].who) == Some(&who)
+				})
+				.ok_or(Error::<T>::NotPaired)?;
+			let match_id = pairing.match_id.ok_or(Error::<T>::NotStarted)?;
+			ensure!(!pairing.joined, Error::<T>::AlreadyStarted);
+			pairing.joined = true;
+
+			let join_match = pallet_chess::Call::<T>::join_match { match_id };
+			ensure!(
+				T::ChessCallFilter::contains(&join_match),
+				frame_system::Error::<T>::CallFiltered
+			);
+			join_match
+				.dispatch_bypass_filter(frame_system::RawOrigin::Signed(who).into())
+				.map_err(|e| e.error)?;
+			Pairings::<T>::insert(id, pairings);
+
+			Self::deposit_event(Event::GameJoined { id, match_id });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account holding the prize pool of the tournament `id`, derived from the `PalletId`
+		/// of `pallet_chess`.
+		pub fn pool_account(id: TournamentId) -> T::AccountId {
+			<T as pallet_chess::Config>::PalletId::get().into_sub_account_truncating(id)
+		}
+
+		/// Refund the entry fees of the tournament `id` and remove it, returning the number of
+		/// players.
+		fn refund(id: TournamentId, tournament: &TournamentOf<T>) -> Result<u32, DispatchError> {
+			let participants = Participants::<T>::take(id);
+			for participant in participants.iter() {
+				<T as Config>::Assets::transfer(
+					tournament.entry_fee_asset,
+					&Self::pool_account(id),
+					&participant.who,
+					tournament.entry_fee,
+					false,
+				)?;
+			}
+			Tournaments::<T>::remove(id);
+			Prizes::<T>::remove(id);
+
+			Self::deposit_event(Event::TournamentCancelled { id });
+			Ok(participants.len() as u32)
+		}
+
+		/// Score the games of round `number`, and return the number of players who forfeited.
+		///
+		/// Fails if games are not finished, unless the round is `stalled`.
+		fn score_round(
+			id: TournamentId,
+			number: u32,
+			tournament: &TournamentOf<T>,
+			participants: &mut [Participant<T::AccountId>],
+			stalled: bool,
+		) -> Result<u32, DispatchError> {
+			let mut forfeits = 0;
+			for pairing in Pairings::<T>::take(id) {
+				let (white_points, black_points) = match pairing.black {
+					None => (WIN, 0),
+					Some(_) => Self::game_result(&pairing, participants, stalled)
+						.ok_or(Error::<T>::RoundNotFinished)?,
+				};
+				let white = pairing.white as usize;
+				participants[white].points.saturating_accrue(white_points);
+				let black = match pairing.black {
+					Some(black) => black as usize,
+					None => continue,
+				};
+				participants[black].points.saturating_accrue(black_points);
+				// by white if they didn't start the match, by black if they didn't join it
+				if !pairing.joined {
+					forfeits += 1;
+				}
+
+				if tournament.format == Format::Knockout {
+					// draws are won by the player who registered first, i.e. white
+					if black_points > white_points || white_points == 0 {
+						participants[white].eliminated_in = Some(number);
+					}
+					if white_points >= black_points {
+						participants[black].eliminated_in = Some(number);
+					}
+				}
+			}
+			Ok(forfeits)
+		}
+
+		/// The points of white and black in the game of `pairing`, if it's finished or the round
+		/// is `stalled`.
+		fn game_result(
+			pairing: &Pairing<T::Hash>,
+			participants: &[Participant<T::AccountId>],
+			stalled: bool,
+		) -> Option<(u32, u32)> {
+			let match_id = match pairing.match_id {
+				Some(match_id) => match_id,
+				// white didn't start the match
+				None => return stalled.then_some((0, WIN)),
+			};
+			let state = pallet_chess::Matches::<T>::get(match_id).map(|m| m.state);
+			match state {
+				// white aborted the match before black joined it
+				None if !pairing.joined => Some((0, WIN)),
+				// black didn't join the match
+				Some(MatchState::AwaitingOpponent) => stalled.then_some((WIN, 0)),
+				Some(MatchState::Won(winner))
+					if winner == participants[pairing.white as usize].who =>
+				{
+					Some((WIN, 0))
+				},
+				Some(MatchState::Won(_)) => Some((0, WIN)),
+				Some(MatchState::Drawn) => Some((DRAW, DRAW)),
+				// games still being played are drawn
+				_ => stalled.then_some((DRAW, DRAW)),
+			}
+		}
+
+		/// Pay `janitor` an `IncentiveShare` of the entry fee of the `forfeits` players who
+		/// forfeited, from the prize pool.
+		fn reward_janitor(
+			id: TournamentId,
+			tournament: &mut TournamentOf<T>,
+			janitor: T::AccountId,
+			forfeits: u32,
+		) -> DispatchResult {
+			let share = Percent::from_percent(<T as pallet_chess::Config>::IncentiveShare::get());
+			let amount = (share * tournament.entry_fee)
+				.saturating_mul(forfeits.into())
+				.min(tournament.pool);
+			if amount.is_zero() {
+				return Ok(());
+			}
+			<T as Config>::Assets::transfer(
+				tournament.entry_fee_asset,
+				&Self::pool_account(id),
+				&janitor,
+				amount,
+				false,
+			)?;
+			tournament.pool.saturating_reduce(amount);
+
+			Self::deposit_event(Event::JanitorRewarded { id, janitor, amount });
+			Ok(())
+		}
+
+		/// Pair the players of the next round of the tournament `id`.
+		fn pair(id: TournamentId, format: &Format, participants: &mut [Participant<T::AccountId>]) {
+			let mut order: Vec<u32> = (0..participants.len() as u32)
+				.filter(|i| participants[*i as usize].eliminated_in.is_none())
+				.collect();
+			if let Format::Swiss { .. } = format {
+				// stable, so players of the same score keep their seed order
+				order.sort_by(|a, b| {
+					participants[*b as usize].points.cmp(&participants[*a as usize].points)
+				});
+			}
+
+			let mut pairings = Vec::new();
+			// the lowest ranked player who hasn't had one gets the bye
+			if order.len() % 2 == 1 {
+				let index = order
+					.iter()
+					.rposition(|i| !participants[*i as usize].had_bye)
+					.unwrap_or(order.len() - 1);
+				let bye = order.remove(index);
+				participants[bye as usize].had_bye = true;
+				pairings.push(Pairing { white: bye, black: None, match_id: None, joined: false });
+			}
+
+			let opponents: Vec<BoundedVec<u32, T::MaxRounds>> =
+				(0..participants.len() as u32).map(|i| Opponents::<T>::get(id, i)).collect();
+			while !order.is_empty() {
+				let white = order.remove(0);
+				// the next player they haven't played, if any
+				let index =
+					order.iter().position(|i| !opponents[white as usize].contains(i)).unwrap_or(0);
+				let black = order.remove(index);
+				for (player, opponent) in [(white, black), (black, white)] {
+					Opponents::<T>::mutate(id, player, |opponents| {
+						let _ = opponents.try_push(opponent);
+					});
+				}
+				pairings.push(Pairing { white, black: Some(black), match_id: None, joined: false });
+			}
+			// there are at most as many pairings as players
+			Pairings::<T>::insert(id, BoundedVec::truncate_from(pairings));
+		}
+
+		/// Pay the prizes of the tournament `id` to its best ranked players.
+		fn distribute_prizes(
+			id: TournamentId,
+			tournament: &mut TournamentOf<T>,
+			participants: &[Participant<T::AccountId>],
+		) -> DispatchResult {
+			let mut ranking: Vec<usize> = (0..participants.len()).collect();
+			// players knocked out last first, then by score, then by seed
+			ranking.sort_by_key(|i| {
+				let participant = &participants[*i];
+				(
+					Reverse(participant.eliminated_in.unwrap_or(u32::MAX)),
+					Reverse(participant.points),
+				)
+			});
+
+			let prizes = Prizes::<T>::take(id);
+			let pool = tournament.pool;
+			let mut amounts: Vec<BalanceOf<T>> =
+				prizes.iter().take(ranking.len()).map(|prize| *prize * pool).collect();
+			// rounding and the prizes of missing ranks go to the winner
+			let paid = amounts.iter().fold(BalanceOf::<T>::zero(), |sum, a| sum.saturating_add(*a));
+			if let Some(first) = amounts.first_mut() {
+				first.saturating_accrue(pool.saturating_sub(paid));
+			}
+
+			for (rank, (index, amount)) in ranking.iter().zip(amounts).enumerate() {
+				if amount.is_zero() {
+					continue;
+				}
+				let who = participants[*index].who.clone();
+				<T as Config>::Assets::transfer(
+					tournament.entry_fee_asset,
+					&Self::pool_account(id),
+					&who,
+					amount,
+					false,
+				)?;
+				tournament.pool.saturating_reduce(amount);
+				Self::deposit_event(Event::PrizePaid { id, who, rank: rank as u32 + 1, amount });
+			}
+			Ok(())
+		}
+	}
+}
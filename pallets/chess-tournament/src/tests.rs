@@ -0,0 +1,531 @@
+use crate::{
+	mock::*, Config, Error, Event, Format, NextTournamentId, Pairings, Participants, TournamentId,
+	TournamentState, Tournaments,
+};
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use pallet_chess::{MatchState, MatchStyle};
+use sp_core::H256;
+use sp_runtime::{DispatchError, Percent};
+
+const FEE: u128 = 10 * UNITS;
+const STARTS_AT: u64 = 10;
+const ROUND_DURATION: u64 = 20;
+
+fn prizes(percents: &[u8]) -> BoundedVec<Percent, <Test as Config>::MaxPrizes> {
+	percents
+		.iter()
+		.map(|percent| Percent::from_percent(*percent))
+		.collect::<Vec<_>>()
+		.try_into()
+		.unwrap()
+}
+
+fn create(format: Format, max_players: u32, percents: &[u8]) -> Result<(), DispatchError> {
+	ChessTournament::create(
+		RuntimeOrigin::signed(ORGANIZER),
+		format,
+		MatchStyle::Rapid,
+		XUSD,
+		FEE,
+		max_players,
+		STARTS_AT,
+		ROUND_DURATION,
+		prizes(percents),
+	)
+}
+
+/// Create a tournament with `players` of `PLAYERS` registered, and return its ID.
+fn setup(format: Format, players: usize, percents: &[u8]) -> TournamentId {
+	assert_ok!(create(format, 8, percents));
+	for who in &PLAYERS[..players] {
+		assert_ok!(ChessTournament::register(RuntimeOrigin::signed(*who), 0));
+	}
+	0
+}
+
+fn advance(id: TournamentId) -> Result<(), DispatchError> {
+	ChessTournament::advance(RuntimeOrigin::signed(ORGANIZER), id)
+		.map(|_| ())
+		.map_err(|e| e.error)
+}
+
+/// The games of the current round, by index of the players.
+fn pairings(id: TournamentId) -> Vec<(u32, Option<u32>)> {
+	ChessTournament::pairings(id)
+		.iter()
+		.map(|pairing| (pairing.white, pairing.black))
+		.collect()
+}
+
+/// Create a match of `PLAYERS[white]` against `PLAYERS[black]`, without a bet, and return its ID.
+fn create_match(white: u32, black: u32) -> H256 {
+	let (white, black) = (PLAYERS[white as usize], PLAYERS[black as usize]);
+	assert_ok!(Chess::create_match(
+		RuntimeOrigin::signed(white),
+		black,
+		MatchStyle::Rapid,
+		XUSD,
+		0
+	));
+	pallet_chess::Matches::<Test>::iter()
+		.find(|(_, chess_match)| {
+			chess_match.challenger == white &&
+				chess_match.opponent == black &&
+				matches!(chess_match.state, MatchState::AwaitingOpponent)
+		})
+		.map(|(match_id, _)| match_id)
+		.unwrap()
+}
+
+/// Start the game of the players of index `white` and `black`, and return the ID of its match.
+fn start(id: TournamentId, white: u32, black: u32) -> H256 {
+	let match_id = create_match(white, black);
+	assert_ok!(ChessTournament::start_match(
+		RuntimeOrigin::signed(PLAYERS[white as usize]),
+		id,
+		match_id
+	));
+	match_id
+}
+
+/// Play the game of the players of index `white` and `black`, ending in `state`.
+fn play(id: TournamentId, white: u32, black: u32, state: MatchState<u64>) {
+	let match_id = start(id, white, black);
+	assert_ok!(ChessTournament::join_match(RuntimeOrigin::signed(PLAYERS[black as usize]), id));
+	pallet_chess::Matches::<Test>::mutate(match_id, |chess_match| {
+		chess_match.as_mut().unwrap().state = state;
+	});
+}
+
+fn points(id: TournamentId) -> Vec<u32> {
+	ChessTournament::participants(id)
+		.iter()
+		.map(|participant| participant.points)
+		.collect()
+}
+
+#[test]
+fn create_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create(Format::Swiss { rounds: 3 }, 8, &[60, 30, 10]));
+
+		let tournament = ChessTournament::tournaments(0).unwrap();
+		assert_eq!(tournament.organizer, ORGANIZER);
+		assert_eq!(tournament.state, TournamentState::Registration { starts_at: STARTS_AT });
+		assert_eq!(ChessTournament::prizes(0), prizes(&[60, 30, 10]));
+		System::assert_last_event(Event::TournamentCreated { id: 0, organizer: ORGANIZER }.into());
+	});
+}
+
+#[test]
+fn create_fails() {
+	new_test_ext().execute_with(|| {
+		for max_players in [1, 9] {
+			assert_noop!(
+				create(Format::Knockout, max_players, &[100]),
+				Error::<Test>::InvalidMaxPlayers
+			);
+		}
+		for rounds in [0, 4] {
+			assert_noop!(create(Format::Swiss { rounds }, 8, &[100]), Error::<Test>::InvalidRounds);
+		}
+		assert_noop!(create(Format::Knockout, 8, &[60, 30]), Error::<Test>::InvalidPrizes);
+		assert_noop!(create(Format::Knockout, 8, &[]), Error::<Test>::InvalidPrizes);
+		NextTournamentId::<Test>::put(TournamentId::MAX);
+		assert_noop!(create(Format::Knockout, 8, &[100]), Error::<Test>::NoAvailableTournamentId);
+		NextTournamentId::<Test>::kill();
+		assert_noop!(
+			ChessTournament::create(
+				RuntimeOrigin::signed(ORGANIZER),
+				Format::Knockout,
+				MatchStyle::Rapid,
+				XUSD,
+				FEE,
+				8,
+				STARTS_AT,
+				5,
+				prizes(&[100]),
+			),
+			Error::<Test>::RoundTooShort
+		);
+		assert_noop!(
+			ChessTournament::create(
+				RuntimeOrigin::signed(ORGANIZER),
+				Format::Knockout,
+				MatchStyle::Rapid,
+				XUSD,
+				FEE,
+				8,
+				1,
+				ROUND_DURATION,
+				prizes(&[100]),
+			),
+			Error::<Test>::StartInPast
+		);
+	});
+}
+
+#[test]
+fn register_pays_entry_fee() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create(Format::Knockout, 2, &[100]));
+
+		for who in &PLAYERS[..2] {
+			assert_ok!(ChessTournament::register(RuntimeOrigin::signed(*who), 0));
+		}
+
+		assert_eq!(Assets::balance(XUSD, PLAYERS[0]), 1_000 * UNITS - FEE);
+		assert_eq!(Assets::balance(XUSD, ChessTournament::pool_account(0)), 2 * FEE);
+		assert_eq!(ChessTournament::tournaments(0).unwrap().pool, 2 * FEE);
+		System::assert_last_event(Event::PlayerRegistered { id: 0, who: PLAYERS[1] }.into());
+
+		assert_noop!(
+			ChessTournament::register(RuntimeOrigin::signed(PLAYERS[0]), 0),
+			Error::<Test>::AlreadyRegistered
+		);
+		assert_noop!(
+			ChessTournament::register(RuntimeOrigin::signed(PLAYERS[2]), 0),
+			Error::<Test>::TournamentFull
+		);
+		assert_noop!(
+			ChessTournament::register(RuntimeOrigin::signed(PLAYERS[2]), 1),
+			Error::<Test>::UnknownTournament
+		);
+
+		assert_ok!(create(Format::Knockout, 2, &[100]));
+		System::set_block_number(STARTS_AT);
+		assert_noop!(
+			ChessTournament::register(RuntimeOrigin::signed(PLAYERS[2]), 1),
+			Error::<Test>::RegistrationClosed
+		);
+	});
+}
+
+#[test]
+fn cancel_refunds_entry_fees() {
+	new_test_ext().execute_with(|| {
+		let id = setup(Format::Knockout, 2, &[100]);
+		assert_noop!(
+			ChessTournament::cancel(RuntimeOrigin::signed(PLAYERS[0]), id),
+			Error::<Test>::NotOrganizer
+		);
+
+		assert_ok!(ChessTournament::cancel(RuntimeOrigin::signed(ORGANIZER), id));
+
+		assert_eq!(Assets::balance(XUSD, PLAYERS[0]), 1_000 * UNITS);
+		assert_eq!(Assets::balance(XUSD, ChessTournament::pool_account(id)), 0);
+		assert!(Tournaments::<Test>::get(id).is_none());
+		assert!(Participants::<Test>::get(id).is_empty());
+		System::assert_last_event(Event::TournamentCancelled { id }.into());
+	});
+}
+
+#[test]
+fn tournaments_without_enough_players_are_cancelled() {
+	new_test_ext().execute_with(|| {
+		let id = setup(Format::Knockout, 1, &[100]);
+		assert_noop!(advance(id), Error::<Test>::TooEarly);
+
+		System::set_block_number(STARTS_AT);
+		assert_ok!(advance(id));
+
+		assert_eq!(Assets::balance(XUSD, PLAYERS[0]), 1_000 * UNITS);
+		assert!(Tournaments::<Test>::get(id).is_none());
+		System::assert_last_event(Event::TournamentCancelled { id }.into());
+	});
+}
+
+#[test]
+fn swiss_tournament_works() {
+	new_test_ext().execute_with(|| {
+		let id = setup(Format::Swiss { rounds: 2 }, 4, &[60, 40]);
+		System::set_block_number(STARTS_AT);
+		assert_ok!(advance(id));
+		assert_noop!(
+			ChessTournament::cancel(RuntimeOrigin::signed(ORGANIZER), id),
+			Error::<Test>::RegistrationClosed
+		);
+
+		assert_eq!(
+			ChessTournament::tournaments(id).unwrap().state,
+			TournamentState::Round { number: 1, deadline: STARTS_AT + ROUND_DURATION }
+		);
+		assert_eq!(pairings(id), vec![(0, Some(1)), (2, Some(3))]);
+		System::assert_last_event(Event::RoundStarted { id, round: 1 }.into());
+
+		play(id, 0, 1, MatchState::Won(PLAYERS[0]));
+		assert_noop!(advance(id), Error::<Test>::RoundNotFinished);
+		play(id, 2, 3, MatchState::Won(PLAYERS[2]));
+		assert_ok!(advance(id));
+
+		assert_eq!(points(id), vec![2, 0, 2, 0]);
+		// players of the same score who haven't played each other
+		assert_eq!(pairings(id), vec![(0, Some(2)), (1, Some(3))]);
+
+		play(id, 0, 2, MatchState::Drawn);
+		play(id, 1, 3, MatchState::Won(PLAYERS[3]));
+		assert_ok!(advance(id));
+
+		assert_eq!(points(id), vec![3, 0, 3, 2]);
+		assert_eq!(ChessTournament::tournaments(id).unwrap().state, TournamentState::Finished);
+		assert!(Pairings::<Test>::get(id).is_empty());
+		// ties are broken by seed
+		assert_eq!(Assets::balance(XUSD, PLAYERS[0]), 1_000 * UNITS - FEE + 24 * UNITS);
+		assert_eq!(Assets::balance(XUSD, PLAYERS[2]), 1_000 * UNITS - FEE + 16 * UNITS);
+		assert_eq!(Assets::balance(XUSD, ChessTournament::pool_account(id)), 0);
+		System::assert_has_event(
+			Event::PrizePaid { id, who: PLAYERS[2], rank: 2, amount: 16 * UNITS }.into(),
+		);
+		System::assert_last_event(Event::TournamentFinished { id }.into());
+
+		assert_noop!(advance(id), Error::<Test>::TournamentFinished);
+	});
+}
+
+#[test]
+fn byes_go_to_the_lowest_ranked_players_without_one() {
+	new_test_ext().execute_with(|| {
+		let id = setup(Format::Swiss { rounds: 2 }, 3, &[100]);
+		System::set_block_number(STARTS_AT);
+		assert_ok!(advance(id));
+
+		assert_eq!(pairings(id), vec![(2, None), (0, Some(1))]);
+		assert_noop!(
+			ChessTournament::start_match(RuntimeOrigin::signed(PLAYERS[2]), id, H256::zero()),
+			Error::<Test>::NotPaired
+		);
+
+		play(id, 0, 1, MatchState::Won(PLAYERS[0]));
+		assert_ok!(advance(id));
+
+		assert_eq!(points(id), vec![2, 0, 2]);
+		assert_eq!(pairings(id), vec![(1, None), (0, Some(2))]);
+	});
+}
+
+#[test]
+fn knockout_tournament_works() {
+	new_test_ext().execute_with(|| {
+		let id = setup(Format::Knockout, 5, &[100]);
+		System::set_block_number(STARTS_AT);
+		assert_ok!(advance(id));
+
+		assert_eq!(pairings(id), vec![(4, None), (0, Some(1)), (2, Some(3))]);
+		play(id, 0, 1, MatchState::Won(PLAYERS[1]));
+		// drawn games are won by the player who registered first
+		play(id, 2, 3, MatchState::Drawn);
+		assert_ok!(advance(id));
+
+		let eliminated: Vec<_> = ChessTournament::participants(id)
+			.iter()
+			.map(|participant| participant.eliminated_in)
+			.collect();
+		assert_eq!(eliminated, vec![Some(1), None, None, Some(1), None]);
+		assert_eq!(pairings(id), vec![(2, None), (1, Some(4))]);
+
+		play(id, 1, 4, MatchState::Won(PLAYERS[1]));
+		assert_ok!(advance(id));
+		assert_eq!(pairings(id), vec![(1, Some(2))]);
+
+		play(id, 1, 2, MatchState::Won(PLAYERS[2]));
+		assert_ok!(advance(id));
+
+		assert_eq!(ChessTournament::tournaments(id).unwrap().state, TournamentState::Finished);
+		assert_eq!(Assets::balance(XUSD, PLAYERS[2]), 1_000 * UNITS - FEE + 5 * FEE);
+		System::assert_has_event(
+			Event::PrizePaid { id, who: PLAYERS[2], rank: 1, amount: 5 * FEE }.into(),
+		);
+	});
+}
+
+#[test]
+fn stalled_rounds_reward_the_janitor() {
+	new_test_ext().execute_with(|| {
+		let id = setup(Format::Swiss { rounds: 1 }, 4, &[100]);
+		System::set_block_number(STARTS_AT);
+		assert_ok!(advance(id));
+
+		// black doesn't join, and the other game is not started
+		let match_id = start(id, 0, 1);
+		assert_noop!(
+			ChessTournament::start_match(RuntimeOrigin::signed(PLAYERS[0]), id, match_id),
+			Error::<Test>::AlreadyStarted
+		);
+		System::set_block_number(STARTS_AT + ROUND_DURATION - 1);
+		assert_noop!(advance(id), Error::<Test>::RoundNotFinished);
+
+		System::set_block_number(STARTS_AT + ROUND_DURATION);
+		assert_ok!(advance(id));
+
+		// white forfeits the game they didn't start
+		assert_eq!(points(id), vec![2, 0, 0, 2]);
+		// 10% of the entry fee of the 2 players who forfeited
+		let reward = 2 * FEE / 10;
+		assert_eq!(Assets::balance(XUSD, ORGANIZER), reward);
+		assert_eq!(Assets::balance(XUSD, PLAYERS[0]), 1_000 * UNITS + 3 * FEE - reward);
+		System::assert_has_event(
+			Event::JanitorRewarded { id, janitor: ORGANIZER, amount: reward }.into(),
+		);
+	});
+}
+
+#[test]
+fn aborted_games_are_forfeited_by_white() {
+	new_test_ext().execute_with(|| {
+		let id = setup(Format::Swiss { rounds: 1 }, 4, &[100]);
+		System::set_block_number(STARTS_AT);
+		assert_ok!(advance(id));
+
+		let match_id = start(id, 0, 1);
+		assert_ok!(Chess::abort_match(RuntimeOrigin::signed(PLAYERS[0]), match_id));
+		assert!(ChessTournament::join_match(RuntimeOrigin::signed(PLAYERS[1]), id).is_err());
+		play(id, 2, 3, MatchState::Drawn);
+
+		// the game is over without waiting for the deadline
+		assert_ok!(advance(id));
+
+		assert_eq!(points(id), vec![0, 2, 1, 1]);
+		assert_eq!(Assets::balance(XUSD, PLAYERS[1]), 1_000 * UNITS + 3 * FEE);
+	});
+}
+
+#[test]
+fn unstarted_games_are_forfeited_by_white() {
+	new_test_ext().execute_with(|| {
+		let id = setup(Format::Knockout, 4, &[100]);
+		System::set_block_number(STARTS_AT);
+		assert_ok!(advance(id));
+
+		// white never starts the game of 0 and 1
+		play(id, 2, 3, MatchState::Won(PLAYERS[2]));
+		System::set_block_number(STARTS_AT + ROUND_DURATION);
+		assert_ok!(ChessTournament::advance(RuntimeOrigin::signed(PLAYERS[0]), id));
+
+		assert_eq!(points(id), vec![0, 2, 2, 0]);
+		let eliminated: Vec<_> = ChessTournament::participants(id)
+			.iter()
+			.map(|participant| participant.eliminated_in)
+			.collect();
+		assert_eq!(eliminated, vec![Some(1), None, None, Some(1)]);
+		assert_eq!(pairings(id), vec![(1, Some(2))]);
+		// only white forfeited
+		System::assert_has_event(
+			Event::JanitorRewarded { id, janitor: PLAYERS[0], amount: FEE / 10 }.into(),
+		);
+	});
+}
+
+#[test]
+fn played_games_are_scored() {
+	new_test_ext().execute_with(|| {
+		let id = setup(Format::Swiss { rounds: 1 }, 2, &[100]);
+		System::set_block_number(STARTS_AT);
+		assert_ok!(advance(id));
+
+		let match_id = start(id, 0, 1);
+		assert_ok!(ChessTournament::join_match(RuntimeOrigin::signed(PLAYERS[1]), id));
+		// the fool's mate, won by black
+		let (white, black) = (PLAYERS[0], PLAYERS[1]);
+		for (who, mv) in [(white, "f2f3"), (black, "e7e5"), (white, "g2g4"), (black, "d8h4")] {
+			assert_ok!(Chess::make_move(RuntimeOrigin::signed(who), match_id, mv.into()));
+		}
+		assert_ok!(advance(id));
+
+		assert_eq!(points(id), vec![0, 2]);
+		assert_eq!(ChessTournament::tournaments(id).unwrap().state, TournamentState::Finished);
+		assert_eq!(Assets::balance(XUSD, PLAYERS[1]), 1_000 * UNITS + FEE);
+	});
+}
+
+#[test]
+fn start_match_checks_the_match() {
+	new_test_ext().execute_with(|| {
+		let id = setup(Format::Swiss { rounds: 1 }, 4, &[100]);
+		assert_noop!(
+			ChessTournament::start_match(RuntimeOrigin::signed(PLAYERS[0]), id, H256::zero()),
+			Error::<Test>::NotInRound
+		);
+		System::set_block_number(STARTS_AT);
+		assert_ok!(advance(id));
+
+		assert_noop!(
+			ChessTournament::start_match(RuntimeOrigin::signed(PLAYERS[0]), id, H256::zero()),
+			Error::<Test>::UnknownMatch
+		);
+		// a match between the players of another game
+		let match_id = create_match(2, 1);
+		assert_noop!(
+			ChessTournament::start_match(RuntimeOrigin::signed(PLAYERS[0]), id, match_id),
+			Error::<Test>::WrongMatch
+		);
+		// a match with a bet
+		assert_ok!(Chess::create_match(
+			RuntimeOrigin::signed(PLAYERS[0]),
+			PLAYERS[1],
+			MatchStyle::Rapid,
+			XUSD,
+			FEE
+		));
+		let match_id = pallet_chess::Matches::<Test>::iter()
+			.find(|(_, chess_match)| chess_match.challenger == PLAYERS[0])
+			.map(|(match_id, _)| match_id)
+			.unwrap();
+		assert_noop!(
+			ChessTournament::start_match(RuntimeOrigin::signed(PLAYERS[0]), id, match_id),
+			Error::<Test>::WrongMatch
+		);
+		assert_ok!(Chess::abort_match(RuntimeOrigin::signed(PLAYERS[0]), match_id));
+
+		let match_id = start(id, 0, 1);
+		assert_eq!(ChessTournament::pairings(id)[0].match_id, Some(match_id));
+		System::assert_last_event(Event::GameStarted { id, match_id }.into());
+	});
+}
+
+#[test]
+fn join_match_checks_the_game() {
+	new_test_ext().execute_with(|| {
+		let id = setup(Format::Swiss { rounds: 1 }, 4, &[100]);
+		System::set_block_number(STARTS_AT);
+		assert_ok!(advance(id));
+
+		assert_noop!(
+			ChessTournament::join_match(RuntimeOrigin::signed(PLAYERS[1]), id),
+			Error::<Test>::NotStarted
+		);
+		assert_noop!(
+			ChessTournament::join_match(RuntimeOrigin::signed(PLAYERS[0]), id),
+			Error::<Test>::NotPaired
+		);
+
+		play(id, 0, 1, MatchState::Drawn);
+		let pairing = &ChessTournament::pairings(id)[0];
+		assert!(pairing.joined);
+		System::assert_last_event(
+			Event::GameJoined { id, match_id: pairing.match_id.unwrap() }.into(),
+		);
+		assert_noop!(
+			ChessTournament::join_match(RuntimeOrigin::signed(PLAYERS[1]), id),
+			Error::<Test>::AlreadyStarted
+		);
+	});
+}
+
+#[test]
+fn chess_calls_are_filtered() {
+	new_test_ext().execute_with(|| {
+		let id = setup(Format::Swiss { rounds: 1 }, 4, &[100]);
+		System::set_block_number(STARTS_AT);
+		assert_ok!(advance(id));
+		start(id, 0, 1);
+
+		ChessCallsPaused::set(true);
+		assert_noop!(
+			ChessTournament::join_match(RuntimeOrigin::signed(PLAYERS[1]), id),
+			frame_system::Error::<Test>::CallFiltered
+		);
+
+		ChessCallsPaused::set(false);
+		assert_ok!(ChessTournament::join_match(RuntimeOrigin::signed(PLAYERS[1]), id));
+	});
+}
@@ -0,0 +1,159 @@
+//! Weights for `pallet_chess_tournament`
+//!
+//! Estimated from the storage accesses of each call, the transfers of the entry fees and prizes
+//! being counted as two reads and two writes. The weights of `pallet_chess` calls are added by the
+//! pallet.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+pub trait WeightInfo {
+	fn create() -> Weight;
+	fn register() -> Weight;
+	fn cancel(n: u32, ) -> Weight;
+	fn advance(n: u32, ) -> Weight;
+	fn start_match() -> Weight;
+	fn join_match() -> Weight;
+}
+
+/// Weight functions for `pallet_chess_tournament`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: crate::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: ChessTournament NextTournamentId (r:1 w:1)
+	// Storage: ChessTournament Tournaments (r:0 w:1)
+	// Storage: ChessTournament Prizes (r:0 w:1)
+	fn create() -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: ChessTournament Tournaments (r:1 w:1)
+	// Storage: ChessTournament Participants (r:1 w:1)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	fn register() -> Weight {
+		Weight::from_ref_time(40_000_000)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	// Storage: ChessTournament Tournaments (r:1 w:1)
+	// Storage: ChessTournament Participants (r:1 w:1)
+	// Storage: ChessTournament Prizes (r:0 w:1)
+	// Storage: Assets Asset (r:n w:n)
+	// Storage: Assets Account (r:n w:n)
+	/// The range of component `n` is `[0, MaxPlayers]`.
+	fn cancel(n: u32, ) -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(Weight::from_ref_time(30_000_000).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(3))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
+	// Storage: ChessTournament Tournaments (r:1 w:1)
+	// Storage: ChessTournament Participants (r:1 w:1)
+	// Storage: ChessTournament Pairings (r:1 w:1)
+	// Storage: ChessTournament Prizes (r:1 w:1)
+	// Storage: Chess Matches (r:n w:0)
+	// Storage: ChessTournament Opponents (r:2n w:2n)
+	// Storage: Assets Asset (r:n w:n)
+	// Storage: Assets Account (r:n w:n)
+	/// The range of component `n` is `[0, MaxPlayers]`.
+	fn advance(n: u32, ) -> Weight {
+		Weight::from_ref_time(30_000_000)
+			.saturating_add(Weight::from_ref_time(40_000_000).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
+			.saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(n.into())))
+	}
+	// Storage: ChessTournament Tournaments (r:1 w:0)
+	// Storage: ChessTournament Participants (r:1 w:0)
+	// Storage: ChessTournament Pairings (r:1 w:1)
+	// Storage: Chess Matches (r:1 w:0)
+	fn start_match() -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: ChessTournament Tournaments (r:1 w:0)
+	// Storage: ChessTournament Participants (r:1 w:0)
+	// Storage: ChessTournament Pairings (r:1 w:1)
+	fn join_match() -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+}
+
+impl WeightInfo for () {
+	// Storage: ChessTournament NextTournamentId (r:1 w:1)
+	// Storage: ChessTournament Tournaments (r:0 w:1)
+	// Storage: ChessTournament Prizes (r:0 w:1)
+	fn create() -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	// Storage: ChessTournament Tournaments (r:1 w:1)
+	// Storage: ChessTournament Participants (r:1 w:1)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	fn register() -> Weight {
+		Weight::from_ref_time(40_000_000)
+			.saturating_add(RocksDbWeight::get().reads(4))
+			.saturating_add(RocksDbWeight::get().writes(4))
+	}
+	// Storage: ChessTournament Tournaments (r:1 w:1)
+	// Storage: ChessTournament Participants (r:1 w:1)
+	// Storage: ChessTournament Prizes (r:0 w:1)
+	// Storage: Assets Asset (r:n w:n)
+	// Storage: Assets Account (r:n w:n)
+	/// The range of component `n` is `[0, MaxPlayers]`.
+	fn cancel(n: u32, ) -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(Weight::from_ref_time(30_000_000).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(3))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
+	// Storage: ChessTournament Tournaments (r:1 w:1)
+	// Storage: ChessTournament Participants (r:1 w:1)
+	// Storage: ChessTournament Pairings (r:1 w:1)
+	// Storage: ChessTournament Prizes (r:1 w:1)
+	// Storage: Chess Matches (r:n w:0)
+	// Storage: ChessTournament Opponents (r:2n w:2n)
+	// Storage: Assets Asset (r:n w:n)
+	// Storage: Assets Account (r:n w:n)
+	/// The range of component `n` is `[0, MaxPlayers]`.
+	fn advance(n: u32, ) -> Weight {
+		Weight::from_ref_time(30_000_000)
+			.saturating_add(Weight::from_ref_time(40_000_000).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(4))
+			.saturating_add(RocksDbWeight::get().writes(4))
+			.saturating_add(RocksDbWeight::get().reads((5_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(n.into())))
+	}
+	// Storage: ChessTournament Tournaments (r:1 w:0)
+	// Storage: ChessTournament Participants (r:1 w:0)
+	// Storage: ChessTournament Pairings (r:1 w:1)
+	// Storage: Chess Matches (r:1 w:0)
+	fn start_match() -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(RocksDbWeight::get().reads(4))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	// Storage: ChessTournament Tournaments (r:1 w:0)
+	// Storage: ChessTournament Participants (r:1 w:0)
+	// Storage: ChessTournament Pairings (r:1 w:1)
+	fn join_match() -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+}
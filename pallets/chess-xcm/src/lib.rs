@@ -0,0 +1,453 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Lets accounts of sibling parachains play `pallet_chess` matches, staking reserve-backed assets.
+//!
+//! Players reserve transfer their stake to their account on this chain, derived from their
+//! location, and `Transact` [`Pallet::create_match`] or [`Pallet::join_match`] with their XCM
+//! origin. Moves are made by `Transact`ing `pallet_chess` calls as their account.
+//!
+//! The stakes of players on siblings must be assets which can be transferred back to them.
+//! `pallet_chess` pays their winnings and refunds to their account on this chain. The matches
+//! joined through this pallet are checked in `on_idle`, as weight allows, and once they end, all
+//! of the asset of their bet held by their players of siblings is transferred to their account
+//! on their parachain. Matches created by players of siblings and joined directly with
+//! `pallet_chess` are checked once anyone [`Pallet::watch_match`]es them. Players can also
+//! [`Pallet::claim`] or [`Pallet::withdraw`] their assets themselves, e.g. when a payout failed.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		pallet_prelude::*,
+		storage::with_storage_layer,
+		traits::{fungibles::Inspect, Contains, UnfilteredDispatchable},
+		weights::Weight,
+	};
+	use frame_system::pallet_prelude::*;
+	use pallet_chess::{MatchState, MatchStyle, WeightInfo as ChessWeightInfo};
+	use sp_runtime::traits::{SaturatedConversion, UniqueSaturatedInto, Zero};
+	use sp_std::vec::Vec;
+	use xcm::latest::prelude::*;
+	use xcm_executor::traits::{Convert, WeightBounds};
+
+	type RuntimeCallOf<T> = <T as frame_system::Config>::RuntimeCall;
+	pub type AssetIdOf<T> = <<T as pallet_chess::Config>::Assets as Inspect<
+		<T as frame_system::Config>::AccountId,
+	>>::AssetId;
+	pub type AssetBalanceOf<T> = <T as pallet_chess::Config>::AssetBalance;
+	pub type WatchedMatchOf<T> = WatchedMatch<
+		<T as frame_system::Config>::Hash,
+		<T as frame_system::Config>::AccountId,
+		AssetIdOf<T>,
+	>;
+
+	/// A match of players of siblings being played.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct WatchedMatch<Hash, AccountId, AssetId> {
+		pub match_id: Hash,
+		/// The challenger and the opponent.
+		pub players: [AccountId; 2],
+		/// The asset of the bet, which the players are paid out.
+		pub bet_asset_id: AssetId,
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_chess::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The XCM origin of accounts on sibling parachains.
+		type PlayerOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = MultiLocation>;
+		/// Converts the location of players to their account on this chain.
+		type LocationToAccountId: Convert<MultiLocation, Self::AccountId>;
+		/// Converts between the `MultiLocation` of assets and their local `AssetId`.
+		type AssetLocation: Convert<MultiLocation, AssetIdOf<Self>>;
+		/// This chain, as seen from its siblings.
+		#[pallet::constant]
+		type SelfLocation: Get<MultiLocation>;
+		/// Executes the messages transferring assets back to players.
+		type XcmExecutor: ExecuteXcm<RuntimeCallOf<Self>>;
+		/// Determines the weight of the executed messages.
+		type Weigher: WeightBounds<RuntimeCallOf<Self>>;
+		/// Filters the `pallet_chess` calls this pallet dispatches for players of siblings, which
+		/// bypass `BaseCallFilter`.
+		type ChessCallFilter: Contains<pallet_chess::Call<Self>>;
+		/// The maximum number of matches of players of siblings checked for payouts.
+		#[pallet::constant]
+		type MaxOngoingMatches: Get<u32>;
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The location of the players of siblings who created or joined a match, by account.
+	#[pallet::storage]
+	pub type Players<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, MultiLocation>;
+
+	/// The matches of players of siblings being played, whose players are paid out once they end.
+	#[pallet::storage]
+	#[pallet::getter(fn ongoing_matches)]
+	pub type OngoingMatches<T: Config> =
+		StorageValue<_, BoundedVec<WatchedMatchOf<T>, T::MaxOngoingMatches>, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// `asset` was transferred from the account `who` to the player at `location`.
+		PaidOut { who: T::AccountId, location: MultiLocation, asset: MultiAsset },
+		/// The match `match_id` is checked for payouts.
+		MatchWatched { match_id: T::Hash },
+		/// The winnings or refund of `who` in the match `match_id` could not be transferred, and
+		/// are left in their account on this chain.
+		PayoutFailed { who: T::AccountId, match_id: T::Hash, error: DispatchError },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The origin is not an account of a sibling parachain
+		NotSiblingAccount,
+		/// The origin has no account on this chain
+		NoAccount,
+		/// The asset has no `MultiLocation`, or its reserve is not supported
+		UnsupportedAsset,
+		/// The match does not exist
+		UnknownMatch,
+		/// The message transferring the assets could not be weighed
+		UnweighableMessage,
+		/// The message transferring the assets failed to execute
+		PayoutFailed,
+		/// The account of the player holds none of the asset
+		NothingToClaim,
+		/// The match is not being played
+		MatchNotOngoing,
+		/// No player of the match is a player of a sibling
+		NoSiblingPlayer,
+		/// The match is already checked for payouts
+		AlreadyWatched,
+		/// `MaxOngoingMatches` matches are already checked for payouts
+		TooManyOngoingMatches,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			if !Self::on_idle_weight(0).all_lte(remaining_weight) {
+				return Weight::zero();
+			}
+			let mut ongoing = OngoingMatches::<T>::get();
+			let len = ongoing.len() as u32;
+			let mut checked = 0;
+			while checked < len && Self::on_idle_weight(checked + 1).all_lte(remaining_weight) {
+				checked += 1;
+			}
+			if checked == 0 {
+				return Self::on_idle_weight(0);
+			}
+
+			// rotate the first match checked, so that all are when weight is short
+			let start: u32 = n.unique_saturated_into() % len;
+			let finished: Vec<T::Hash> = (start..start + checked)
+				.map(|i| &ongoing[(i % len) as usize])
+				.filter(|watched| Self::try_pay_out(watched))
+				.map(|watched| watched.match_id)
+				.collect();
+			if !finished.is_empty() {
+				ongoing.retain(|watched| !finished.contains(&watched.match_id));
+				OngoingMatches::<T>::put(ongoing);
+			}
+
+			Self::on_idle_weight(checked)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create a match against `opponent`, staking `bet_amount` of `bet_asset_id` from the
+		/// account of the player on this chain.
+		#[pallet::call_index(0)]
+		#[pallet::weight(
+			<T as pallet::Config>::WeightInfo::create_match()
+				.saturating_add(<T as pallet_chess::Config>::WeightInfo::create_match())
+		)]
+		pub fn create_match(
+			origin: OriginFor<T>,
+			opponent: T::AccountId,
+			style: MatchStyle,
+			bet_asset_id: AssetIdOf<T>,
+			bet_amount: AssetBalanceOf<T>,
+		) -> DispatchResult {
+			let (who, location) = Self::ensure_player(origin)?;
+			Self::ensure_payable(&location, bet_asset_id)?;
+			Players::<T>::insert(&who, location);
+
+			let create_match =
+				pallet_chess::Call::<T>::create_match { opponent, style, bet_asset_id, bet_amount };
+			ensure!(
+				T::ChessCallFilter::contains(&create_match),
+				frame_system::Error::<T>::CallFiltered
+			);
+
+			create_match
+				.dispatch_bypass_filter(frame_system::RawOrigin::Signed(who).into())
+				.map(|_| ())
+				.map_err(|e| e.error)
+		}
+
+		/// Join the match `match_id`, staking its bet from the account of the player on this
+		/// chain.
+		#[pallet::call_index(1)]
+		#[pallet::weight(
+			<T as pallet::Config>::WeightInfo::join_match()
+				.saturating_add(<T as pallet_chess::Config>::WeightInfo::join_match())
+		)]
+		pub fn join_match(origin: OriginFor<T>, match_id: T::Hash) -> DispatchResult {
+			let (who, location) = Self::ensure_player(origin)?;
+			let chess_match =
+				pallet_chess::Matches::<T>::get(match_id).ok_or(Error::<T>::UnknownMatch)?;
+			Self::ensure_payable(&location, chess_match.bet_asset_id)?;
+			Players::<T>::insert(&who, location);
+			Self::watch(WatchedMatch {
+				match_id,
+				players: [chess_match.challenger, who.clone()],
+				bet_asset_id: chess_match.bet_asset_id,
+			})?;
+			let join_match = pallet_chess::Call::<T>::join_match { match_id };
+			ensure!(
+				T::ChessCallFilter::contains(&join_match),
+				frame_system::Error::<T>::CallFiltered
+			);
+
+			join_match
+				.dispatch_bypass_filter(frame_system::RawOrigin::Signed(who).into())
+				.map(|_| ())
+				.map_err(|e| e.error)
+		}
+
+		/// Transfer `amount` of `asset_id` from the account of the player on this chain to their
+		/// account on their parachain.
+		#[pallet::call_index(2)]
+		#[pallet::weight(
+			<T as pallet::Config>::WeightInfo::withdraw()
+				.saturating_add(Pallet::<T>::max_payout_weight())
+		)]
+		pub fn withdraw(
+			origin: OriginFor<T>,
+			asset_id: AssetIdOf<T>,
+			amount: AssetBalanceOf<T>,
+		) -> DispatchResult {
+			let (who, location) = Self::ensure_player(origin)?;
+			Self::pay_out(who, location, asset_id, amount)
+		}
+
+		/// Transfer all of `asset_id` from the account of the player on this chain, e.g. their
+		/// winnings and refunds, to their account on their parachain.
+		#[pallet::call_index(3)]
+		#[pallet::weight(
+			<T as pallet::Config>::WeightInfo::claim()
+				.saturating_add(Pallet::<T>::max_payout_weight())
+		)]
+		pub fn claim(origin: OriginFor<T>, asset_id: AssetIdOf<T>) -> DispatchResult {
+			let (who, location) = Self::ensure_player(origin)?;
+			let amount =
+				<T as pallet_chess::Config>::Assets::reducible_balance(asset_id, &who, false);
+			ensure!(!amount.is_zero(), Error::<T>::NothingToClaim);
+			Self::pay_out(who, location, asset_id, amount.saturated_into())
+		}
+
+		/// Check the match `match_id`, created by a player of a sibling and joined with
+		/// `pallet_chess`, for payouts.
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::watch_match())]
+		pub fn watch_match(origin: OriginFor<T>, match_id: T::Hash) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let chess_match =
+				pallet_chess::Matches::<T>::get(match_id).ok_or(Error::<T>::UnknownMatch)?;
+			ensure!(
+				matches!(chess_match.state, MatchState::OnGoing(_)),
+				Error::<T>::MatchNotOngoing
+			);
+			ensure!(
+				Players::<T>::contains_key(&chess_match.challenger) ||
+					Players::<T>::contains_key(&chess_match.opponent),
+				Error::<T>::NoSiblingPlayer
+			);
+			Self::watch(WatchedMatch {
+				match_id,
+				players: [chess_match.challenger, chess_match.opponent],
+				bet_asset_id: chess_match.bet_asset_id,
+			})
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The location of the player dispatching `origin`, and their account on this chain.
+		fn ensure_player(
+			origin: OriginFor<T>,
+		) -> Result<(T::AccountId, MultiLocation), DispatchError> {
+			let location = T::PlayerOrigin::ensure_origin(origin)?;
+			ensure!(
+				matches!(
+					location,
+					MultiLocation { parents: 1, interior: X2(Parachain(_), AccountId32 { .. }) }
+				),
+				Error::<T>::NotSiblingAccount
+			);
+			let who = T::LocationToAccountId::convert_ref(&location)
+				.map_err(|()| Error::<T>::NoAccount)?;
+			Ok((who, location))
+		}
+
+		/// Ensure that `asset_id` can be transferred back to the player at `location`.
+		fn ensure_payable(location: &MultiLocation, asset_id: AssetIdOf<T>) -> DispatchResult {
+			let asset_location = T::AssetLocation::reverse_ref(asset_id)
+				.map_err(|()| Error::<T>::UnsupportedAsset)?;
+			Self::payout_message(location, (asset_location, 0u128).into())
+				.map(|_| ())
+				.ok_or_else(|| Error::<T>::UnsupportedAsset.into())
+		}
+
+		/// Check the match `watched` for payouts.
+		fn watch(watched: WatchedMatchOf<T>) -> DispatchResult {
+			let match_id = watched.match_id;
+			OngoingMatches::<T>::try_mutate(|ongoing| {
+				ensure!(
+					!ongoing.iter().any(|watched| watched.match_id == match_id),
+					Error::<T>::AlreadyWatched
+				);
+				ongoing.try_push(watched).map_err(|_| Error::<T>::TooManyOngoingMatches)
+			})?;
+			Self::deposit_event(Event::MatchWatched { match_id });
+			Ok(())
+		}
+
+		/// Pay the players of siblings of the match `watched` out if it ended, and return whether
+		/// it did.
+		///
+		/// Matches which are no longer stored ended too.
+		fn try_pay_out(watched: &WatchedMatchOf<T>) -> bool {
+			let match_id = watched.match_id;
+			let ongoing = pallet_chess::Matches::<T>::get(match_id).map_or(false, |chess_match| {
+				!matches!(chess_match.state, MatchState::Won(_) | MatchState::Drawn)
+			});
+			if ongoing {
+				return false;
+			}
+			for who in watched.players.iter().cloned() {
+				let location = match Players::<T>::get(&who) {
+					Some(location) => location,
+					None => continue,
+				};
+				let amount = <T as pallet_chess::Config>::Assets::reducible_balance(
+					watched.bet_asset_id,
+					&who,
+					false,
+				);
+				if amount.is_zero() {
+					continue;
+				}
+				// a failed transfer leaves the assets in the account of the player
+				let paid_out = with_storage_layer(|| {
+					Self::pay_out(
+						who.clone(),
+						location,
+						watched.bet_asset_id,
+						amount.saturated_into(),
+					)
+				});
+				if let Err(error) = paid_out {
+					Self::deposit_event(Event::PayoutFailed { who, match_id, error });
+				}
+			}
+			true
+		}
+
+		/// The weight of `on_idle` checking `n` matches, paying both players of each out.
+		fn on_idle_weight(n: u32) -> Weight {
+			<T as pallet::Config>::WeightInfo::on_idle(n)
+				.saturating_add(Self::max_payout_weight().saturating_mul(2 * n as u64))
+		}
+
+		/// Transfer `amount` of `asset_id` from the account `who` of the player at `location` to
+		/// their account on their parachain.
+		fn pay_out(
+			who: T::AccountId,
+			location: MultiLocation,
+			asset_id: AssetIdOf<T>,
+			amount: AssetBalanceOf<T>,
+		) -> DispatchResult {
+			let asset_location = T::AssetLocation::reverse_ref(asset_id)
+				.map_err(|()| Error::<T>::UnsupportedAsset)?;
+			let asset: MultiAsset = (asset_location, amount.saturated_into::<u128>()).into();
+
+			let mut message = Self::payout_message(&location, asset.clone())
+				.ok_or(Error::<T>::UnsupportedAsset)?;
+			let weight =
+				T::Weigher::weight(&mut message).map_err(|()| Error::<T>::UnweighableMessage)?;
+			ensure!(
+				Weight::from_ref_time(weight).all_lte(Self::max_payout_weight()),
+				Error::<T>::UnweighableMessage
+			);
+
+			// the transfer is paid by the extrinsic's weight
+			let outcome =
+				T::XcmExecutor::execute_xcm_in_credit(location.clone(), message, weight, weight);
+			ensure!(matches!(outcome, Outcome::Complete(_)), Error::<T>::PayoutFailed);
+
+			Self::deposit_event(Event::PaidOut { who, location, asset });
+			Ok(())
+		}
+
+		/// The message transferring `asset` from the account of the player at `location` to their
+		/// account on their parachain.
+		fn payout_message(
+			location: &MultiLocation,
+			asset: MultiAsset,
+		) -> Option<Xcm<RuntimeCallOf<T>>> {
+			let (para_id, id) = match location {
+				MultiLocation {
+					parents: 1,
+					interior: X2(Parachain(para_id), AccountId32 { id, .. }),
+				} => (*para_id, *id),
+				_ => return None,
+			};
+			let (asset_location, amount) = match asset {
+				MultiAsset { id: Concrete(asset_location), fun: Fungible(amount) } => {
+					(asset_location, amount)
+				},
+				_ => return None,
+			};
+			xcm_primitives::reserve_transfer_to_sibling(
+				asset_location,
+				amount,
+				MultiLocation::new(1, X1(Parachain(para_id))),
+				MultiLocation::new(0, X1(AccountId32 { network: NetworkId::Any, id })),
+				T::SelfLocation::get(),
+			)
+		}
+
+		/// The weight of transferring an asset to a player through the longest route, i.e. when
+		/// their parachain is not the reserve of the asset. It only depends on the instructions of
+		/// the message.
+		pub fn max_payout_weight() -> Weight {
+			let location = MultiLocation::new(
+				1,
+				X2(Parachain(u32::MAX), AccountId32 { network: NetworkId::Any, id: [0; 32] }),
+			);
+			let asset_location =
+				MultiLocation::new(1, X3(Parachain(1), PalletInstance(0), GeneralIndex(0)));
+			Self::payout_message(&location, (asset_location, 0u128).into())
+				.and_then(|mut message| T::Weigher::weight(&mut message).ok())
+				.map_or(Weight::zero(), Weight::from_ref_time)
+		}
+	}
+}
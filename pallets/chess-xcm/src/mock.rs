@@ -0,0 +1,268 @@
+use crate as pallet_chess_xcm;
+use frame_support::{
+	assert_ok, parameter_types,
+	traits::{
+		AsEnsureOriginWithArg, ConstU128, ConstU16, ConstU64, ConstU8, Contains, EnsureOrigin,
+	},
+	PalletId,
+};
+use frame_system::{EnsureRoot, EnsureSigned, RawOrigin};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, ConstU32, IdentityLookup},
+};
+use sp_std::borrow::Borrow;
+use xcm::latest::prelude::*;
+use xcm_builder::FixedWeightBounds;
+use xcm_executor::traits::Convert;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+/// A player on `SIBLING`.
+pub const ALICE: u64 = 1;
+/// A player on this chain.
+pub const BOB: u64 = 2;
+pub const UNITS: u128 = 1_000_000_000_000;
+/// The account signing for the relay chain in [`EnsureXcmOrigin`].
+pub const RELAY: u64 = 0;
+/// The reserve parachain of xUSD.
+pub const RESERVE: u32 = 1000;
+pub const SIBLING: u32 = 2000;
+pub const SELF_PARA_ID: u32 = 3000;
+
+pub const XUSD: u32 = 1;
+pub const TOKEN: u32 = 2;
+/// An asset without `MultiLocation`.
+pub const NO_LOCATION: u32 = 3;
+
+parameter_types! {
+	pub SelfLocation: MultiLocation = MultiLocation::new(1, X1(Parachain(SELF_PARA_ID)));
+	pub static ExecutedMessages: Vec<(MultiLocation, Xcm<RuntimeCall>)> = vec![];
+	pub static PayoutOutcome: Option<XcmError> = None;
+}
+
+pub fn xusd_location() -> MultiLocation {
+	MultiLocation::new(1, X3(Parachain(RESERVE), PalletInstance(50), GeneralIndex(XUSD.into())))
+}
+
+pub fn token_location() -> MultiLocation {
+	MultiLocation::new(0, X2(PalletInstance(42), GeneralIndex(TOKEN.into())))
+}
+
+/// The location of the account `who` of `SIBLING`.
+pub fn player(who: u64) -> MultiLocation {
+	MultiLocation::new(
+		1,
+		X2(Parachain(SIBLING), AccountId32 { network: NetworkId::Any, id: [who as u8; 32] }),
+	)
+}
+
+/// Stands in for the XCM origin: accounts sign for the account of `SIBLING` of the same ID, and
+/// `RELAY` for the relay chain.
+pub struct EnsureXcmOrigin;
+impl EnsureOrigin<RuntimeOrigin> for EnsureXcmOrigin {
+	type Success = MultiLocation;
+
+	fn try_origin(o: RuntimeOrigin) -> Result<MultiLocation, RuntimeOrigin> {
+		o.into().and_then(|o| match o {
+			RawOrigin::Signed(RELAY) => Ok(MultiLocation::parent()),
+			RawOrigin::Signed(who) => Ok(player(who)),
+			r => Err(RuntimeOrigin::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+		Ok(RuntimeOrigin::signed(ALICE))
+	}
+}
+
+/// Accounts of siblings have the ID of their first byte on this chain.
+pub struct PlayerAccount;
+impl Convert<MultiLocation, u64> for PlayerAccount {
+	fn convert_ref(location: impl Borrow<MultiLocation>) -> Result<u64, ()> {
+		match location.borrow() {
+			MultiLocation { parents: 1, interior: X2(Parachain(_), AccountId32 { id, .. }) } => {
+				Ok(id[0].into())
+			},
+			_ => Err(()),
+		}
+	}
+}
+
+pub struct AssetLocation;
+impl Convert<MultiLocation, u32> for AssetLocation {
+	fn convert_ref(location: impl Borrow<MultiLocation>) -> Result<u32, ()> {
+		let location = location.borrow();
+		if *location == xusd_location() {
+			Ok(XUSD)
+		} else if *location == token_location() {
+			Ok(TOKEN)
+		} else {
+			Err(())
+		}
+	}
+
+	fn reverse_ref(asset_id: impl Borrow<u32>) -> Result<MultiLocation, ()> {
+		match *asset_id.borrow() {
+			XUSD => Ok(xusd_location()),
+			TOKEN => Ok(token_location()),
+			_ => Err(()),
+		}
+	}
+}
+
+/// Records the executed messages, completing or failing them as set in `PayoutOutcome`.
+pub struct RecordingXcmExecutor;
+impl ExecuteXcm<RuntimeCall> for RecordingXcmExecutor {
+	fn execute_xcm_in_credit(
+		origin: impl Into<MultiLocation>,
+		message: Xcm<RuntimeCall>,
+		weight_limit: u64,
+		_weight_credit: u64,
+	) -> Outcome {
+		ExecutedMessages::mutate(|messages| messages.push((origin.into(), message)));
+		match PayoutOutcome::get() {
+			None => Outcome::Complete(weight_limit),
+			Some(error) => Outcome::Incomplete(weight_limit, error),
+		}
+	}
+}
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		Assets: pallet_assets,
+		Chess: pallet_chess,
+		ChessXcm: pallet_chess_xcm::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u128>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type Balance = u128;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU128<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+}
+
+impl pallet_assets::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = u128;
+	type AssetId = u32;
+	type AssetIdParameter = u32;
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<u64>>;
+	type ForceOrigin = EnsureRoot<u64>;
+	type AssetDeposit = ConstU128<1>;
+	type MetadataDepositBase = ConstU128<1>;
+	type MetadataDepositPerByte = ConstU128<1>;
+	type ApprovalDeposit = ConstU128<1>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+	type AssetAccountDeposit = ConstU128<1>;
+	type RemoveItemsLimit = ConstU32<1000>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+	type CallbackHandle = ();
+}
+
+parameter_types! {
+	pub const ChessPalletId: PalletId = PalletId(*b"subchess");
+	pub static ChessCallsPaused: bool = false;
+}
+
+impl pallet_chess::Config for Test {
+	type PalletId = ChessPalletId;
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_chess::weights::SubstrateWeight<Test>;
+	type Assets = Assets;
+	type AssetBalance = u128;
+	type BulletPeriod = ConstU64<10>;
+	type BlitzPeriod = ConstU64<50>;
+	type RapidPeriod = ConstU64<150>;
+	type DailyPeriod = ConstU64<14_400>;
+	type IncentiveShare = ConstU8<10>;
+}
+
+impl pallet_chess_xcm::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type PlayerOrigin = EnsureXcmOrigin;
+	type LocationToAccountId = PlayerAccount;
+	type AssetLocation = AssetLocation;
+	type SelfLocation = SelfLocation;
+	type XcmExecutor = RecordingXcmExecutor;
+	type Weigher = FixedWeightBounds<ConstU64<10>, RuntimeCall, ConstU32<100>>;
+	type ChessCallFilter = ChessCallFilter;
+	type MaxOngoingMatches = ConstU32<2>;
+	type WeightInfo = pallet_chess_xcm::weights::SubstrateWeight<Test>;
+}
+
+pub struct ChessCallFilter;
+impl Contains<pallet_chess::Call<Test>> for ChessCallFilter {
+	fn contains(_: &pallet_chess::Call<Test>) -> bool {
+		!ChessCallsPaused::get()
+	}
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(ALICE, UNITS), (BOB, UNITS)] }
+		.assimilate_storage(&mut storage)
+		.unwrap();
+
+	let mut ext: sp_io::TestExternalities = storage.into();
+	// events are not deposited on the genesis block
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		for asset_id in [XUSD, TOKEN, NO_LOCATION] {
+			assert_ok!(Assets::force_create(RuntimeOrigin::root(), asset_id, BOB, true, 1));
+			for who in [ALICE, BOB] {
+				assert_ok!(Assets::mint(RuntimeOrigin::signed(BOB), asset_id, who, 1_000 * UNITS));
+			}
+		}
+	});
+	ext
+}
@@ -0,0 +1,342 @@
+use crate::{mock::*, Error, Event};
+use frame_support::{assert_noop, assert_ok, traits::Hooks, weights::Weight};
+use pallet_chess::{MatchState, MatchStyle};
+use sp_core::H256;
+use sp_runtime::{traits::AccountIdConversion, DispatchError};
+use xcm::latest::prelude::*;
+
+const BET: u128 = 10 * UNITS;
+
+fn create_match(who: u64, opponent: u64, asset_id: u32) -> Result<(), DispatchError> {
+	ChessXcm::create_match(RuntimeOrigin::signed(who), opponent, MatchStyle::Bullet, asset_id, BET)
+}
+
+fn match_id() -> H256 {
+	pallet_chess::Matches::<Test>::iter_keys().next().unwrap()
+}
+
+/// The account of `pallet_chess`, which holds the stakes.
+fn chess_account() -> u64 {
+	ChessPalletId::get().into_account_truncating()
+}
+
+/// Play the fool's mate in the match `match_id`, which black wins.
+fn play_fools_mate(match_id: H256) {
+	let chess_match = pallet_chess::Matches::<Test>::get(match_id).unwrap();
+	let (white, black) = (chess_match.challenger, chess_match.opponent);
+	for (who, mv) in [(white, "f2f3"), (black, "e7e5"), (white, "g2g4"), (black, "d8h4")] {
+		assert_ok!(Chess::make_move(RuntimeOrigin::signed(who), match_id, mv.into()));
+	}
+}
+
+/// BOB creates a match against ALICE, who joins it through the pallet.
+fn start_match() -> H256 {
+	assert_ok!(Chess::create_match(
+		RuntimeOrigin::signed(BOB),
+		ALICE,
+		MatchStyle::Bullet,
+		XUSD,
+		BET
+	));
+	let match_id = match_id();
+	assert_ok!(ChessXcm::join_match(RuntimeOrigin::signed(ALICE), match_id));
+	match_id
+}
+
+/// The message transferring `amount` of xUSD back to `ALICE` on `SIBLING`.
+fn payout(amount: u128) -> Xcm<RuntimeCall> {
+	xcm_primitives::reserve_transfer_to_sibling(
+		xusd_location(),
+		amount,
+		MultiLocation::new(1, X1(Parachain(SIBLING))),
+		MultiLocation::new(0, X1(AccountId32 { network: NetworkId::Any, id: [ALICE as u8; 32] })),
+		SelfLocation::get(),
+	)
+	.unwrap()
+}
+
+#[test]
+fn create_match_stakes_from_player_account() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_match(ALICE, BOB, XUSD));
+
+		assert_eq!(Assets::balance(XUSD, ALICE), 1_000 * UNITS - BET);
+		assert_eq!(Assets::balance(XUSD, chess_account()), BET);
+		assert!(ExecutedMessages::get().is_empty());
+	});
+}
+
+#[test]
+fn join_match_stakes_from_player_account() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Chess::create_match(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			MatchStyle::Bullet,
+			XUSD,
+			BET
+		));
+
+		assert_ok!(ChessXcm::join_match(RuntimeOrigin::signed(ALICE), match_id()));
+
+		assert_eq!(Assets::balance(XUSD, ALICE), 1_000 * UNITS - BET);
+		assert_eq!(Assets::balance(XUSD, chess_account()), 2 * BET);
+
+		assert_noop!(
+			ChessXcm::join_match(RuntimeOrigin::signed(ALICE), H256::zero()),
+			Error::<Test>::UnknownMatch
+		);
+	});
+}
+
+#[test]
+fn stakes_must_be_transferable_back() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(create_match(ALICE, BOB, NO_LOCATION), Error::<Test>::UnsupportedAsset);
+
+		assert_ok!(Chess::create_match(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			MatchStyle::Bullet,
+			NO_LOCATION,
+			BET
+		));
+		assert_noop!(
+			ChessXcm::join_match(RuntimeOrigin::signed(ALICE), match_id()),
+			Error::<Test>::UnsupportedAsset
+		);
+	});
+}
+
+#[test]
+fn refunds_are_claimed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_match(ALICE, BOB, XUSD));
+
+		// the refund is left in the account of the player until they claim it
+		assert_ok!(Chess::abort_match(RuntimeOrigin::signed(ALICE), match_id()));
+		assert_eq!(Assets::balance(XUSD, ALICE), 1_000 * UNITS);
+		assert!(ExecutedMessages::get().is_empty());
+
+		assert_ok!(ChessXcm::claim(RuntimeOrigin::signed(ALICE), XUSD));
+
+		assert_eq!(ExecutedMessages::get(), vec![(player(ALICE), payout(1_000 * UNITS))]);
+		System::assert_last_event(
+			Event::PaidOut {
+				who: ALICE,
+				location: player(ALICE),
+				asset: (xusd_location(), 1_000 * UNITS).into(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn withdraw_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ChessXcm::withdraw(RuntimeOrigin::signed(ALICE), XUSD, BET));
+
+		assert_eq!(ExecutedMessages::get(), vec![(player(ALICE), payout(BET))]);
+		System::assert_last_event(
+			Event::PaidOut {
+				who: ALICE,
+				location: player(ALICE),
+				asset: (xusd_location(), BET).into(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn payouts_fail() {
+	new_test_ext().execute_with(|| {
+		// an account of `SIBLING` without xUSD
+		assert_noop!(
+			ChessXcm::claim(RuntimeOrigin::signed(3), XUSD),
+			Error::<Test>::NothingToClaim
+		);
+		assert_noop!(
+			ChessXcm::withdraw(RuntimeOrigin::signed(ALICE), NO_LOCATION, BET),
+			Error::<Test>::UnsupportedAsset
+		);
+
+		PayoutOutcome::set(Some(XcmError::FailedToTransactAsset("")));
+		assert_noop!(
+			ChessXcm::claim(RuntimeOrigin::signed(ALICE), XUSD),
+			Error::<Test>::PayoutFailed
+		);
+	});
+}
+
+#[test]
+fn payout_weight_is_bounded_by_the_longest_route() {
+	use crate::{Config, WeightInfo};
+	use frame_support::{dispatch::GetDispatchInfo, weights::Weight};
+
+	new_test_ext().execute_with(|| {
+		// six instructions to a sibling which is not the reserve of the asset
+		let payout_weight = Weight::from_ref_time(60);
+		assert_eq!(ChessXcm::max_payout_weight(), payout_weight);
+
+		for asset_id in [XUSD, TOKEN, NO_LOCATION] {
+			assert_eq!(
+				crate::Call::<Test>::claim { asset_id }.get_dispatch_info().weight,
+				<Test as Config>::WeightInfo::claim().saturating_add(payout_weight)
+			);
+		}
+	});
+}
+
+#[test]
+fn calls_fail_for_non_sibling_accounts() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(create_match(RELAY, BOB, XUSD), Error::<Test>::NotSiblingAccount);
+		assert_noop!(
+			ChessXcm::withdraw(RuntimeOrigin::signed(RELAY), XUSD, BET),
+			Error::<Test>::NotSiblingAccount
+		);
+		assert_noop!(
+			ChessXcm::claim(RuntimeOrigin::signed(RELAY), XUSD),
+			Error::<Test>::NotSiblingAccount
+		);
+		assert_noop!(
+			ChessXcm::create_match(RuntimeOrigin::root(), BOB, MatchStyle::Bullet, XUSD, BET),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn chess_calls_are_filtered() {
+	new_test_ext().execute_with(|| {
+		ChessCallsPaused::set(true);
+		assert_noop!(create_match(ALICE, BOB, XUSD), frame_system::Error::<Test>::CallFiltered);
+
+		assert_ok!(Chess::create_match(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			MatchStyle::Bullet,
+			XUSD,
+			BET
+		));
+		assert_noop!(
+			ChessXcm::join_match(RuntimeOrigin::signed(ALICE), match_id()),
+			frame_system::Error::<Test>::CallFiltered
+		);
+	});
+}
+
+#[test]
+fn players_are_paid_out_when_matches_end() {
+	new_test_ext().execute_with(|| {
+		let match_id = start_match();
+		assert_eq!(ChessXcm::ongoing_matches()[0].match_id, match_id);
+
+		// not ended
+		ChessXcm::on_idle(1, Weight::MAX);
+		assert!(ExecutedMessages::get().is_empty());
+		assert_eq!(ChessXcm::ongoing_matches().len(), 1);
+
+		play_fools_mate(match_id);
+		assert_eq!(
+			pallet_chess::Matches::<Test>::get(match_id).map(|m| m.state),
+			Some(MatchState::Won(ALICE))
+		);
+		let winnings = Assets::balance(XUSD, ALICE);
+		assert!(winnings > 1_000 * UNITS);
+
+		// no weight left
+		assert_eq!(ChessXcm::on_idle(1, Weight::zero()), Weight::zero());
+		assert_eq!(ChessXcm::ongoing_matches().len(), 1);
+
+		ChessXcm::on_idle(1, Weight::MAX);
+		assert_eq!(ExecutedMessages::get(), vec![(player(ALICE), payout(winnings))]);
+		assert!(ChessXcm::ongoing_matches().is_empty());
+		System::assert_last_event(
+			Event::PaidOut {
+				who: ALICE,
+				location: player(ALICE),
+				asset: (xusd_location(), winnings).into(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn failed_payouts_are_left_to_claim() {
+	new_test_ext().execute_with(|| {
+		let match_id = start_match();
+		play_fools_mate(match_id);
+		let winnings = Assets::balance(XUSD, ALICE);
+
+		PayoutOutcome::set(Some(XcmError::FailedToTransactAsset("")));
+		ChessXcm::on_idle(1, Weight::MAX);
+
+		assert!(ChessXcm::ongoing_matches().is_empty());
+		assert_eq!(Assets::balance(XUSD, ALICE), winnings);
+		System::assert_last_event(
+			Event::PayoutFailed { who: ALICE, match_id, error: Error::<Test>::PayoutFailed.into() }
+				.into(),
+		);
+
+		PayoutOutcome::set(None);
+		assert_ok!(ChessXcm::claim(RuntimeOrigin::signed(ALICE), XUSD));
+		assert_eq!(ExecutedMessages::get().last(), Some(&(player(ALICE), payout(winnings))));
+	});
+}
+
+#[test]
+fn matches_joined_with_pallet_chess_are_watched() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_match(ALICE, BOB, XUSD));
+		let match_id = match_id();
+		assert_noop!(
+			ChessXcm::watch_match(RuntimeOrigin::signed(BOB), match_id),
+			Error::<Test>::MatchNotOngoing
+		);
+		assert_noop!(
+			ChessXcm::watch_match(RuntimeOrigin::signed(BOB), H256::zero()),
+			Error::<Test>::UnknownMatch
+		);
+
+		assert_ok!(Chess::join_match(RuntimeOrigin::signed(BOB), match_id));
+		assert_ok!(ChessXcm::watch_match(RuntimeOrigin::signed(BOB), match_id));
+		System::assert_last_event(Event::MatchWatched { match_id }.into());
+		assert_noop!(
+			ChessXcm::watch_match(RuntimeOrigin::signed(BOB), match_id),
+			Error::<Test>::AlreadyWatched
+		);
+
+		// ALICE, playing white, is mated
+		play_fools_mate(match_id);
+		ChessXcm::on_idle(1, Weight::MAX);
+		assert_eq!(
+			ExecutedMessages::get(),
+			vec![(player(ALICE), payout(Assets::balance(XUSD, ALICE)))]
+		);
+	});
+}
+
+#[test]
+fn only_matches_of_siblings_are_watched() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(BOB), XUSD, 4, 1_000 * UNITS));
+		assert_ok!(Chess::create_match(
+			RuntimeOrigin::signed(BOB),
+			4,
+			MatchStyle::Bullet,
+			XUSD,
+			BET
+		));
+		let match_id = match_id();
+		assert_ok!(Chess::join_match(RuntimeOrigin::signed(4), match_id));
+
+		assert_noop!(
+			ChessXcm::watch_match(RuntimeOrigin::signed(BOB), match_id),
+			Error::<Test>::NoSiblingPlayer
+		);
+	});
+}
@@ -0,0 +1,124 @@
+//! Weights for `pallet_chess_xcm`
+//!
+//! Estimated from the storage accesses of each call and of the matches checked in `on_idle`. The
+//! weights of `pallet_chess` calls and of the executed messages are added by the pallet.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+pub trait WeightInfo {
+	fn create_match() -> Weight;
+	fn join_match() -> Weight;
+	fn withdraw() -> Weight;
+	fn claim() -> Weight;
+	fn watch_match() -> Weight;
+	fn on_idle(n: u32, ) -> Weight;
+}
+
+/// Weight functions for `pallet_chess_xcm`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: crate::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: AssetRegistry AssetIdMultiLocation (r:1 w:0)
+	// Storage: ChessXcm Players (r:0 w:1)
+	fn create_match() -> Weight {
+		Weight::from_ref_time(15_000_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: Chess Matches (r:1 w:0)
+	// Storage: AssetRegistry AssetIdMultiLocation (r:1 w:0)
+	// Storage: ChessXcm Players (r:0 w:1)
+	// Storage: ChessXcm OngoingMatches (r:1 w:1)
+	fn join_match() -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: AssetRegistry AssetIdMultiLocation (r:1 w:0)
+	fn withdraw() -> Weight {
+		Weight::from_ref_time(15_000_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+	}
+	// Storage: Assets Account (r:1 w:0)
+	// Storage: AssetRegistry AssetIdMultiLocation (r:1 w:0)
+	fn claim() -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(T::DbWeight::get().reads(2))
+	}
+	// Storage: Chess Matches (r:1 w:0)
+	// Storage: ChessXcm Players (r:2 w:0)
+	// Storage: ChessXcm OngoingMatches (r:1 w:1)
+	fn watch_match() -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: ChessXcm OngoingMatches (r:1 w:1)
+	// Storage: Chess Matches (r:n w:0)
+	// Storage: ChessXcm Players (r:2n w:0)
+	// Storage: Assets Account (r:2n w:0)
+	// Storage: AssetRegistry AssetIdMultiLocation (r:2n w:0)
+	/// The range of component `n` is `[0, MaxOngoingMatches]`.
+	fn on_idle(n: u32, ) -> Weight {
+		Weight::from_ref_time(5_000_000)
+			.saturating_add(Weight::from_ref_time(30_000_000).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().reads((7_u64).saturating_mul(n.into())))
+	}
+}
+
+impl WeightInfo for () {
+	// Storage: AssetRegistry AssetIdMultiLocation (r:1 w:0)
+	// Storage: ChessXcm Players (r:0 w:1)
+	fn create_match() -> Weight {
+		Weight::from_ref_time(15_000_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	// Storage: Chess Matches (r:1 w:0)
+	// Storage: AssetRegistry AssetIdMultiLocation (r:1 w:0)
+	// Storage: ChessXcm Players (r:0 w:1)
+	// Storage: ChessXcm OngoingMatches (r:1 w:1)
+	fn join_match() -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	// Storage: AssetRegistry AssetIdMultiLocation (r:1 w:0)
+	fn withdraw() -> Weight {
+		Weight::from_ref_time(15_000_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+	}
+	// Storage: Assets Account (r:1 w:0)
+	// Storage: AssetRegistry AssetIdMultiLocation (r:1 w:0)
+	fn claim() -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(RocksDbWeight::get().reads(2))
+	}
+	// Storage: Chess Matches (r:1 w:0)
+	// Storage: ChessXcm Players (r:2 w:0)
+	// Storage: ChessXcm OngoingMatches (r:1 w:1)
+	fn watch_match() -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(RocksDbWeight::get().reads(4))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	// Storage: ChessXcm OngoingMatches (r:1 w:1)
+	// Storage: Chess Matches (r:n w:0)
+	// Storage: ChessXcm Players (r:2n w:0)
+	// Storage: Assets Account (r:2n w:0)
+	// Storage: AssetRegistry AssetIdMultiLocation (r:2n w:0)
+	/// The range of component `n` is `[0, MaxOngoingMatches]`.
+	fn on_idle(n: u32, ) -> Weight {
+		Weight::from_ref_time(5_000_000)
+			.saturating_add(Weight::from_ref_time(30_000_000).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+			.saturating_add(RocksDbWeight::get().reads((7_u64).saturating_mul(n.into())))
+	}
+}
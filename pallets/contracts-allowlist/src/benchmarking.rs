@@ -0,0 +1,41 @@
+//! Benchmarking setup for pallet-contracts-allowlist
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as ContractsAllowlist;
+use frame_benchmarking::benchmarks;
+use frame_support::assert_ok;
+use frame_system::RawOrigin;
+use sp_runtime::traits::Hash;
+
+benchmarks! {
+	approve_code_hash {
+		let code_hash = T::Hashing::hash(b"code");
+	}: _(RawOrigin::Root, code_hash)
+	verify {
+		assert!(ApprovedCodeHashes::<T>::contains_key(code_hash));
+	}
+
+	revoke_code_hash {
+		let code_hash = T::Hashing::hash(b"code");
+		assert_ok!(ContractsAllowlist::<T>::approve_code_hash(RawOrigin::Root.into(), code_hash));
+	}: _(RawOrigin::Root, code_hash)
+	verify {
+		assert!(!ApprovedCodeHashes::<T>::contains_key(code_hash));
+	}
+
+	set_enforced {
+		let c in 0 .. 1_000;
+		for i in 0..c {
+			let code_hash = T::Hashing::hash_of(&i);
+			T::StoredCode::insert(code_hash);
+			assert_ok!(ContractsAllowlist::<T>::approve_code_hash(RawOrigin::Root.into(), code_hash));
+		}
+	}: _(RawOrigin::Root, true, c)
+	verify {
+		assert!(ContractsAllowlist::<T>::enforced());
+	}
+
+	impl_benchmark_test_suite!(ContractsAllowlist, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,195 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Keeps a governance managed list of contract code hashes.
+//!
+//! While enforcement is enabled, only approved code hashes should be uploaded or instantiated.
+//! The pallet itself doesn't hook into `pallet_contracts`: runtimes check [`Pallet::is_allowed`]
+//! (or [`Pallet::is_code_allowed`]) from their `BaseCallFilter` when contracts are uploaded or
+//! instantiated. Dev chains can disable enforcement in genesis, which lets any code through.
+//!
+//! Contracts instantiate other contracts from stored code without going through the
+//! `BaseCallFilter`, so the pallet also keeps all the code stored by `pallet_contracts` approved
+//! while enforcement is enabled: code hashes can't be revoked while their code is stored, and
+//! enforcement can't be enabled while unapproved code is stored.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::Hash;
+	use sp_std::prelude::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// The contract code stored by `pallet_contracts`.
+	pub trait StoredCode<Hash> {
+		type CodeHashes: Iterator<Item = Hash>;
+
+		/// Whether code with the hash `code_hash` is stored.
+		fn contains(code_hash: &Hash) -> bool;
+
+		/// The hashes of the stored code.
+		fn code_hashes() -> Self::CodeHashes;
+
+		/// Stores code with the hash `code_hash`.
+		#[cfg(feature = "runtime-benchmarks")]
+		fn insert(code_hash: Hash);
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The origin that's allowed to approve and revoke code hashes, and to toggle enforcement.
+		type ApproveOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// The contract code stored by `pallet_contracts`, which contracts can instantiate.
+		type StoredCode: StoredCode<Self::Hash>;
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Code hashes which may be uploaded and instantiated while enforcement is enabled.
+	#[pallet::storage]
+	pub type ApprovedCodeHashes<T: Config> = StorageMap<_, Identity, T::Hash, ()>;
+
+	/// Whether only approved code hashes are allowed.
+	#[pallet::storage]
+	#[pallet::getter(fn enforced)]
+	pub type Enforced<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Whether to enforce the allowlist from genesis. Dev chains usually leave it disabled.
+		pub enforced: bool,
+		pub approved_code_hashes: Vec<T::Hash>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { enforced: false, approved_code_hashes: vec![] }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			Enforced::<T>::put(self.enforced);
+			for code_hash in &self.approved_code_hashes {
+				ApprovedCodeHashes::<T>::insert(code_hash, ());
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		CodeHashApproved { code_hash: T::Hash },
+		CodeHashRevoked { code_hash: T::Hash },
+		EnforcementSet { enforced: bool },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The code hash is already approved
+		CodeHashAlreadyApproved,
+		/// The code hash is not approved
+		CodeHashNotApproved,
+		/// The code of the code hash is stored, so contracts can still instantiate it
+		CodeIsStored,
+		/// Code which is not approved is stored
+		UnapprovedCodeStored,
+		/// More code is stored than the given witness
+		StoredCodeWitnessTooLow,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::approve_code_hash())]
+		pub fn approve_code_hash(origin: OriginFor<T>, code_hash: T::Hash) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+
+			// verify code hash is not yet approved
+			ensure!(
+				!ApprovedCodeHashes::<T>::contains_key(code_hash),
+				Error::<T>::CodeHashAlreadyApproved
+			);
+
+			ApprovedCodeHashes::<T>::insert(code_hash, ());
+
+			Self::deposit_event(Event::CodeHashApproved { code_hash });
+			Ok(())
+		}
+
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::revoke_code_hash())]
+		pub fn revoke_code_hash(origin: OriginFor<T>, code_hash: T::Hash) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+
+			// verify code hash is approved
+			ensure!(
+				ApprovedCodeHashes::<T>::contains_key(code_hash),
+				Error::<T>::CodeHashNotApproved
+			);
+
+			// contracts could still instantiate the code
+			ensure!(!T::StoredCode::contains(&code_hash), Error::<T>::CodeIsStored);
+
+			ApprovedCodeHashes::<T>::remove(code_hash);
+
+			Self::deposit_event(Event::CodeHashRevoked { code_hash });
+			Ok(())
+		}
+
+		#[pallet::call_index(2)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_enforced(*stored_codes))]
+		pub fn set_enforced(
+			origin: OriginFor<T>,
+			enforced: bool,
+			stored_codes: u32,
+		) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+
+			// verify contracts can only instantiate approved code
+			if enforced {
+				for (index, code_hash) in T::StoredCode::code_hashes().enumerate() {
+					ensure!(index < stored_codes as usize, Error::<T>::StoredCodeWitnessTooLow);
+					ensure!(
+						ApprovedCodeHashes::<T>::contains_key(code_hash),
+						Error::<T>::UnapprovedCodeStored
+					);
+				}
+			}
+
+			Enforced::<T>::put(enforced);
+
+			Self::deposit_event(Event::EnforcementSet { enforced });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Whether contracts with the given code hash may be uploaded or instantiated.
+		pub fn is_allowed(code_hash: &T::Hash) -> bool {
+			!Enforced::<T>::get() || ApprovedCodeHashes::<T>::contains_key(code_hash)
+		}
+
+		/// Whether the given Wasm code may be uploaded, hashing it the way `pallet_contracts` does.
+		pub fn is_code_allowed(code: &[u8]) -> bool {
+			!Enforced::<T>::get() || Self::is_allowed(&T::Hashing::hash(code))
+		}
+	}
+}
@@ -0,0 +1,119 @@
+use crate as pallet_contracts_allowlist;
+use frame_support::{
+	parameter_types,
+	traits::{ConstU16, ConstU64, GenesisBuild},
+};
+use frame_system as system;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, ConstU32, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system,
+		ContractsAllowlist: pallet_contracts_allowlist::{Pallet, Call, Storage, Event<T>, Config<T>},
+	}
+);
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+parameter_types! {
+	pub static StoredCodeHashes: Vec<H256> = vec![];
+}
+
+/// Stands in for the code stored by `pallet_contracts`.
+pub struct MockStoredCode;
+impl pallet_contracts_allowlist::StoredCode<H256> for MockStoredCode {
+	type CodeHashes = std::vec::IntoIter<H256>;
+
+	fn contains(code_hash: &H256) -> bool {
+		StoredCodeHashes::get().contains(code_hash)
+	}
+
+	fn code_hashes() -> Self::CodeHashes {
+		StoredCodeHashes::get().into_iter()
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn insert(code_hash: H256) {
+		let mut code_hashes = StoredCodeHashes::get();
+		code_hashes.push(code_hash);
+		StoredCodeHashes::set(code_hashes);
+	}
+}
+
+/// Stores code with the hash of `code`, as if it was uploaded to `pallet_contracts`.
+pub fn store_code(code: &[u8]) {
+	use sp_runtime::traits::Hash;
+
+	let mut code_hashes = StoredCodeHashes::get();
+	code_hashes.push(BlakeTwo256::hash(code));
+	StoredCodeHashes::set(code_hashes);
+}
+
+impl pallet_contracts_allowlist::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type ApproveOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type StoredCode = MockStoredCode;
+	type WeightInfo = pallet_contracts_allowlist::weights::SubstrateWeight<Test>;
+}
+
+pub const GENESIS_CODE: &[u8] = b"genesis code";
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	new_test_ext_with(true)
+}
+
+pub fn new_test_ext_with(enforced: bool) -> sp_io::TestExternalities {
+	use sp_runtime::traits::Hash;
+
+	let mut storage = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+	let config: pallet_contracts_allowlist::GenesisConfig<Test> =
+		pallet_contracts_allowlist::GenesisConfig {
+			enforced,
+			approved_code_hashes: vec![BlakeTwo256::hash(GENESIS_CODE)],
+		};
+	config.assimilate_storage(&mut storage).unwrap();
+
+	let mut ext: sp_io::TestExternalities = storage.into();
+	// events are not deposited on the genesis block
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,127 @@
+use crate::{mock::*, ApprovedCodeHashes, Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash},
+	DispatchError,
+};
+
+#[test]
+fn genesis_approves_code_hashes() {
+	new_test_ext().execute_with(|| {
+		assert!(ContractsAllowlist::enforced());
+		assert!(ContractsAllowlist::is_code_allowed(GENESIS_CODE));
+		assert!(ContractsAllowlist::is_allowed(&BlakeTwo256::hash(GENESIS_CODE)));
+		assert!(!ContractsAllowlist::is_code_allowed(b"other code"));
+	});
+}
+
+#[test]
+fn approve_code_hash_works() {
+	new_test_ext().execute_with(|| {
+		let code_hash = BlakeTwo256::hash(b"code");
+		assert!(!ContractsAllowlist::is_allowed(&code_hash));
+
+		assert_ok!(ContractsAllowlist::approve_code_hash(RuntimeOrigin::root(), code_hash));
+		assert!(ApprovedCodeHashes::<Test>::contains_key(code_hash));
+		assert!(ContractsAllowlist::is_code_allowed(b"code"));
+		System::assert_last_event(Event::CodeHashApproved { code_hash }.into());
+
+		assert_noop!(
+			ContractsAllowlist::approve_code_hash(RuntimeOrigin::root(), code_hash),
+			Error::<Test>::CodeHashAlreadyApproved
+		);
+	});
+}
+
+#[test]
+fn revoke_code_hash_works() {
+	new_test_ext().execute_with(|| {
+		let code_hash = BlakeTwo256::hash(GENESIS_CODE);
+
+		assert_ok!(ContractsAllowlist::revoke_code_hash(RuntimeOrigin::root(), code_hash));
+		assert!(!ContractsAllowlist::is_allowed(&code_hash));
+		System::assert_last_event(Event::CodeHashRevoked { code_hash }.into());
+
+		assert_noop!(
+			ContractsAllowlist::revoke_code_hash(RuntimeOrigin::root(), code_hash),
+			Error::<Test>::CodeHashNotApproved
+		);
+	});
+}
+
+#[test]
+fn revoke_code_hash_of_stored_code_fails() {
+	new_test_ext().execute_with(|| {
+		store_code(GENESIS_CODE);
+
+		assert_noop!(
+			ContractsAllowlist::revoke_code_hash(
+				RuntimeOrigin::root(),
+				BlakeTwo256::hash(GENESIS_CODE)
+			),
+			Error::<Test>::CodeIsStored
+		);
+	});
+}
+
+#[test]
+fn set_enforced_works() {
+	new_test_ext_with(false).execute_with(|| {
+		assert!(!ContractsAllowlist::enforced());
+		assert!(ContractsAllowlist::is_code_allowed(b"any code"));
+
+		assert_ok!(ContractsAllowlist::set_enforced(RuntimeOrigin::root(), true, 0));
+		assert!(!ContractsAllowlist::is_code_allowed(b"any code"));
+		assert!(ContractsAllowlist::is_code_allowed(GENESIS_CODE));
+		System::assert_last_event(Event::EnforcementSet { enforced: true }.into());
+	});
+}
+
+#[test]
+fn calls_require_approve_origin() {
+	new_test_ext().execute_with(|| {
+		let code_hash = BlakeTwo256::hash(b"code");
+
+		assert_noop!(
+			ContractsAllowlist::approve_code_hash(RuntimeOrigin::signed(1), code_hash),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			ContractsAllowlist::revoke_code_hash(
+				RuntimeOrigin::signed(1),
+				BlakeTwo256::hash(GENESIS_CODE)
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			ContractsAllowlist::set_enforced(RuntimeOrigin::signed(1), false, 0),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn set_enforced_requires_stored_code_to_be_approved() {
+	new_test_ext_with(false).execute_with(|| {
+		store_code(GENESIS_CODE);
+		store_code(b"code");
+
+		assert_noop!(
+			ContractsAllowlist::set_enforced(RuntimeOrigin::root(), true, 2),
+			Error::<Test>::UnapprovedCodeStored
+		);
+
+		assert_ok!(ContractsAllowlist::approve_code_hash(
+			RuntimeOrigin::root(),
+			BlakeTwo256::hash(b"code")
+		));
+		assert_noop!(
+			ContractsAllowlist::set_enforced(RuntimeOrigin::root(), true, 1),
+			Error::<Test>::StoredCodeWitnessTooLow
+		);
+		assert_ok!(ContractsAllowlist::set_enforced(RuntimeOrigin::root(), true, 2));
+
+		// disabling enforcement doesn't check the stored code
+		assert_ok!(ContractsAllowlist::set_enforced(RuntimeOrigin::root(), false, 0));
+	});
+}
@@ -0,0 +1,89 @@
+
+//! Weights for `pallet_contracts_allowlist`
+//!
+//! Estimated from the storage accesses of each extrinsic, to be replaced by the output of:
+
+// ./target/release/trappist-collator
+// benchmark
+// pallet
+// --chain
+// dev
+// --pallet
+// pallet_contracts_allowlist
+// --extrinsic
+// *
+// --steps
+// 20
+// --repeat
+// 10
+// --output
+// pallets/contracts-allowlist/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+pub trait WeightInfo {
+	fn approve_code_hash() -> Weight;
+	fn revoke_code_hash() -> Weight;
+	fn set_enforced(c: u32, ) -> Weight;
+}
+
+/// Weight functions for `pallet_contracts_allowlist`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: ContractsAllowlist ApprovedCodeHashes (r:1 w:1)
+	fn approve_code_hash() -> Weight {
+		Weight::from_ref_time(15_000_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: ContractsAllowlist ApprovedCodeHashes (r:1 w:1)
+	// Storage: Contracts PristineCode (r:1 w:0)
+	fn revoke_code_hash() -> Weight {
+		Weight::from_ref_time(15_000_000)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: Contracts PristineCode (r:1 w:0)
+	// Storage: ContractsAllowlist ApprovedCodeHashes (r:1 w:0)
+	// Storage: ContractsAllowlist Enforced (r:0 w:1)
+	/// The range of component `c` is `[0, 1000]`.
+	fn set_enforced(c: u32, ) -> Weight {
+		Weight::from_ref_time(10_000_000)
+			// Standard Error: 2_000
+			.saturating_add(Weight::from_ref_time(5_000_000).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(c.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+}
+
+impl WeightInfo for () {
+	// Storage: ContractsAllowlist ApprovedCodeHashes (r:1 w:1)
+	fn approve_code_hash() -> Weight {
+		Weight::from_ref_time(15_000_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	// Storage: ContractsAllowlist ApprovedCodeHashes (r:1 w:1)
+	// Storage: Contracts PristineCode (r:1 w:0)
+	fn revoke_code_hash() -> Weight {
+		Weight::from_ref_time(15_000_000)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	// Storage: Contracts PristineCode (r:1 w:0)
+	// Storage: ContractsAllowlist ApprovedCodeHashes (r:1 w:0)
+	// Storage: ContractsAllowlist Enforced (r:0 w:1)
+	/// The range of component `c` is `[0, 1000]`.
+	fn set_enforced(c: u32, ) -> Weight {
+		Weight::from_ref_time(10_000_000)
+			// Standard Error: 2_000
+			.saturating_add(Weight::from_ref_time(5_000_000).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(c.into())))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+}
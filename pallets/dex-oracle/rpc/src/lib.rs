@@ -0,0 +1,104 @@
+//! RPC querying the prices of `pallet_dex_oracle`.
+//!
+//! Prices are returned as the inner value of a `FixedU128`, i.e. scaled by 10^18, as JSON numbers
+//! can't hold them losslessly.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{traits::Block as BlockT, FixedU128};
+
+pub use pallet_dex_oracle_runtime_api::DexOracleApi as DexOracleRuntimeApi;
+
+#[rpc(client, server)]
+pub trait DexOracleApi<BlockHash, AssetId, BlockNumber> {
+	/// The average price of `asset_id` over the last `window` blocks.
+	#[method(name = "dexOracle_twap")]
+	fn twap(
+		&self,
+		asset_id: AssetId,
+		window: BlockNumber,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<NumberOrHex>>;
+
+	/// The current price of `asset_id` in its exchange.
+	#[method(name = "dexOracle_spotPrice")]
+	fn spot_price(
+		&self,
+		asset_id: AssetId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<NumberOrHex>>;
+}
+
+/// Provides the prices of `pallet_dex_oracle`.
+pub struct DexOracle<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> DexOracle<C, Block> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// The runtime API call failed.
+const RUNTIME_ERROR: i32 = 1;
+
+fn runtime_error(error: impl ToString) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Unable to query the price.",
+		Some(error.to_string()),
+	))
+	.into()
+}
+
+fn to_number(price: Option<FixedU128>) -> Option<NumberOrHex> {
+	price.map(|price| price.into_inner().into())
+}
+
+impl<C, Block, AssetId, BlockNumber>
+	DexOracleApiServer<<Block as BlockT>::Hash, AssetId, BlockNumber> for DexOracle<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: DexOracleRuntimeApi<Block, AssetId, BlockNumber>,
+	AssetId: Codec + Send + Sync + 'static,
+	BlockNumber: Codec + Send + Sync + 'static,
+{
+	fn twap(
+		&self,
+		asset_id: AssetId,
+		window: BlockNumber,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<NumberOrHex>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.twap(at, asset_id, window)
+			.map(to_number)
+			.map_err(runtime_error)
+	}
+
+	fn spot_price(
+		&self,
+		asset_id: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<NumberOrHex>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.spot_price(at, asset_id)
+			.map(to_number)
+			.map_err(runtime_error)
+	}
+}
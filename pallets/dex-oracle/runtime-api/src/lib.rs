@@ -0,0 +1,19 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_runtime::FixedU128;
+
+sp_api::decl_runtime_apis! {
+	/// Queries the prices of `pallet_dex_oracle`, in native currency per unit of the asset.
+	pub trait DexOracleApi<AssetId, BlockNumber> where
+		AssetId: Codec,
+		BlockNumber: Codec,
+	{
+		/// The average price of `asset_id` over the last `window` blocks, if it has been tracked
+		/// for the whole window.
+		fn twap(asset_id: AssetId, window: BlockNumber) -> Option<FixedU128>;
+
+		/// The current price of `asset_id` in its exchange, which can be manipulated within a block.
+		fn spot_price(asset_id: AssetId) -> Option<FixedU128>;
+	}
+}
@@ -0,0 +1,247 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Time-weighted average prices (TWAP) of `pallet_dex` exchanges.
+//!
+//! The spot price of an exchange can be moved at will within a block, e.g. by a swap followed by
+//! the call reading the price and a swap back. At the end of each block, the pallet adds the price
+//! the tracked exchanges held since the previous block to a cumulative price, so that averages over
+//! a window can only be moved by holding a manipulated price across the blocks of the window.
+//!
+//! Prices are in native currency per unit of the asset, as held by the exchange reserves, and time
+//! is measured in blocks.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::{
+		traits::{CheckedSub, Saturating, UniqueSaturatedInto, Zero},
+		FixedPointNumber, FixedU128,
+	};
+	use sp_std::marker::PhantomData;
+
+	pub type AssetIdOf<T> = <T as pallet_dex::Config>::AssetId;
+
+	/// The cumulative price of an asset at the end of a block.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Observation<BlockNumber> {
+		pub block_number: BlockNumber,
+		/// The sum of the prices of every block since the asset is tracked, as the inner value of
+		/// a `FixedU128`. It wraps around on overflow, which differences over windows are immune to.
+		pub price_cumulative: u128,
+		/// The spot price at the end of the block, held until the next observation.
+		pub price: FixedU128,
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_dex::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The origin that's allowed to track and untrack the prices of assets.
+		type TrackOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// The maximum number of assets whose prices are tracked.
+		#[pallet::constant]
+		type MaxTrackedAssets: Get<u32>;
+		/// The number of blocks whose observations are kept, which bounds the TWAP window.
+		#[pallet::constant]
+		type HistoryLength: Get<u32>;
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The assets whose prices are tracked.
+	#[pallet::storage]
+	#[pallet::getter(fn tracked_assets)]
+	pub type TrackedAssets<T: Config> =
+		StorageValue<_, BoundedVec<AssetIdOf<T>, T::MaxTrackedAssets>, ValueQuery>;
+
+	/// The block of the first observation of tracked assets.
+	#[pallet::storage]
+	pub type TrackedSince<T: Config> = StorageMap<_, Twox64Concat, AssetIdOf<T>, T::BlockNumber>;
+
+	/// The latest observation of tracked assets.
+	#[pallet::storage]
+	pub type LastObservation<T: Config> =
+		StorageMap<_, Twox64Concat, AssetIdOf<T>, Observation<T::BlockNumber>>;
+
+	/// The observations of the last `HistoryLength` blocks, by block number modulo `HistoryLength`.
+	#[pallet::storage]
+	pub type Observations<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		AssetIdOf<T>,
+		Twox64Concat,
+		u32,
+		Observation<T::BlockNumber>,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		AssetTracked { asset_id: AssetIdOf<T> },
+		AssetUntracked { asset_id: AssetIdOf<T> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The price of the asset is already tracked
+		AlreadyTracked,
+		/// The price of the asset is not tracked
+		NotTracked,
+		/// `MaxTrackedAssets` are already tracked
+		TooManyTrackedAssets,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: T::BlockNumber) -> Weight {
+			<T as pallet::Config>::WeightInfo::on_finalize(
+				TrackedAssets::<T>::decode_len().unwrap_or_default() as u32,
+			)
+		}
+
+		fn on_finalize(n: T::BlockNumber) {
+			for asset_id in TrackedAssets::<T>::get() {
+				Self::observe(asset_id, n);
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Start tracking the price of `asset_id`, from the end of the current block.
+		///
+		/// TWAPs are available once the asset has been tracked for their whole window.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::track_asset())]
+		pub fn track_asset(origin: OriginFor<T>, asset_id: AssetIdOf<T>) -> DispatchResult {
+			T::TrackOrigin::ensure_origin(origin)?;
+
+			TrackedAssets::<T>::try_mutate(|assets| {
+				ensure!(!assets.contains(&asset_id), Error::<T>::AlreadyTracked);
+				assets.try_push(asset_id).map_err(|_| Error::<T>::TooManyTrackedAssets)
+			})?;
+
+			Self::deposit_event(Event::AssetTracked { asset_id });
+			Ok(())
+		}
+
+		/// Stop tracking the price of `asset_id`, and remove its observations.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::untrack_asset())]
+		pub fn untrack_asset(origin: OriginFor<T>, asset_id: AssetIdOf<T>) -> DispatchResult {
+			T::TrackOrigin::ensure_origin(origin)?;
+
+			TrackedAssets::<T>::try_mutate(|assets| {
+				let index =
+					assets.iter().position(|a| *a == asset_id).ok_or(Error::<T>::NotTracked)?;
+				assets.remove(index);
+				Ok::<_, Error<T>>(())
+			})?;
+			TrackedSince::<T>::remove(asset_id);
+			LastObservation::<T>::remove(asset_id);
+			let _ = Observations::<T>::clear_prefix(asset_id, T::HistoryLength::get(), None);
+
+			Self::deposit_event(Event::AssetUntracked { asset_id });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The price of `asset_id` in its exchange, if it has one with liquidity.
+		pub fn spot_price(asset_id: AssetIdOf<T>) -> Option<FixedU128> {
+			let exchange = pallet_dex::Pallet::<T>::exchanges(asset_id)?;
+			let currency_reserve: u128 = exchange.currency_reserve.unique_saturated_into();
+			let token_reserve: u128 = exchange.token_reserve.unique_saturated_into();
+			FixedU128::checked_from_rational(currency_reserve, token_reserve)
+				.filter(|price| !price.is_zero())
+		}
+
+		/// The average price of `asset_id` over the last `window` blocks, including the current
+		/// one.
+		///
+		/// `None` if the asset hasn't been tracked for the whole window, or if the window is empty
+		/// or longer than the history kept.
+		pub fn twap(asset_id: AssetIdOf<T>, window: T::BlockNumber) -> Option<FixedU128> {
+			let history_length: T::BlockNumber = T::HistoryLength::get().into();
+			if window.is_zero() || window >= history_length {
+				return None;
+			}
+			let now = frame_system::Pallet::<T>::block_number();
+			let start = now.checked_sub(&window)?;
+			if start < TrackedSince::<T>::get(asset_id)? {
+				return None;
+			}
+
+			let last = LastObservation::<T>::get(asset_id)?;
+			let price_cumulative = Self::accumulate(&last, now);
+			let start_observation = Observations::<T>::get(asset_id, Self::slot(start))
+				.filter(|observation| observation.block_number == start)?;
+
+			let window: u128 = window.unique_saturated_into();
+			Some(FixedU128::from_inner(
+				price_cumulative.wrapping_sub(start_observation.price_cumulative) / window,
+			))
+		}
+
+		/// Record the price of `asset_id` at the end of block `n`.
+		fn observe(asset_id: AssetIdOf<T>, n: T::BlockNumber) {
+			let last = LastObservation::<T>::get(asset_id);
+			// exchanges without liquidity keep their last price
+			let price = match (Self::spot_price(asset_id), &last) {
+				(Some(price), _) => price,
+				(None, Some(last)) => last.price,
+				// nothing to track yet
+				(None, None) => return,
+			};
+			let price_cumulative = match &last {
+				Some(last) => Self::accumulate(last, n),
+				None => {
+					TrackedSince::<T>::insert(asset_id, n);
+					0
+				},
+			};
+
+			let observation = Observation { block_number: n, price_cumulative, price };
+			Observations::<T>::insert(asset_id, Self::slot(n), &observation);
+			LastObservation::<T>::insert(asset_id, observation);
+		}
+
+		/// The cumulative price at block `n`, from the `last` observation before it.
+		fn accumulate(last: &Observation<T::BlockNumber>, n: T::BlockNumber) -> u128 {
+			let blocks: u128 = n.saturating_sub(last.block_number).unique_saturated_into();
+			last.price_cumulative.wrapping_add(last.price.into_inner().wrapping_mul(blocks))
+		}
+
+		fn slot(n: T::BlockNumber) -> u32 {
+			let n: u32 = n.unique_saturated_into();
+			n % T::HistoryLength::get()
+		}
+	}
+
+	/// The TWAP of `AssetId` over the last `Window` blocks, for runtime components which need a
+	/// price resistant to manipulation.
+	pub struct Twap<T, AssetId, Window>(PhantomData<(T, AssetId, Window)>);
+	impl<T, AssetId, Window> Get<Option<FixedU128>> for Twap<T, AssetId, Window>
+	where
+		T: Config,
+		AssetId: Get<AssetIdOf<T>>,
+		Window: Get<T::BlockNumber>,
+	{
+		fn get() -> Option<FixedU128> {
+			Pallet::<T>::twap(AssetId::get(), Window::get())
+		}
+	}
+}
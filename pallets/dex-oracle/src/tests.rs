@@ -0,0 +1,198 @@
+use crate::{mock::*, Error, Event, LastObservation, Observations, Twap};
+use frame_support::{
+	assert_noop, assert_ok, parameter_types,
+	traits::{Get, Hooks},
+};
+use pallet_dex::TradeAmount;
+use sp_runtime::{DispatchError, FixedU128};
+
+const XUSD: u32 = 1;
+const TOKEN: u32 = 2;
+const NO_EXCHANGE: u32 = 3;
+
+fn setup() {
+	for asset_id in [XUSD, TOKEN, NO_EXCHANGE] {
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), asset_id, ALICE, true, 1));
+		for who in [ALICE, BOB] {
+			assert_ok!(Assets::mint(RuntimeOrigin::signed(ALICE), asset_id, who, 1_000 * UNITS));
+		}
+	}
+	assert_ok!(Dex::create_exchange(RuntimeOrigin::signed(ALICE), XUSD, 10 * UNITS, 100 * UNITS));
+	assert_ok!(Dex::create_exchange(RuntimeOrigin::signed(ALICE), TOKEN, 10 * UNITS, 20 * UNITS));
+}
+
+/// Finalize the blocks up to `n`, excluded.
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		DexOracle::on_finalize(System::block_number());
+		System::set_block_number(System::block_number() + 1);
+		DexOracle::on_initialize(System::block_number());
+	}
+}
+
+fn sell_xusd(amount: u128) {
+	assert_ok!(Dex::asset_to_currency(
+		RuntimeOrigin::signed(BOB),
+		XUSD,
+		TradeAmount::FixedInput { input_amount: amount, min_output: 1 },
+		100,
+		None
+	));
+}
+
+#[test]
+fn track_asset_works() {
+	new_test_ext().execute_with(|| {
+		setup();
+
+		assert_ok!(DexOracle::track_asset(RuntimeOrigin::root(), XUSD));
+
+		assert_eq!(DexOracle::tracked_assets().into_inner(), vec![XUSD]);
+		System::assert_last_event(Event::AssetTracked { asset_id: XUSD }.into());
+	});
+}
+
+#[test]
+fn track_asset_fails() {
+	new_test_ext().execute_with(|| {
+		setup();
+		assert_noop!(
+			DexOracle::track_asset(RuntimeOrigin::signed(ALICE), XUSD),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(DexOracle::track_asset(RuntimeOrigin::root(), XUSD));
+		assert_noop!(
+			DexOracle::track_asset(RuntimeOrigin::root(), XUSD),
+			Error::<Test>::AlreadyTracked
+		);
+
+		assert_ok!(DexOracle::track_asset(RuntimeOrigin::root(), TOKEN));
+		assert_noop!(
+			DexOracle::track_asset(RuntimeOrigin::root(), NO_EXCHANGE),
+			Error::<Test>::TooManyTrackedAssets
+		);
+	});
+}
+
+#[test]
+fn untrack_asset_removes_observations() {
+	new_test_ext().execute_with(|| {
+		setup();
+		assert_ok!(DexOracle::track_asset(RuntimeOrigin::root(), XUSD));
+		run_to_block(5);
+
+		assert_ok!(DexOracle::untrack_asset(RuntimeOrigin::root(), XUSD));
+
+		assert!(DexOracle::tracked_assets().is_empty());
+		assert!(LastObservation::<Test>::get(XUSD).is_none());
+		assert_eq!(Observations::<Test>::iter_prefix(XUSD).count(), 0);
+		assert_eq!(DexOracle::twap(XUSD, 3), None);
+		System::assert_last_event(Event::AssetUntracked { asset_id: XUSD }.into());
+
+		assert_noop!(
+			DexOracle::untrack_asset(RuntimeOrigin::root(), XUSD),
+			Error::<Test>::NotTracked
+		);
+	});
+}
+
+#[test]
+fn spot_price_is_the_ratio_of_reserves() {
+	new_test_ext().execute_with(|| {
+		setup();
+
+		assert_eq!(DexOracle::spot_price(XUSD), Some(FixedU128::from_rational(1, 10)));
+		assert_eq!(DexOracle::spot_price(TOKEN), Some(FixedU128::from_rational(1, 2)));
+		assert_eq!(DexOracle::spot_price(NO_EXCHANGE), None);
+	});
+}
+
+#[test]
+fn twap_of_constant_price() {
+	new_test_ext().execute_with(|| {
+		setup();
+		assert_ok!(DexOracle::track_asset(RuntimeOrigin::root(), XUSD));
+		run_to_block(6);
+
+		assert_eq!(DexOracle::twap(XUSD, 5), Some(FixedU128::from_rational(1, 10)));
+		assert_eq!(DexOracle::twap(XUSD, 1), Some(FixedU128::from_rational(1, 10)));
+	});
+}
+
+#[test]
+fn twap_needs_the_whole_window() {
+	new_test_ext().execute_with(|| {
+		setup();
+		assert_ok!(DexOracle::track_asset(RuntimeOrigin::root(), XUSD));
+		run_to_block(6);
+
+		// tracked since block 1
+		assert_eq!(DexOracle::twap(XUSD, 6), None);
+		assert_eq!(DexOracle::twap(XUSD, 0), None);
+		assert_eq!(DexOracle::twap(TOKEN, 1), None);
+
+		// the history is 10 blocks long
+		run_to_block(20);
+		assert!(DexOracle::twap(XUSD, 9).is_some());
+		assert_eq!(DexOracle::twap(XUSD, 10), None);
+	});
+}
+
+#[test]
+fn twap_is_weighted_by_blocks() {
+	new_test_ext().execute_with(|| {
+		setup();
+		assert_ok!(DexOracle::track_asset(RuntimeOrigin::root(), XUSD));
+		let initial_price = DexOracle::spot_price(XUSD).unwrap();
+		run_to_block(4);
+
+		sell_xusd(100 * UNITS);
+		let price = DexOracle::spot_price(XUSD).unwrap();
+		assert!(price < initial_price);
+		run_to_block(7);
+
+		// blocks 1 to 3 closed at the initial price, and blocks 4 to 6 at the new one
+		assert_eq!(
+			DexOracle::twap(XUSD, 6),
+			Some(FixedU128::from_inner(
+				(3 * initial_price.into_inner() + 3 * price.into_inner()) / 6
+			))
+		);
+		assert_eq!(DexOracle::twap(XUSD, 3), Some(price));
+	});
+}
+
+#[test]
+fn twap_ignores_prices_within_a_block() {
+	new_test_ext().execute_with(|| {
+		setup();
+		assert_ok!(DexOracle::track_asset(RuntimeOrigin::root(), XUSD));
+		let price = DexOracle::spot_price(XUSD).unwrap();
+		run_to_block(6);
+
+		sell_xusd(500 * UNITS);
+
+		assert!(DexOracle::spot_price(XUSD).unwrap() < price);
+		assert_eq!(DexOracle::twap(XUSD, 5), Some(price));
+	});
+}
+
+parameter_types! {
+	pub const XusdId: u32 = XUSD;
+	pub const Window: u64 = 5;
+}
+
+#[test]
+fn twap_adapter_works() {
+	new_test_ext().execute_with(|| {
+		setup();
+		assert_ok!(DexOracle::track_asset(RuntimeOrigin::root(), XUSD));
+		assert_eq!(Twap::<Test, XusdId, Window>::get(), None);
+
+		run_to_block(6);
+
+		assert_eq!(Twap::<Test, XusdId, Window>::get(), DexOracle::twap(XUSD, 5));
+		assert!(Twap::<Test, XusdId, Window>::get().is_some());
+	});
+}
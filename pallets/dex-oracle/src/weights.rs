@@ -0,0 +1,81 @@
+//! Weights for `pallet_dex_oracle`
+//!
+//! Estimated from the storage accesses of each call and of the observations made in
+//! `on_finalize`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+pub trait WeightInfo {
+	fn track_asset() -> Weight;
+	fn untrack_asset() -> Weight;
+	fn on_finalize(n: u32, ) -> Weight;
+}
+
+/// Weight functions for `pallet_dex_oracle`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: crate::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: DexOracle TrackedAssets (r:1 w:1)
+	fn track_asset() -> Weight {
+		Weight::from_ref_time(15_000_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: DexOracle TrackedAssets (r:1 w:1)
+	// Storage: DexOracle TrackedSince (r:0 w:1)
+	// Storage: DexOracle LastObservation (r:0 w:1)
+	// Storage: DexOracle Observations (r:0 w:HistoryLength)
+	fn untrack_asset() -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(3))
+			.saturating_add(T::DbWeight::get().writes(T::HistoryLength::get().into()))
+	}
+	// Storage: DexOracle TrackedAssets (r:1 w:0)
+	// Storage: Dex Exchanges (r:1 w:0)
+	// Storage: DexOracle LastObservation (r:1 w:1)
+	// Storage: DexOracle TrackedSince (r:0 w:1)
+	// Storage: DexOracle Observations (r:0 w:1)
+	/// The range of component `n` is `[0, MaxTrackedAssets]`.
+	fn on_finalize(n: u32, ) -> Weight {
+		Weight::from_ref_time(5_000_000)
+			.saturating_add(Weight::from_ref_time(15_000_000).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+}
+
+impl WeightInfo for () {
+	// Storage: DexOracle TrackedAssets (r:1 w:1)
+	fn track_asset() -> Weight {
+		Weight::from_ref_time(15_000_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	// Storage: DexOracle TrackedAssets (r:1 w:1)
+	// Storage: DexOracle TrackedSince (r:0 w:1)
+	// Storage: DexOracle LastObservation (r:0 w:1)
+	fn untrack_asset() -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	// Storage: DexOracle TrackedAssets (r:1 w:0)
+	// Storage: Dex Exchanges (r:1 w:0)
+	// Storage: DexOracle LastObservation (r:1 w:1)
+	// Storage: DexOracle TrackedSince (r:0 w:1)
+	// Storage: DexOracle Observations (r:0 w:1)
+	/// The range of component `n` is `[0, MaxTrackedAssets]`.
+	fn on_finalize(n: u32, ) -> Weight {
+		Weight::from_ref_time(5_000_000)
+			.saturating_add(Weight::from_ref_time(15_000_000).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+}
@@ -0,0 +1,27 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_runtime::DispatchError;
+
+sp_api::decl_runtime_apis! {
+	/// Quotes the swaps of `pallet_dex_router`, which go from an asset to the native currency
+	/// and on to another asset through their `pallet_dex` exchanges.
+	pub trait DexRouterApi<AssetId, AssetBalance> where
+		AssetId: Codec,
+		AssetBalance: Codec,
+	{
+		/// The amount of `asset_out` received by swapping `amount_in` of `asset_in`.
+		fn quote_exact_in(
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount_in: AssetBalance,
+		) -> Result<AssetBalance, DispatchError>;
+
+		/// The amount of `asset_in` needed to receive `amount_out` of `asset_out`.
+		fn quote_exact_out(
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount_out: AssetBalance,
+		) -> Result<AssetBalance, DispatchError>;
+	}
+}
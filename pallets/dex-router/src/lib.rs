@@ -0,0 +1,173 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Swaps assets for other assets through the native currency.
+//!
+//! `pallet_dex` exchanges always pair the native currency with one asset, so swapping an asset
+//! for another takes two swaps. This pallet makes both in a single call, which either completes
+//! with at least the expected output or doesn't swap at all.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Contains, UnfilteredDispatchable},
+	};
+	use frame_system::pallet_prelude::*;
+	use pallet_dex::{TradeAmount, WeightInfo as DexWeightInfo};
+
+	pub type AssetIdOf<T> = <T as pallet_dex::Config>::AssetId;
+	pub type AssetBalanceOf<T> = <T as pallet_dex::Config>::AssetBalance;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_dex::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Filters the `pallet_dex` swaps this pallet dispatches for the signer, which bypass
+		/// `BaseCallFilter`.
+		type DexCallFilter: Contains<pallet_dex::Call<Self>>;
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		Swapped {
+			who: T::AccountId,
+			asset_in: AssetIdOf<T>,
+			asset_out: AssetIdOf<T>,
+			amount_in: AssetBalanceOf<T>,
+			amount_out: AssetBalanceOf<T>,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The input and output assets are the same
+		IdenticalAssets,
+		/// No quote could be computed, e.g. because an exchange does not exist or lacks liquidity
+		QuoteUnavailable,
+		/// The swap would return less than the minimum output
+		SlippageExceeded,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Swap `amount_in` of `asset_in` for at least `min_amount_out` of `asset_out`, through
+		/// the native currency.
+		///
+		/// Both swaps are made by `pallet_dex` before `deadline`. If either fails, none is made.
+		#[pallet::call_index(0)]
+		#[pallet::weight(
+			<T as pallet::Config>::WeightInfo::swap()
+				.saturating_add(<T as pallet_dex::Config>::WeightInfo::asset_to_currency())
+				.saturating_add(<T as pallet_dex::Config>::WeightInfo::currency_to_asset())
+		)]
+		pub fn swap(
+			origin: OriginFor<T>,
+			asset_in: AssetIdOf<T>,
+			asset_out: AssetIdOf<T>,
+			amount_in: AssetBalanceOf<T>,
+			min_amount_out: AssetBalanceOf<T>,
+			deadline: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin.clone())?;
+			ensure!(asset_in != asset_out, Error::<T>::IdenticalAssets);
+
+			let currency_amount =
+				pallet_dex::Pallet::<T>::get_asset_to_currency_output_amount(asset_in, amount_in)
+					.map_err(|_| Error::<T>::QuoteUnavailable)?;
+			let amount_out = pallet_dex::Pallet::<T>::get_currency_to_asset_output_amount(
+				asset_out,
+				currency_amount,
+			)
+			.map_err(|_| Error::<T>::QuoteUnavailable)?;
+			ensure!(amount_out >= min_amount_out, Error::<T>::SlippageExceeded);
+
+			let swaps = [
+				pallet_dex::Call::<T>::asset_to_currency {
+					asset_id: asset_in,
+					amount: TradeAmount::FixedInput {
+						input_amount: amount_in,
+						min_output: currency_amount,
+					},
+					deadline,
+					recipient: None,
+				},
+				pallet_dex::Call::<T>::currency_to_asset {
+					asset_id: asset_out,
+					amount: TradeAmount::FixedInput {
+						input_amount: currency_amount,
+						min_output: amount_out,
+					},
+					deadline,
+					recipient: None,
+				},
+			];
+			ensure!(
+				swaps.iter().all(T::DexCallFilter::contains),
+				frame_system::Error::<T>::CallFiltered
+			);
+
+			// the call is transactional, so a failing second swap reverts the first one
+			for swap in swaps {
+				swap.dispatch_bypass_filter(origin.clone()).map_err(|e| e.error)?;
+			}
+
+			Self::deposit_event(Event::Swapped { who, asset_in, asset_out, amount_in, amount_out });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The amount of `asset_out` received by swapping `amount_in` of `asset_in`.
+		pub fn quote_exact_in(
+			asset_in: AssetIdOf<T>,
+			asset_out: AssetIdOf<T>,
+			amount_in: AssetBalanceOf<T>,
+		) -> Result<AssetBalanceOf<T>, DispatchError> {
+			ensure!(asset_in != asset_out, Error::<T>::IdenticalAssets);
+
+			let currency_amount =
+				pallet_dex::Pallet::<T>::get_asset_to_currency_output_amount(asset_in, amount_in)
+					.map_err(|_| Error::<T>::QuoteUnavailable)?;
+			let amount_out = pallet_dex::Pallet::<T>::get_currency_to_asset_output_amount(
+				asset_out,
+				currency_amount,
+			)
+			.map_err(|_| Error::<T>::QuoteUnavailable)?;
+			Ok(amount_out)
+		}
+
+		/// The amount of `asset_in` needed to receive `amount_out` of `asset_out`.
+		pub fn quote_exact_out(
+			asset_in: AssetIdOf<T>,
+			asset_out: AssetIdOf<T>,
+			amount_out: AssetBalanceOf<T>,
+		) -> Result<AssetBalanceOf<T>, DispatchError> {
+			ensure!(asset_in != asset_out, Error::<T>::IdenticalAssets);
+
+			let currency_amount =
+				pallet_dex::Pallet::<T>::get_currency_to_asset_input_amount(asset_out, amount_out)
+					.map_err(|_| Error::<T>::QuoteUnavailable)?;
+			let amount_in = pallet_dex::Pallet::<T>::get_asset_to_currency_input_amount(
+				asset_in,
+				currency_amount,
+			)
+			.map_err(|_| Error::<T>::QuoteUnavailable)?;
+			Ok(amount_in)
+		}
+	}
+}
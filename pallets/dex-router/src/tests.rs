@@ -0,0 +1,126 @@
+use crate::{mock::*, Error, Event};
+use frame_support::{assert_noop, assert_ok};
+
+const XUSD: u32 = 1;
+const TOKEN: u32 = 2;
+const NO_EXCHANGE: u32 = 3;
+
+fn setup() {
+	for asset_id in [XUSD, TOKEN, NO_EXCHANGE] {
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), asset_id, ALICE, true, 1));
+		for who in [ALICE, BOB] {
+			assert_ok!(Assets::mint(RuntimeOrigin::signed(ALICE), asset_id, who, 1_000 * UNITS));
+		}
+	}
+	assert_ok!(Dex::create_exchange(RuntimeOrigin::signed(ALICE), XUSD, 10 * UNITS, 100 * UNITS));
+	assert_ok!(Dex::create_exchange(RuntimeOrigin::signed(ALICE), TOKEN, 10 * UNITS, 20 * UNITS));
+}
+
+#[test]
+fn swap_works() {
+	new_test_ext().execute_with(|| {
+		setup();
+		let expected = DexRouter::quote_exact_in(XUSD, TOKEN, UNITS).unwrap();
+		let native_balance = Balances::free_balance(BOB);
+
+		assert_ok!(DexRouter::swap(RuntimeOrigin::signed(BOB), XUSD, TOKEN, UNITS, expected, 10));
+
+		assert_eq!(Assets::balance(XUSD, BOB), 999 * UNITS);
+		assert_eq!(Assets::balance(TOKEN, BOB), 1_000 * UNITS + expected);
+		// the native currency is only routed through
+		assert_eq!(Balances::free_balance(BOB), native_balance);
+		System::assert_last_event(
+			Event::Swapped {
+				who: BOB,
+				asset_in: XUSD,
+				asset_out: TOKEN,
+				amount_in: UNITS,
+				amount_out: expected,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn swap_fails_below_min_amount_out() {
+	new_test_ext().execute_with(|| {
+		setup();
+		let expected = DexRouter::quote_exact_in(XUSD, TOKEN, UNITS).unwrap();
+
+		assert_noop!(
+			DexRouter::swap(RuntimeOrigin::signed(BOB), XUSD, TOKEN, UNITS, expected + 1, 10),
+			Error::<Test>::SlippageExceeded
+		);
+	});
+}
+
+#[test]
+fn swap_fails_without_exchange() {
+	new_test_ext().execute_with(|| {
+		setup();
+
+		assert_noop!(
+			DexRouter::swap(RuntimeOrigin::signed(BOB), XUSD, NO_EXCHANGE, UNITS, 0, 10),
+			Error::<Test>::QuoteUnavailable
+		);
+		assert_noop!(
+			DexRouter::swap(RuntimeOrigin::signed(BOB), NO_EXCHANGE, XUSD, UNITS, 0, 10),
+			Error::<Test>::QuoteUnavailable
+		);
+	});
+}
+
+#[test]
+fn swap_fails_for_identical_assets() {
+	new_test_ext().execute_with(|| {
+		setup();
+
+		assert_noop!(
+			DexRouter::swap(RuntimeOrigin::signed(BOB), XUSD, XUSD, UNITS, 0, 10),
+			Error::<Test>::IdenticalAssets
+		);
+	});
+}
+
+#[test]
+fn swap_fails_after_deadline() {
+	new_test_ext().execute_with(|| {
+		setup();
+		System::set_block_number(11);
+
+		// the deadline has passed, so `pallet_dex` rejects the swaps
+		assert!(DexRouter::swap(RuntimeOrigin::signed(BOB), XUSD, TOKEN, UNITS, 0, 10).is_err());
+
+		assert_eq!(Assets::balance(XUSD, BOB), 1_000 * UNITS);
+		assert_eq!(Assets::balance(TOKEN, BOB), 1_000 * UNITS);
+	});
+}
+
+#[test]
+fn quotes_are_consistent() {
+	new_test_ext().execute_with(|| {
+		setup();
+
+		let amount_in = DexRouter::quote_exact_out(XUSD, TOKEN, UNITS).unwrap();
+		assert!(DexRouter::quote_exact_in(XUSD, TOKEN, amount_in).unwrap() >= UNITS);
+		assert!(DexRouter::quote_exact_in(XUSD, TOKEN, amount_in - 1).unwrap() < UNITS);
+		assert_eq!(
+			DexRouter::quote_exact_out(XUSD, NO_EXCHANGE, UNITS),
+			Err(Error::<Test>::QuoteUnavailable.into())
+		);
+	});
+}
+
+#[test]
+fn swap_fails_if_dex_calls_are_filtered() {
+	new_test_ext().execute_with(|| {
+		setup();
+		DexCallsPaused::set(true);
+
+		assert_noop!(
+			DexRouter::swap(RuntimeOrigin::signed(BOB), XUSD, TOKEN, UNITS, 1, 10),
+			frame_system::Error::<Test>::CallFiltered
+		);
+	});
+}
@@ -0,0 +1,35 @@
+//! Weights for `pallet_dex_router`
+//!
+//! The swaps are weighed by `pallet_dex`. These are the quotes the router makes before them,
+//! estimated from their storage accesses.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+pub trait WeightInfo {
+	fn swap() -> Weight;
+}
+
+/// Weight functions for `pallet_dex_router`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Dex Exchanges (r:2 w:0)
+	// Storage: Assets Asset (r:2 w:0)
+	fn swap() -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(T::DbWeight::get().reads(4))
+	}
+}
+
+impl WeightInfo for () {
+	// Storage: Dex Exchanges (r:2 w:0)
+	// Storage: Assets Asset (r:2 w:0)
+	fn swap() -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(RocksDbWeight::get().reads(4))
+	}
+}
@@ -0,0 +1,292 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Lets sibling parachains swap assets on `pallet_dex` over XCM.
+//!
+//! Siblings don't need an account on this chain: they deposit the asset to swap into their
+//! sovereign account and `Transact` [`Pallet::swap`] with their XCM origin, in the same message.
+//! The asset is swapped through `pallet_dex_router` by the sovereign account, and the proceeds are
+//! reserve transferred to a beneficiary on the sibling. If the swap or the transfer fails, the
+//! deposited asset is withdrawn into the asset trap, from which the sibling can claim it.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		pallet_prelude::*,
+		storage::{with_transaction, TransactionOutcome},
+		traits::{Contains, UnfilteredDispatchable},
+		weights::Weight,
+	};
+	use frame_system::pallet_prelude::*;
+	use pallet_dex::WeightInfo as DexWeightInfo;
+	use pallet_dex_router::{AssetBalanceOf, AssetIdOf, WeightInfo as RouterWeightInfo};
+	use sp_runtime::traits::SaturatedConversion;
+	use sp_std::{boxed::Box, vec};
+	use xcm::{latest::prelude::*, VersionedMultiLocation};
+	use xcm_executor::traits::{Convert, WeightBounds};
+
+	type RuntimeCallOf<T> = <T as frame_system::Config>::RuntimeCall;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_dex_router::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The XCM origin of sibling parachains.
+		type SiblingOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = MultiLocation>;
+		/// Converts the location of siblings to their sovereign account.
+		type LocationToAccountId: Convert<MultiLocation, Self::AccountId>;
+		/// Converts between the `MultiLocation` of assets and their local `AssetId`.
+		type AssetLocation: Convert<MultiLocation, AssetIdOf<Self>>;
+		/// This chain, as seen from its siblings.
+		type SelfLocation: Get<MultiLocation>;
+		/// Executes the messages transferring the proceeds and trapping the assets of failed swaps.
+		type XcmExecutor: ExecuteXcm<RuntimeCallOf<Self>>;
+		/// Determines the weight of the executed messages.
+		type Weigher: WeightBounds<RuntimeCallOf<Self>>;
+		/// Filters the `pallet_dex_router` swaps this pallet dispatches for siblings, which bypass
+		/// `BaseCallFilter`.
+		type RouterCallFilter: Contains<pallet_dex_router::Call<Self>>;
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		SwapCompleted {
+			origin: MultiLocation,
+			asset_in: AssetIdOf<T>,
+			asset_out: AssetIdOf<T>,
+			amount_in: AssetBalanceOf<T>,
+			amount_out: AssetBalanceOf<T>,
+			beneficiary: MultiLocation,
+		},
+		/// The swap failed and `amount_in` of `asset_in` was trapped.
+		SwapFailed {
+			origin: MultiLocation,
+			asset_in: AssetIdOf<T>,
+			amount_in: AssetBalanceOf<T>,
+			error: DispatchError,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The origin is not a sibling parachain
+		NotSibling,
+		/// The origin has no sovereign account
+		NoSovereignAccount,
+		/// The version of the given `MultiLocation` is not supported
+		BadVersion,
+		/// The asset has no `MultiLocation`, or its reserve is not supported
+		UnsupportedAsset,
+		/// The message transferring the proceeds could not be weighed
+		UnweighableMessage,
+		/// The message transferring the proceeds failed to execute
+		PayoutFailed,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Swap `amount_in` of `asset_in`, held by the sovereign account of the sibling, for at
+		/// least `min_amount_out` of `asset_out`, and transfer it to `beneficiary` on the sibling.
+		///
+		/// Failed swaps don't make the call fail, as `Transact` would ignore it: `amount_in` of
+		/// `asset_in` is withdrawn from the sovereign account into the asset trap instead.
+		#[pallet::call_index(0)]
+		#[pallet::weight({
+			let beneficiary: Result<MultiLocation, ()> = beneficiary.as_ref().clone().try_into();
+			// unweighable payouts fail, and the asset is trapped instead. Failed payouts are
+			// reverted, but their execution is still paid
+			let payout_weight = beneficiary
+				.ok()
+				.and_then(|beneficiary| {
+					Pallet::<T>::payout_weight(*asset_out, *min_amount_out, beneficiary)
+				})
+				.unwrap_or_default();
+			<T as pallet::Config>::WeightInfo::swap()
+				.saturating_add(<T as pallet_dex_router::Config>::WeightInfo::swap())
+				.saturating_add(<T as pallet_dex::Config>::WeightInfo::asset_to_currency())
+				.saturating_add(<T as pallet_dex::Config>::WeightInfo::currency_to_asset())
+				.saturating_add(payout_weight)
+				.saturating_add(Pallet::<T>::trap_weight(*asset_in, *amount_in))
+		})]
+		pub fn swap(
+			origin: OriginFor<T>,
+			asset_in: AssetIdOf<T>,
+			asset_out: AssetIdOf<T>,
+			amount_in: AssetBalanceOf<T>,
+			min_amount_out: AssetBalanceOf<T>,
+			deadline: T::BlockNumber,
+			beneficiary: Box<VersionedMultiLocation>,
+		) -> DispatchResult {
+			let location = T::SiblingOrigin::ensure_origin(origin)?;
+			ensure!(
+				matches!(location, MultiLocation { parents: 1, interior: X1(Parachain(_)) }),
+				Error::<T>::NotSibling
+			);
+			let beneficiary: MultiLocation =
+				(*beneficiary).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let who = T::LocationToAccountId::convert_ref(&location)
+				.map_err(|()| Error::<T>::NoSovereignAccount)?;
+
+			let result = with_transaction(|| {
+				let result = Self::swap_and_pay(
+					who,
+					&location,
+					asset_in,
+					asset_out,
+					amount_in,
+					min_amount_out,
+					deadline,
+					beneficiary.clone(),
+				);
+				match result {
+					Ok(_) => TransactionOutcome::Commit(result),
+					Err(_) => TransactionOutcome::Rollback(result),
+				}
+			});
+
+			match result {
+				Ok(amount_out) => Self::deposit_event(Event::SwapCompleted {
+					origin: location,
+					asset_in,
+					asset_out,
+					amount_in,
+					amount_out,
+					beneficiary,
+				}),
+				Err(error) => {
+					Self::trap(&location, asset_in, amount_in);
+					Self::deposit_event(Event::SwapFailed {
+						origin: location,
+						asset_in,
+						amount_in,
+						error,
+					});
+				},
+			}
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Swap for the sovereign account `who` of the sibling at `location`, and transfer the
+		/// proceeds to `beneficiary` on the sibling.
+		#[allow(clippy::too_many_arguments)]
+		fn swap_and_pay(
+			who: T::AccountId,
+			location: &MultiLocation,
+			asset_in: AssetIdOf<T>,
+			asset_out: AssetIdOf<T>,
+			amount_in: AssetBalanceOf<T>,
+			min_amount_out: AssetBalanceOf<T>,
+			deadline: T::BlockNumber,
+			beneficiary: MultiLocation,
+		) -> Result<AssetBalanceOf<T>, DispatchError> {
+			let amount_out =
+				pallet_dex_router::Pallet::<T>::quote_exact_in(asset_in, asset_out, amount_in)?;
+			let swap = pallet_dex_router::Call::<T>::swap {
+				asset_in,
+				asset_out,
+				amount_in,
+				min_amount_out,
+				deadline,
+			};
+			ensure!(T::RouterCallFilter::contains(&swap), frame_system::Error::<T>::CallFiltered);
+			swap.dispatch_bypass_filter(frame_system::RawOrigin::Signed(who).into())
+				.map_err(|e| e.error)?;
+
+			let mut message = Self::payout_message(
+				asset_out,
+				amount_out.saturated_into(),
+				location.clone(),
+				beneficiary,
+			)?;
+			let weight =
+				T::Weigher::weight(&mut message).map_err(|()| Error::<T>::UnweighableMessage)?;
+			// the transfer is paid by the extrinsic's weight
+			let outcome =
+				T::XcmExecutor::execute_xcm_in_credit(location.clone(), message, weight, weight);
+			ensure!(matches!(outcome, Outcome::Complete(_)), Error::<T>::PayoutFailed);
+
+			Ok(amount_out)
+		}
+
+		/// Withdraw `amount` of `asset_id` from the sovereign account of the sibling at `location`
+		/// into the holding register of an otherwise empty message, which the XCM executor then
+		/// drops into the asset trap.
+		fn trap(location: &MultiLocation, asset_id: AssetIdOf<T>, amount: AssetBalanceOf<T>) {
+			// assets which can't be withdrawn over XCM are left in the sovereign account
+			if let Some(mut message) = Self::trap_message(asset_id, amount) {
+				if let Ok(weight) = T::Weigher::weight(&mut message) {
+					T::XcmExecutor::execute_xcm_in_credit(
+						location.clone(),
+						message,
+						weight,
+						weight,
+					);
+				}
+			}
+		}
+
+		fn trap_message(
+			asset_id: AssetIdOf<T>,
+			amount: AssetBalanceOf<T>,
+		) -> Option<Xcm<RuntimeCallOf<T>>> {
+			let asset_location = T::AssetLocation::reverse_ref(asset_id).ok()?;
+			Some(Xcm(vec![WithdrawAsset((asset_location, amount.saturated_into::<u128>()).into())]))
+		}
+
+		/// The weight of trapping the asset of a failed swap.
+		pub fn trap_weight(asset_id: AssetIdOf<T>, amount: AssetBalanceOf<T>) -> Weight {
+			Self::trap_message(asset_id, amount)
+				.and_then(|mut message| T::Weigher::weight(&mut message).ok())
+				.map_or(Weight::zero(), Weight::from_ref_time)
+		}
+
+		/// The weight of transferring the proceeds, through the longest route.
+		pub fn payout_weight(
+			asset_id: AssetIdOf<T>,
+			amount: AssetBalanceOf<T>,
+			beneficiary: MultiLocation,
+		) -> Option<Weight> {
+			// a sibling which is not the reserve of the asset
+			let dest = MultiLocation::new(1, X1(Parachain(u32::MAX)));
+			let mut message =
+				Self::payout_message(asset_id, amount.saturated_into(), dest, beneficiary).ok()?;
+			T::Weigher::weight(&mut message).ok().map(Weight::from_ref_time)
+		}
+
+		/// The message withdrawing `amount` of `asset_id` from the sovereign account of the
+		/// sibling `dest` and transferring it to `beneficiary` on `dest`.
+		pub fn payout_message(
+			asset_id: AssetIdOf<T>,
+			amount: u128,
+			dest: MultiLocation,
+			beneficiary: MultiLocation,
+		) -> Result<Xcm<RuntimeCallOf<T>>, Error<T>> {
+			let asset_location = T::AssetLocation::reverse_ref(asset_id)
+				.map_err(|()| Error::<T>::UnsupportedAsset)?;
+			xcm_primitives::reserve_transfer_to_sibling(
+				asset_location,
+				amount,
+				dest,
+				beneficiary,
+				T::SelfLocation::get(),
+			)
+			.ok_or(Error::<T>::UnsupportedAsset)
+		}
+	}
+}
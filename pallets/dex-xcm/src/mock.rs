@@ -0,0 +1,285 @@
+use crate as pallet_dex_xcm;
+use frame_support::{
+	assert_ok, parameter_types,
+	traits::{
+		AsEnsureOriginWithArg, ConstU128, ConstU16, ConstU64, Contains, EnsureOrigin, Everything,
+	},
+	PalletId,
+};
+use frame_system::{EnsureRoot, EnsureSigned, RawOrigin};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, ConstU32, IdentityLookup},
+};
+use sp_std::borrow::Borrow;
+use xcm::latest::prelude::*;
+use xcm_builder::FixedWeightBounds;
+use xcm_executor::traits::Convert;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub const ALICE: u64 = 1;
+pub const UNITS: u128 = 1_000_000_000_000;
+/// The account signing for the relay chain in [`EnsureXcmOrigin`].
+pub const RELAY: u64 = 0;
+/// The reserve parachain of xUSD.
+pub const RESERVE: u32 = 1000;
+pub const SIBLING: u32 = 2000;
+pub const SELF_PARA_ID: u32 = 3000;
+
+pub const XUSD: u32 = 1;
+pub const TOKEN: u32 = 2;
+
+parameter_types! {
+	pub SelfLocation: MultiLocation = MultiLocation::new(1, X1(Parachain(SELF_PARA_ID)));
+	pub static ExecutedMessages: Vec<(MultiLocation, Xcm<RuntimeCall>)> = vec![];
+	pub static PayoutOutcome: Option<XcmError> = None;
+	pub static RouterCallsPaused: bool = false;
+}
+
+pub fn xusd_location() -> MultiLocation {
+	MultiLocation::new(1, X3(Parachain(RESERVE), PalletInstance(50), GeneralIndex(XUSD.into())))
+}
+
+pub fn token_location() -> MultiLocation {
+	MultiLocation::new(0, X2(PalletInstance(42), GeneralIndex(TOKEN.into())))
+}
+
+pub fn sibling(para_id: u32) -> MultiLocation {
+	MultiLocation::new(1, X1(Parachain(para_id)))
+}
+
+/// Stands in for the XCM origin: accounts sign for the parachain of the same ID, and `RELAY` for
+/// the relay chain.
+pub struct EnsureXcmOrigin;
+impl EnsureOrigin<RuntimeOrigin> for EnsureXcmOrigin {
+	type Success = MultiLocation;
+
+	fn try_origin(o: RuntimeOrigin) -> Result<MultiLocation, RuntimeOrigin> {
+		o.into().and_then(|o| match o {
+			RawOrigin::Signed(RELAY) => Ok(MultiLocation::parent()),
+			RawOrigin::Signed(who) => Ok(sibling(who as u32)),
+			r => Err(RuntimeOrigin::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+		Ok(RuntimeOrigin::signed(SIBLING.into()))
+	}
+}
+
+/// The sovereign account of parachains has the ID of the parachain.
+pub struct SovereignAccount;
+impl Convert<MultiLocation, u64> for SovereignAccount {
+	fn convert_ref(location: impl Borrow<MultiLocation>) -> Result<u64, ()> {
+		match location.borrow() {
+			MultiLocation { parents: 1, interior: X1(Parachain(para_id)) } => Ok((*para_id).into()),
+			_ => Err(()),
+		}
+	}
+}
+
+pub struct AssetLocation;
+impl Convert<MultiLocation, u32> for AssetLocation {
+	fn convert_ref(location: impl Borrow<MultiLocation>) -> Result<u32, ()> {
+		let location = location.borrow();
+		if *location == xusd_location() {
+			Ok(XUSD)
+		} else if *location == token_location() {
+			Ok(TOKEN)
+		} else {
+			Err(())
+		}
+	}
+
+	fn reverse_ref(asset_id: impl Borrow<u32>) -> Result<MultiLocation, ()> {
+		match *asset_id.borrow() {
+			XUSD => Ok(xusd_location()),
+			TOKEN => Ok(token_location()),
+			_ => Err(()),
+		}
+	}
+}
+
+/// Records the executed messages, completing or failing them as set in `PayoutOutcome`.
+pub struct RecordingXcmExecutor;
+impl ExecuteXcm<RuntimeCall> for RecordingXcmExecutor {
+	fn execute_xcm_in_credit(
+		origin: impl Into<MultiLocation>,
+		message: Xcm<RuntimeCall>,
+		weight_limit: u64,
+		_weight_credit: u64,
+	) -> Outcome {
+		ExecutedMessages::mutate(|messages| messages.push((origin.into(), message)));
+		match PayoutOutcome::get() {
+			None => Outcome::Complete(weight_limit),
+			Some(error) => Outcome::Incomplete(weight_limit, error),
+		}
+	}
+}
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		Assets: pallet_assets,
+		Dex: pallet_dex,
+		DexRouter: pallet_dex_router::{Pallet, Call, Event<T>},
+		DexXcm: pallet_dex_xcm::{Pallet, Call, Event<T>},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u128>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type Balance = u128;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU128<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+}
+
+impl pallet_assets::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = u128;
+	type AssetId = u32;
+	type AssetIdParameter = u32;
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<u64>>;
+	type ForceOrigin = EnsureRoot<u64>;
+	type AssetDeposit = ConstU128<1>;
+	type MetadataDepositBase = ConstU128<1>;
+	type MetadataDepositPerByte = ConstU128<1>;
+	type ApprovalDeposit = ConstU128<1>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+	type AssetAccountDeposit = ConstU128<1>;
+	type RemoveItemsLimit = ConstU32<1000>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+	type CallbackHandle = ();
+}
+
+parameter_types! {
+	pub const DexPalletId: PalletId = PalletId(*b"test/dex");
+}
+
+impl pallet_dex::Config for Test {
+	type PalletId = DexPalletId;
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type AssetBalance = u128;
+	type AssetToCurrencyBalance = sp_runtime::traits::Identity;
+	type CurrencyToAssetBalance = sp_runtime::traits::Identity;
+	type AssetId = u32;
+	type Assets = Assets;
+	type AssetRegistry = Assets;
+	type WeightInfo = pallet_dex::weights::SubstrateWeight<Test>;
+	type ProviderFeeNumerator = ConstU128<3>;
+	type ProviderFeeDenominator = ConstU128<1000>;
+	type MinDeposit = ConstU128<{ UNITS }>;
+}
+
+impl pallet_dex_router::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type DexCallFilter = Everything;
+	type WeightInfo = pallet_dex_router::weights::SubstrateWeight<Test>;
+}
+
+impl pallet_dex_xcm::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type SiblingOrigin = EnsureXcmOrigin;
+	type LocationToAccountId = SovereignAccount;
+	type AssetLocation = AssetLocation;
+	type SelfLocation = SelfLocation;
+	type XcmExecutor = RecordingXcmExecutor;
+	type Weigher = FixedWeightBounds<ConstU64<10>, RuntimeCall, ConstU32<100>>;
+	type RouterCallFilter = RouterCallFilter;
+	type WeightInfo = pallet_dex_xcm::weights::SubstrateWeight<Test>;
+}
+
+pub struct RouterCallFilter;
+impl Contains<pallet_dex_router::Call<Test>> for RouterCallFilter {
+	fn contains(_: &pallet_dex_router::Call<Test>) -> bool {
+		!RouterCallsPaused::get()
+	}
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(ALICE, 1_000 * UNITS), (SIBLING.into(), UNITS), (RESERVE.into(), UNITS)],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+
+	let mut ext: sp_io::TestExternalities = storage.into();
+	// events are not deposited on the genesis block
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		for asset_id in [XUSD, TOKEN] {
+			assert_ok!(Assets::force_create(RuntimeOrigin::root(), asset_id, ALICE, true, 1));
+			for who in [ALICE, SIBLING.into(), RESERVE.into()] {
+				assert_ok!(Assets::mint(
+					RuntimeOrigin::signed(ALICE),
+					asset_id,
+					who,
+					1_000 * UNITS
+				));
+			}
+		}
+		assert_ok!(Dex::create_exchange(
+			RuntimeOrigin::signed(ALICE),
+			XUSD,
+			10 * UNITS,
+			100 * UNITS
+		));
+		assert_ok!(Dex::create_exchange(
+			RuntimeOrigin::signed(ALICE),
+			TOKEN,
+			10 * UNITS,
+			20 * UNITS
+		));
+	});
+	ext
+}
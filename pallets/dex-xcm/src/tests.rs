@@ -0,0 +1,271 @@
+use crate::{mock::*, Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+use xcm::latest::prelude::*;
+
+fn swap(
+	para_id: u32,
+	asset_in: u32,
+	asset_out: u32,
+	amount_in: u128,
+	min_amount_out: u128,
+) -> Result<(), DispatchError> {
+	DexXcm::swap(
+		RuntimeOrigin::signed(para_id.into()),
+		asset_in,
+		asset_out,
+		amount_in,
+		min_amount_out,
+		10,
+		Box::new(bob().into()),
+	)
+}
+
+fn bob() -> MultiLocation {
+	MultiLocation::new(0, X1(AccountId32 { network: Any, id: [2u8; 32] }))
+}
+
+fn deposit() -> Instruction<RuntimeCall> {
+	DepositAsset { assets: Wild(All), max_assets: 1, beneficiary: bob() }
+}
+
+#[test]
+fn swap_pays_out_assets_of_this_chain() {
+	new_test_ext().execute_with(|| {
+		let expected = DexRouter::quote_exact_in(XUSD, TOKEN, UNITS).unwrap();
+
+		assert_ok!(swap(SIBLING, XUSD, TOKEN, UNITS, expected));
+
+		let sovereign_account: u64 = SIBLING.into();
+		assert_eq!(Assets::balance(XUSD, sovereign_account), 999 * UNITS);
+		assert_eq!(Assets::balance(TOKEN, sovereign_account), 1_000 * UNITS + expected);
+		assert_eq!(
+			ExecutedMessages::get(),
+			vec![(
+				sibling(SIBLING),
+				Xcm(vec![
+					WithdrawAsset((token_location(), expected).into()),
+					DepositReserveAsset {
+						assets: Wild(All),
+						max_assets: 1,
+						dest: sibling(SIBLING),
+						xcm: Xcm(vec![
+							BuyExecution {
+								fees: (
+									MultiLocation::new(
+										1,
+										X3(
+											Parachain(SELF_PARA_ID),
+											PalletInstance(42),
+											GeneralIndex(TOKEN.into())
+										)
+									),
+									expected
+								)
+									.into(),
+								weight_limit: Unlimited,
+							},
+							deposit(),
+						]),
+					},
+				])
+			)]
+		);
+		System::assert_last_event(
+			Event::SwapCompleted {
+				origin: sibling(SIBLING),
+				asset_in: XUSD,
+				asset_out: TOKEN,
+				amount_in: UNITS,
+				amount_out: expected,
+				beneficiary: bob(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn swap_pays_out_to_reserve() {
+	new_test_ext().execute_with(|| {
+		let expected = DexRouter::quote_exact_in(TOKEN, XUSD, UNITS).unwrap();
+
+		assert_ok!(swap(RESERVE, TOKEN, XUSD, UNITS, expected));
+
+		assert_eq!(
+			ExecutedMessages::get(),
+			vec![(
+				sibling(RESERVE),
+				Xcm(vec![
+					WithdrawAsset((xusd_location(), expected).into()),
+					InitiateReserveWithdraw {
+						assets: Wild(All),
+						reserve: sibling(RESERVE),
+						xcm: Xcm(vec![
+							BuyExecution {
+								fees: (
+									MultiLocation::new(
+										0,
+										X2(PalletInstance(50), GeneralIndex(XUSD.into()))
+									),
+									expected
+								)
+									.into(),
+								weight_limit: Unlimited,
+							},
+							deposit(),
+						]),
+					},
+				])
+			)]
+		);
+	});
+}
+
+#[test]
+fn swap_pays_out_through_reserve() {
+	new_test_ext().execute_with(|| {
+		let expected = DexRouter::quote_exact_in(TOKEN, XUSD, UNITS).unwrap();
+
+		assert_ok!(swap(SIBLING, TOKEN, XUSD, UNITS, expected));
+
+		let half = expected / 2;
+		assert_eq!(
+			ExecutedMessages::get(),
+			vec![(
+				sibling(SIBLING),
+				Xcm(vec![
+					WithdrawAsset((xusd_location(), expected).into()),
+					InitiateReserveWithdraw {
+						assets: Wild(All),
+						reserve: sibling(RESERVE),
+						xcm: Xcm(vec![
+							BuyExecution {
+								fees: (
+									MultiLocation::new(
+										0,
+										X2(PalletInstance(50), GeneralIndex(XUSD.into()))
+									),
+									half
+								)
+									.into(),
+								weight_limit: Unlimited,
+							},
+							DepositReserveAsset {
+								assets: Wild(All),
+								max_assets: 1,
+								dest: sibling(SIBLING),
+								xcm: Xcm(vec![
+									BuyExecution {
+										fees: (xusd_location(), half).into(),
+										weight_limit: Unlimited,
+									},
+									deposit(),
+								]),
+							},
+						]),
+					},
+				])
+			)]
+		);
+	});
+}
+
+#[test]
+fn failed_swap_traps_asset_in() {
+	new_test_ext().execute_with(|| {
+		let expected = DexRouter::quote_exact_in(XUSD, TOKEN, UNITS).unwrap();
+
+		// the call succeeds, as `Transact` would ignore its failure
+		assert_ok!(swap(SIBLING, XUSD, TOKEN, UNITS, expected + 1));
+
+		let sovereign_account: u64 = SIBLING.into();
+		assert_eq!(Assets::balance(XUSD, sovereign_account), 1_000 * UNITS);
+		assert_eq!(Assets::balance(TOKEN, sovereign_account), 1_000 * UNITS);
+		assert_eq!(
+			ExecutedMessages::get(),
+			vec![(sibling(SIBLING), Xcm(vec![WithdrawAsset((xusd_location(), UNITS).into())]))]
+		);
+		System::assert_last_event(
+			Event::SwapFailed {
+				origin: sibling(SIBLING),
+				asset_in: XUSD,
+				amount_in: UNITS,
+				error: pallet_dex_router::Error::<Test>::SlippageExceeded.into(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn failed_payout_reverts_swap() {
+	new_test_ext().execute_with(|| {
+		PayoutOutcome::set(Some(XcmError::FailedToTransactAsset("")));
+
+		assert_ok!(swap(SIBLING, XUSD, TOKEN, UNITS, 1));
+
+		// the swap is reverted, and the asset trapped
+		let sovereign_account: u64 = SIBLING.into();
+		assert_eq!(Assets::balance(XUSD, sovereign_account), 1_000 * UNITS);
+		assert_eq!(Assets::balance(TOKEN, sovereign_account), 1_000 * UNITS);
+		let messages = ExecutedMessages::get();
+		assert_eq!(messages.len(), 2);
+		assert_eq!(
+			messages[1],
+			(sibling(SIBLING), Xcm(vec![WithdrawAsset((xusd_location(), UNITS).into())]))
+		);
+		System::assert_last_event(
+			Event::SwapFailed {
+				origin: sibling(SIBLING),
+				asset_in: XUSD,
+				amount_in: UNITS,
+				error: Error::<Test>::PayoutFailed.into(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn swap_fails_for_non_siblings() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			DexXcm::swap(
+				RuntimeOrigin::signed(RELAY),
+				XUSD,
+				TOKEN,
+				UNITS,
+				1,
+				10,
+				Box::new(bob().into())
+			),
+			Error::<Test>::NotSibling
+		);
+		assert_noop!(
+			DexXcm::swap(RuntimeOrigin::root(), XUSD, TOKEN, UNITS, 1, 10, Box::new(bob().into())),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn filtered_swap_traps_asset_in() {
+	new_test_ext().execute_with(|| {
+		RouterCallsPaused::set(true);
+
+		assert_ok!(swap(SIBLING, XUSD, TOKEN, UNITS, 1));
+
+		let sovereign_account: u64 = SIBLING.into();
+		assert_eq!(Assets::balance(TOKEN, sovereign_account), 1_000 * UNITS);
+		System::assert_last_event(
+			Event::SwapFailed {
+				origin: sibling(SIBLING),
+				asset_in: XUSD,
+				amount_in: UNITS,
+				error: frame_system::Error::<Test>::CallFiltered.into(),
+			}
+			.into(),
+		);
+	});
+}
@@ -0,0 +1,35 @@
+//! Weights for `pallet_dex_xcm`
+//!
+//! The swaps are weighed by `pallet_dex_router` and `pallet_dex`, and the executed messages by the
+//! pallet's `Weigher`. These only account for the dispatch itself.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+pub trait WeightInfo {
+	fn swap() -> Weight;
+}
+
+/// Weight functions for `pallet_dex_xcm`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Dex Exchanges (r:2 w:0)
+	// Storage: AssetRegistry AssetIdMultiLocation (r:2 w:0)
+	fn swap() -> Weight {
+		Weight::from_ref_time(30_000_000)
+			.saturating_add(T::DbWeight::get().reads(4))
+	}
+}
+
+impl WeightInfo for () {
+	// Storage: Dex Exchanges (r:2 w:0)
+	// Storage: AssetRegistry AssetIdMultiLocation (r:2 w:0)
+	fn swap() -> Weight {
+		Weight::from_ref_time(30_000_000)
+			.saturating_add(RocksDbWeight::get().reads(4))
+	}
+}
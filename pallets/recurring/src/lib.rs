@@ -0,0 +1,307 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Recurring payments, such as transfers, DEX swaps (e.g. dollar-cost averaging) or XCM reserve
+//! transfers, registered by signed accounts.
+//!
+//! A recurring payment is a call allowed by `RecurringCalls`, stored with `Preimages` and
+//! dispatched on behalf of its owner every `period` blocks by the `Scheduler`, a given number of
+//! times or until cancelled. Failed executions don't stop the payment: they are reported by
+//! [`Event::PaymentFailed`], and the next execution is made as scheduled.
+//!
+//! Accounts reserve a deposit for each of their payments and each byte of their calls, and may
+//! only register up to `MaxPaymentsPerAccount` of them. No more than `MaxPayments` payments are
+//! registered at once, so that they can't fill the agenda of the `Scheduler` for other tasks
+//! (e.g. the enactment of referenda).
+//!
+//! Executions are dispatched by the `Scheduler` as `Root`, so filtering the calls of this pallet
+//! (e.g. pausing them) doesn't stop them. The calls of payments are dispatched as signed by their
+//! owner though, and go through the runtime's `BaseCallFilter`: filtered calls fail, and the
+//! payment carries on.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use codec::Encode;
+	use frame_support::{
+		dispatch::{GetDispatchInfo, PostDispatchInfo},
+		pallet_prelude::*,
+		traits::{
+			schedule::{v3::Named as ScheduleNamed, DispatchTime, LOWEST_PRIORITY},
+			Bounded, Contains, Currency, QueryPreimage, ReservableCurrency, StorePreimage,
+		},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_io::hashing::blake2_256;
+	use sp_runtime::traits::{Dispatchable, One, Saturating};
+	use sp_std::boxed::Box;
+
+	pub type PaymentId = u32;
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type CallOf<T> = <T as Config>::RuntimeCall;
+	pub type PaymentOf<T> = RecurringPayment<
+		<T as frame_system::Config>::AccountId,
+		Bounded<CallOf<T>>,
+		<T as frame_system::Config>::BlockNumber,
+		BalanceOf<T>,
+	>;
+
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct RecurringPayment<AccountId, Call, BlockNumber, Balance> {
+		pub owner: AccountId,
+		/// The call dispatched on behalf of `owner`, stored with `Preimages`.
+		pub call: Call,
+		pub period: BlockNumber,
+		/// The number of executions left, if limited.
+		pub remaining: Option<u32>,
+		pub deposit: Balance,
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The overarching call type.
+		type RuntimeCall: Parameter
+			+ Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ From<Call<Self>>;
+		/// The caller origin, overarching type of all pallets origins.
+		type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>>;
+		/// Dispatches the executions of payments.
+		type Scheduler: ScheduleNamed<Self::BlockNumber, CallOf<Self>, Self::PalletsOrigin>;
+		/// Stores the calls of payments.
+		type Preimages: QueryPreimage + StorePreimage;
+		/// The currency in which deposits are reserved.
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// The calls which can be made recurring, e.g. transfers and swaps.
+		type RecurringCalls: Contains<CallOf<Self>>;
+		/// The deposit reserved for each payment.
+		#[pallet::constant]
+		type PaymentDeposit: Get<BalanceOf<Self>>;
+		/// The deposit reserved for each byte of the call of a payment.
+		#[pallet::constant]
+		type ByteDeposit: Get<BalanceOf<Self>>;
+		/// The maximum number of payments of an account.
+		#[pallet::constant]
+		type MaxPaymentsPerAccount: Get<u32>;
+		/// The maximum number of payments. Should be lower than the maximum number of tasks
+		/// scheduled per block by the `Scheduler`.
+		#[pallet::constant]
+		type MaxPayments: Get<u32>;
+		/// The shortest period between two executions.
+		#[pallet::constant]
+		type MinPeriod: Get<Self::BlockNumber>;
+		/// The maximum weight of the calls of payments.
+		#[pallet::constant]
+		type MaxCallWeight: Get<Weight>;
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The ID of the next payment.
+	#[pallet::storage]
+	pub type NextPaymentId<T: Config> = StorageValue<_, PaymentId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn payments)]
+	pub type Payments<T: Config> = CountedStorageMap<_, Twox64Concat, PaymentId, PaymentOf<T>>;
+
+	/// The number of payments of each account.
+	#[pallet::storage]
+	#[pallet::getter(fn payment_count)]
+	pub type PaymentCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		PaymentCreated {
+			id: PaymentId,
+			owner: T::AccountId,
+			period: T::BlockNumber,
+			count: Option<u32>,
+		},
+		PaymentExecuted {
+			id: PaymentId,
+		},
+		/// The execution of the payment failed. The next one is still made as scheduled.
+		PaymentFailed {
+			id: PaymentId,
+			error: DispatchError,
+		},
+		/// The last execution of the payment was made.
+		PaymentCompleted {
+			id: PaymentId,
+		},
+		PaymentCancelled {
+			id: PaymentId,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The call can't be made recurring
+		CallNotAllowed,
+		/// The call is heavier than `MaxCallWeight`
+		CallTooHeavy,
+		/// The period is shorter than `MinPeriod`
+		PeriodTooShort,
+		/// The payment would never be executed
+		ZeroCount,
+		/// The account already has `MaxPaymentsPerAccount` payments
+		TooManyPayments,
+		/// `MaxPayments` payments are already registered
+		MaxPaymentsReached,
+		/// No more payment IDs are available
+		NoAvailablePaymentId,
+		/// The payment does not exist
+		UnknownPayment,
+		/// The payment is not owned by the signer
+		NotOwner,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Dispatch `call` on behalf of the signer every `period` blocks, `count` times or until
+		/// cancelled if `None`, starting `period` blocks from now.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::create())]
+		pub fn create(
+			origin: OriginFor<T>,
+			call: Box<CallOf<T>>,
+			period: T::BlockNumber,
+			count: Option<u32>,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+
+			ensure!(T::RecurringCalls::contains(&call), Error::<T>::CallNotAllowed);
+			ensure!(
+				!call.get_dispatch_info().weight.any_gt(T::MaxCallWeight::get()),
+				Error::<T>::CallTooHeavy
+			);
+			ensure!(period >= T::MinPeriod::get(), Error::<T>::PeriodTooShort);
+			ensure!(count != Some(0), Error::<T>::ZeroCount);
+			let payment_count = PaymentCount::<T>::get(&owner);
+			ensure!(payment_count < T::MaxPaymentsPerAccount::get(), Error::<T>::TooManyPayments);
+			ensure!(Payments::<T>::count() < T::MaxPayments::get(), Error::<T>::MaxPaymentsReached);
+			let id = NextPaymentId::<T>::get();
+			let next_id = id.checked_add(1).ok_or(Error::<T>::NoAvailablePaymentId)?;
+
+			let bytes: BalanceOf<T> = (call.encoded_size() as u32).into();
+			let deposit = T::PaymentDeposit::get()
+				.saturating_add(T::ByteDeposit::get().saturating_mul(bytes));
+			T::Currency::reserve(&owner, deposit)?;
+
+			// the scheduler only accepts periodic tasks of more than one execution
+			let maybe_periodic =
+				Some((period, count.unwrap_or(u32::MAX))).filter(|(_, count)| *count > 1);
+			T::Scheduler::schedule_named(
+				Self::task_name(id),
+				DispatchTime::After(period.saturating_sub(One::one())),
+				maybe_periodic,
+				LOWEST_PRIORITY,
+				frame_system::RawOrigin::Root.into(),
+				T::Preimages::bound(CallOf::<T>::from(Call::execute { id }))?,
+			)?;
+
+			let payment = RecurringPayment {
+				owner: owner.clone(),
+				call: T::Preimages::bound(*call)?,
+				period,
+				remaining: count,
+				deposit,
+			};
+			Payments::<T>::insert(id, payment);
+			PaymentCount::<T>::insert(&owner, payment_count + 1);
+			NextPaymentId::<T>::put(next_id);
+
+			Self::deposit_event(Event::PaymentCreated { id, owner, period, count });
+			Ok(())
+		}
+
+		/// Cancel the payment `id` of the signer, and unreserve its deposit.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::cancel())]
+		pub fn cancel(origin: OriginFor<T>, id: PaymentId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let payment = Payments::<T>::get(id).ok_or(Error::<T>::UnknownPayment)?;
+			ensure!(payment.owner == who, Error::<T>::NotOwner);
+
+			T::Scheduler::cancel_named(Self::task_name(id))?;
+			Self::remove(id, payment);
+
+			Self::deposit_event(Event::PaymentCancelled { id });
+			Ok(())
+		}
+
+		/// Execute the payment `id`. Dispatched by the `Scheduler`.
+		#[pallet::call_index(2)]
+		#[pallet::weight(
+			<T as pallet::Config>::WeightInfo::execute().saturating_add(T::MaxCallWeight::get())
+		)]
+		pub fn execute(origin: OriginFor<T>, id: PaymentId) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			let mut payment = Payments::<T>::get(id).ok_or(Error::<T>::UnknownPayment)?;
+
+			let (result, call_weight) = match T::Preimages::peek(&payment.call) {
+				Ok((call, _)) => {
+					let info = call.get_dispatch_info();
+					// dispatched as a signed call, so the calls filtered out of the runtime are too
+					let origin = frame_system::RawOrigin::Signed(payment.owner.clone()).into();
+					match call.dispatch(origin) {
+						Ok(post_info) => (Ok(()), post_info.calc_actual_weight(&info)),
+						Err(e) => (Err(e.error), e.post_info.calc_actual_weight(&info)),
+					}
+				},
+				Err(error) => (Err(error), Weight::zero()),
+			};
+			match result {
+				Ok(()) => Self::deposit_event(Event::PaymentExecuted { id }),
+				Err(error) => Self::deposit_event(Event::PaymentFailed { id, error }),
+			}
+
+			match payment.remaining {
+				Some(remaining) if remaining <= 1 => {
+					Self::remove(id, payment);
+					Self::deposit_event(Event::PaymentCompleted { id });
+				},
+				Some(remaining) => {
+					payment.remaining = Some(remaining - 1);
+					Payments::<T>::insert(id, payment);
+				},
+				None => {},
+			}
+
+			Ok(Some(<T as pallet::Config>::WeightInfo::execute().saturating_add(call_weight))
+				.into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The name of the task of the payment `id` in the `Scheduler`.
+		pub fn task_name(id: PaymentId) -> [u8; 32] {
+			(b"recurring", id).using_encoded(blake2_256)
+		}
+
+		fn remove(id: PaymentId, payment: PaymentOf<T>) {
+			T::Preimages::drop(&payment.call);
+			T::Currency::unreserve(&payment.owner, payment.deposit);
+			PaymentCount::<T>::mutate(&payment.owner, |count| *count = count.saturating_sub(1));
+			Payments::<T>::remove(id);
+		}
+	}
+}
@@ -0,0 +1,175 @@
+use crate::{mock::*, Error, Event, NextPaymentId, PaymentCount, Payments};
+use codec::Encode;
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use sp_runtime::{DispatchError, Perbill};
+
+fn transfer(value: u64) -> Box<RuntimeCall> {
+	Box::new(RuntimeCall::Balances(pallet_balances::Call::transfer { dest: BOB, value }))
+}
+
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		Scheduler::on_initialize(System::block_number());
+	}
+}
+
+#[test]
+fn create_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Recurring::create(RuntimeOrigin::signed(ALICE), transfer(10), 5, Some(3)));
+
+		let payment = Recurring::payments(0).unwrap();
+		assert_eq!((payment.owner, payment.period, payment.remaining), (ALICE, 5, Some(3)));
+		assert_eq!(Recurring::payment_count(ALICE), 1);
+		let deposit = DEPOSIT + BYTE_DEPOSIT * transfer(10).encoded_size() as u64;
+		assert_eq!((payment.deposit, Balances::reserved_balance(ALICE)), (deposit, deposit));
+		System::assert_last_event(
+			Event::PaymentCreated { id: 0, owner: ALICE, period: 5, count: Some(3) }.into(),
+		);
+	});
+}
+
+#[test]
+fn payment_is_executed_every_period() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Recurring::create(RuntimeOrigin::signed(ALICE), transfer(10), 5, Some(3)));
+
+		run_to_block(5);
+		assert_eq!(Balances::free_balance(BOB), 1_000);
+
+		run_to_block(6);
+		assert_eq!(Balances::free_balance(BOB), 1_010);
+		System::assert_has_event(Event::PaymentExecuted { id: 0 }.into());
+		assert_eq!(Recurring::payments(0).unwrap().remaining, Some(2));
+
+		run_to_block(16);
+		assert_eq!(Balances::free_balance(BOB), 1_030);
+		System::assert_has_event(Event::PaymentCompleted { id: 0 }.into());
+		assert!(Payments::<Test>::get(0).is_none());
+		assert_eq!(PaymentCount::<Test>::get(ALICE), 0);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+
+		run_to_block(30);
+		assert_eq!(Balances::free_balance(BOB), 1_030);
+	});
+}
+
+#[test]
+fn payment_without_count_runs_until_cancelled() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Recurring::create(RuntimeOrigin::signed(ALICE), transfer(10), 5, None));
+		run_to_block(16);
+		assert_eq!(Balances::free_balance(BOB), 1_030);
+
+		assert_noop!(Recurring::cancel(RuntimeOrigin::signed(BOB), 0), Error::<Test>::NotOwner);
+		assert_ok!(Recurring::cancel(RuntimeOrigin::signed(ALICE), 0));
+
+		System::assert_last_event(Event::PaymentCancelled { id: 0 }.into());
+		assert!(Payments::<Test>::get(0).is_none());
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_noop!(
+			Recurring::cancel(RuntimeOrigin::signed(ALICE), 0),
+			Error::<Test>::UnknownPayment
+		);
+
+		run_to_block(30);
+		assert_eq!(Balances::free_balance(BOB), 1_030);
+	});
+}
+
+#[test]
+fn failed_execution_is_reported() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Recurring::create(RuntimeOrigin::signed(ALICE), transfer(600), 5, Some(2)));
+
+		run_to_block(6);
+		assert_eq!(Balances::free_balance(BOB), 1_600);
+
+		// ALICE can't afford the second transfer
+		run_to_block(11);
+		assert_eq!(Balances::free_balance(BOB), 1_600);
+		System::assert_has_event(
+			Event::PaymentFailed {
+				id: 0,
+				error: pallet_balances::Error::<Test>::InsufficientBalance.into(),
+			}
+			.into(),
+		);
+		System::assert_has_event(Event::PaymentCompleted { id: 0 }.into());
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+	});
+}
+
+#[test]
+fn create_fails() {
+	new_test_ext().execute_with(|| {
+		let remark = Box::new(RuntimeCall::System(frame_system::Call::remark { remark: vec![] }));
+		assert_noop!(
+			Recurring::create(RuntimeOrigin::signed(ALICE), remark, 5, None),
+			Error::<Test>::CallNotAllowed
+		);
+		let fill_block =
+			Box::new(RuntimeCall::System(frame_system::Call::fill_block { ratio: Perbill::one() }));
+		assert_noop!(
+			Recurring::create(RuntimeOrigin::signed(ALICE), fill_block, 5, None),
+			Error::<Test>::CallTooHeavy
+		);
+		assert_noop!(
+			Recurring::create(RuntimeOrigin::signed(ALICE), transfer(10), 1, None),
+			Error::<Test>::PeriodTooShort
+		);
+		assert_noop!(
+			Recurring::create(RuntimeOrigin::signed(ALICE), transfer(10), 5, Some(0)),
+			Error::<Test>::ZeroCount
+		);
+
+		assert_ok!(Recurring::create(RuntimeOrigin::signed(ALICE), transfer(10), 5, None));
+		assert_ok!(Recurring::create(RuntimeOrigin::signed(ALICE), transfer(10), 5, None));
+		assert_noop!(
+			Recurring::create(RuntimeOrigin::signed(ALICE), transfer(10), 5, None),
+			Error::<Test>::TooManyPayments
+		);
+	});
+}
+
+#[test]
+fn payments_are_capped() {
+	new_test_ext().execute_with(|| {
+		for who in [ALICE, ALICE, BOB] {
+			assert_ok!(Recurring::create(RuntimeOrigin::signed(who), transfer(10), 5, None));
+		}
+		assert_noop!(
+			Recurring::create(RuntimeOrigin::signed(CHARLIE), transfer(10), 5, None),
+			Error::<Test>::MaxPaymentsReached
+		);
+
+		// cancelled and completed payments free their slot
+		assert_ok!(Recurring::cancel(RuntimeOrigin::signed(BOB), 2));
+		assert_ok!(Recurring::create(RuntimeOrigin::signed(CHARLIE), transfer(10), 5, Some(1)));
+		run_to_block(6);
+		System::assert_has_event(Event::PaymentCompleted { id: 3 }.into());
+		assert_ok!(Recurring::create(RuntimeOrigin::signed(CHARLIE), transfer(10), 5, None));
+	});
+}
+
+#[test]
+fn payment_ids_do_not_wrap() {
+	new_test_ext().execute_with(|| {
+		NextPaymentId::<Test>::put(u32::MAX);
+
+		assert_noop!(
+			Recurring::create(RuntimeOrigin::signed(ALICE), transfer(10), 5, None),
+			Error::<Test>::NoAvailablePaymentId
+		);
+	});
+}
+
+#[test]
+fn execute_is_only_dispatched_by_root() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Recurring::create(RuntimeOrigin::signed(ALICE), transfer(10), 5, None));
+
+		assert_noop!(Recurring::execute(RuntimeOrigin::signed(ALICE), 0), DispatchError::BadOrigin);
+	});
+}
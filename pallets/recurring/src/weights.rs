@@ -0,0 +1,101 @@
+//! Weights for `pallet_recurring`
+//!
+//! Estimated from the storage accesses of each call. The weight of the calls of payments is added
+//! to `execute` by the pallet.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+pub trait WeightInfo {
+	fn create() -> Weight;
+	fn cancel() -> Weight;
+	fn execute() -> Weight;
+}
+
+/// Weight functions for `pallet_recurring`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: crate::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Recurring PaymentCount (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Recurring NextPaymentId (r:1 w:1)
+	// Storage: Scheduler Lookup (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: Preimage PreimageFor (r:0 w:1)
+	// Storage: Recurring Payments (r:0 w:1)
+	// Storage: Recurring CounterForPayments (r:1 w:1)
+	fn create() -> Weight {
+		Weight::from_ref_time(60_000_000)
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().writes(9))
+	}
+	// Storage: Recurring Payments (r:1 w:1)
+	// Storage: Scheduler Lookup (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: Preimage PreimageFor (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Recurring PaymentCount (r:1 w:1)
+	// Storage: Recurring CounterForPayments (r:1 w:1)
+	fn cancel() -> Weight {
+		Weight::from_ref_time(50_000_000)
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().writes(8))
+	}
+	// Storage: Recurring Payments (r:1 w:1)
+	// Storage: Preimage PreimageFor (r:1 w:1)
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Recurring PaymentCount (r:1 w:1)
+	// Storage: Recurring CounterForPayments (r:1 w:1)
+	fn execute() -> Weight {
+		Weight::from_ref_time(30_000_000)
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(6))
+	}
+}
+
+impl WeightInfo for () {
+	// Storage: Recurring PaymentCount (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Recurring NextPaymentId (r:1 w:1)
+	// Storage: Scheduler Lookup (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: Preimage PreimageFor (r:0 w:1)
+	// Storage: Recurring Payments (r:0 w:1)
+	// Storage: Recurring CounterForPayments (r:1 w:1)
+	fn create() -> Weight {
+		Weight::from_ref_time(60_000_000)
+			.saturating_add(RocksDbWeight::get().reads(7))
+			.saturating_add(RocksDbWeight::get().writes(9))
+	}
+	// Storage: Recurring Payments (r:1 w:1)
+	// Storage: Scheduler Lookup (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: Preimage PreimageFor (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Recurring PaymentCount (r:1 w:1)
+	// Storage: Recurring CounterForPayments (r:1 w:1)
+	fn cancel() -> Weight {
+		Weight::from_ref_time(50_000_000)
+			.saturating_add(RocksDbWeight::get().reads(7))
+			.saturating_add(RocksDbWeight::get().writes(8))
+	}
+	// Storage: Recurring Payments (r:1 w:1)
+	// Storage: Preimage PreimageFor (r:1 w:1)
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Recurring PaymentCount (r:1 w:1)
+	// Storage: Recurring CounterForPayments (r:1 w:1)
+	fn execute() -> Weight {
+		Weight::from_ref_time(30_000_000)
+			.saturating_add(RocksDbWeight::get().reads(6))
+			.saturating_add(RocksDbWeight::get().writes(6))
+	}
+}
@@ -0,0 +1,186 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Spends the reserve assets held by the treasury, paying out over XCM.
+//!
+//! `pallet_treasury` only spends the native currency. The treasury account also collects
+//! reserve-backed assets registered in `pallet_asset_registry` (e.g. fees paid in xUSD), which
+//! this pallet lets governance send to beneficiaries on this chain, on the asset's reserve or on
+//! any sibling chain.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{pallet_prelude::*, weights::Weight};
+	use frame_system::pallet_prelude::*;
+	use sp_std::{boxed::Box, vec};
+	use xcm::{latest::prelude::*, VersionedMultiLocation};
+	use xcm_executor::traits::WeightBounds;
+	use xcm_primitives::AssetMultiLocationGetter;
+
+	type RuntimeCallOf<T> = <T as frame_system::Config>::RuntimeCall;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The origin that's allowed to spend the reserve assets of the treasury.
+		type SpendOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		type AssetId: Member + Parameter + MaxEncodedLen + Copy;
+		/// Converts between the local `AssetId` and the `MultiLocation` of reserve assets.
+		type AssetMultiLocationGetter: AssetMultiLocationGetter<Self::AssetId>;
+		/// The location of the treasury account, which the assets are withdrawn from.
+		type TreasuryLocation: Get<MultiLocation>;
+		/// This chain, as seen from its siblings.
+		type SelfLocation: Get<MultiLocation>;
+		/// Executes the payout messages.
+		type XcmExecutor: ExecuteXcm<RuntimeCallOf<Self>>;
+		/// Determines the weight of the payout messages.
+		type Weigher: WeightBounds<RuntimeCallOf<Self>>;
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		AssetSpent {
+			asset_id: T::AssetId,
+			amount: u128,
+			dest: MultiLocation,
+			beneficiary: MultiLocation,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The Asset ID is not registered
+		AssetIsNotRegistered,
+		/// The asset is not backed by the relay chain or a sibling parachain
+		NotReserveAsset,
+		/// The destination is not this chain, the asset's reserve nor a sibling parachain
+		UnsupportedDestination,
+		/// The version of the given `MultiLocation` is not supported
+		BadVersion,
+		/// The payout message could not be weighed, or weighs more than through the longest route
+		UnweighableMessage,
+		/// The payout message failed to execute
+		PayoutFailed,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Pay `amount` of the registered reserve asset `asset_id` from the treasury to
+		/// `beneficiary` on `dest`.
+		///
+		/// `dest` is either this chain (`Here`), the reserve of the asset or a sibling parachain,
+		/// and `beneficiary` is relative to it. Payouts on other chains go through the asset's
+		/// reserve, and pay their execution with up to half of `amount` on each hop.
+		#[pallet::call_index(0)]
+		#[pallet::weight(
+			<T as pallet::Config>::WeightInfo::spend()
+				.saturating_add(Pallet::<T>::max_payout_weight())
+		)]
+		pub fn spend(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			amount: u128,
+			dest: Box<VersionedMultiLocation>,
+			beneficiary: Box<VersionedMultiLocation>,
+		) -> DispatchResult {
+			T::SpendOrigin::ensure_origin(origin)?;
+
+			let dest: MultiLocation = (*dest).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let beneficiary: MultiLocation =
+				(*beneficiary).try_into().map_err(|()| Error::<T>::BadVersion)?;
+
+			let mut message =
+				Self::payout_message(asset_id, amount, dest.clone(), beneficiary.clone())?;
+			let weight =
+				T::Weigher::weight(&mut message).map_err(|()| Error::<T>::UnweighableMessage)?;
+			ensure!(
+				Weight::from_ref_time(weight).all_lte(Self::max_payout_weight()),
+				Error::<T>::UnweighableMessage
+			);
+
+			// the payout is paid by the extrinsic's weight
+			let outcome = T::XcmExecutor::execute_xcm_in_credit(
+				T::TreasuryLocation::get(),
+				message,
+				weight,
+				weight,
+			);
+			ensure!(matches!(outcome, Outcome::Complete(_)), Error::<T>::PayoutFailed);
+
+			Self::deposit_event(Event::AssetSpent { asset_id, amount, dest, beneficiary });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The weight of the payout message through the longest route, i.e. to a sibling which
+		/// is not the reserve of the asset. It only depends on the instructions of the message.
+		pub fn max_payout_weight() -> Weight {
+			xcm_primitives::reserve_transfer_to_sibling(
+				MultiLocation::new(1, X3(Parachain(1), PalletInstance(0), GeneralIndex(0))),
+				0,
+				MultiLocation::new(1, X1(Parachain(2))),
+				MultiLocation::here(),
+				T::SelfLocation::get(),
+			)
+			.and_then(|mut message: Xcm<RuntimeCallOf<T>>| T::Weigher::weight(&mut message).ok())
+			.map_or(Weight::zero(), Weight::from_ref_time)
+		}
+
+		/// The message withdrawing `amount` of `asset_id` from the treasury and depositing it to
+		/// `beneficiary` on `dest`.
+		pub fn payout_message(
+			asset_id: T::AssetId,
+			amount: u128,
+			dest: MultiLocation,
+			beneficiary: MultiLocation,
+		) -> Result<Xcm<RuntimeCallOf<T>>, Error<T>> {
+			let asset_location = T::AssetMultiLocationGetter::get_asset_multi_location(asset_id)
+				.ok_or(Error::<T>::AssetIsNotRegistered)?;
+			ensure!(
+				asset_location.parents == 1 &&
+					matches!(asset_location.interior.first(), None | Some(Parachain(_))),
+				Error::<T>::NotReserveAsset
+			);
+
+			if dest == MultiLocation::here() {
+				return Ok(Xcm(vec![
+					WithdrawAsset((asset_location, amount).into()),
+					DepositAsset { assets: Wild(All), max_assets: 1, beneficiary },
+				]));
+			}
+
+			// the relay chain only receives its own token
+			let is_sibling =
+				matches!(dest, MultiLocation { parents: 1, interior: X1(Parachain(_)) });
+			ensure!(
+				is_sibling || (dest == MultiLocation::parent() && asset_location == dest),
+				Error::<T>::UnsupportedDestination
+			);
+
+			xcm_primitives::reserve_transfer_to_sibling(
+				asset_location,
+				amount,
+				dest,
+				beneficiary,
+				T::SelfLocation::get(),
+			)
+			.ok_or(Error::<T>::NotReserveAsset)
+		}
+	}
+}
@@ -0,0 +1,124 @@
+use crate as pallet_treasury_xcm;
+use frame_support::traits::{ConstU16, ConstU64};
+use frame_system as system;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, ConstU32, IdentityLookup},
+};
+use xcm::latest::prelude::*;
+use xcm_builder::FixedWeightBounds;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub const XUSD_ASSET_ID: u32 = 1;
+pub const RELAY_ASSET_ID: u32 = 2;
+pub const LOCAL_ASSET_ID: u32 = 3;
+
+frame_support::parameter_types! {
+	pub TreasuryLocation: MultiLocation =
+		MultiLocation::new(0, X1(AccountId32 { network: Any, id: [7u8; 32] }));
+	pub SelfLocation: MultiLocation = MultiLocation::new(1, X1(Parachain(2000)));
+	pub static ExecutedMessages: Vec<(MultiLocation, Xcm<RuntimeCall>)> = vec![];
+	pub static PayoutOutcome: Option<XcmError> = None;
+}
+
+pub fn xusd_location() -> MultiLocation {
+	MultiLocation::new(1, X3(Parachain(1000), PalletInstance(50), GeneralIndex(1)))
+}
+
+pub struct AssetRegistry;
+impl xcm_primitives::AssetMultiLocationGetter<u32> for AssetRegistry {
+	fn get_asset_multi_location(asset_id: u32) -> Option<MultiLocation> {
+		match asset_id {
+			XUSD_ASSET_ID => Some(xusd_location()),
+			RELAY_ASSET_ID => Some(MultiLocation::parent()),
+			// assets of this chain are not reserve assets
+			LOCAL_ASSET_ID => Some(MultiLocation::new(0, X2(PalletInstance(42), GeneralIndex(3)))),
+			_ => None,
+		}
+	}
+
+	fn get_asset_id(_asset_multi_location: MultiLocation) -> Option<u32> {
+		None
+	}
+}
+
+/// Records the executed messages, completing or failing them as set in `PayoutOutcome`.
+pub struct RecordingXcmExecutor;
+impl ExecuteXcm<RuntimeCall> for RecordingXcmExecutor {
+	fn execute_xcm_in_credit(
+		origin: impl Into<MultiLocation>,
+		message: Xcm<RuntimeCall>,
+		weight_limit: u64,
+		_weight_credit: u64,
+	) -> Outcome {
+		ExecutedMessages::mutate(|messages| messages.push((origin.into(), message)));
+		match PayoutOutcome::get() {
+			None => Outcome::Complete(weight_limit),
+			Some(error) => Outcome::Incomplete(weight_limit, error),
+		}
+	}
+}
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system,
+		TreasuryXcm: pallet_treasury_xcm::{Pallet, Call, Event<T>},
+	}
+);
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_treasury_xcm::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type SpendOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type AssetId = u32;
+	type AssetMultiLocationGetter = AssetRegistry;
+	type TreasuryLocation = TreasuryLocation;
+	type SelfLocation = SelfLocation;
+	type XcmExecutor = RecordingXcmExecutor;
+	type Weigher = FixedWeightBounds<ConstU64<10>, RuntimeCall, ConstU32<100>>;
+	type WeightInfo = pallet_treasury_xcm::weights::SubstrateWeight<Test>;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let storage = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+	let mut ext: sp_io::TestExternalities = storage.into();
+	// events are not deposited on the genesis block
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
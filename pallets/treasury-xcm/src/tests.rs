@@ -0,0 +1,228 @@
+use crate::{mock::*, Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+use xcm::latest::prelude::*;
+
+fn spend(
+	asset_id: u32,
+	amount: u128,
+	dest: MultiLocation,
+	beneficiary: MultiLocation,
+) -> Result<(), DispatchError> {
+	TreasuryXcm::spend(
+		RuntimeOrigin::root(),
+		asset_id,
+		amount,
+		Box::new(dest.into()),
+		Box::new(beneficiary.into()),
+	)
+}
+
+fn bob() -> MultiLocation {
+	MultiLocation::new(0, X1(AccountId32 { network: Any, id: [2u8; 32] }))
+}
+
+fn reserve_xusd(amount: u128) -> MultiAsset {
+	(MultiLocation::new(0, X2(PalletInstance(50), GeneralIndex(1))), amount).into()
+}
+
+#[test]
+fn spend_to_local_beneficiary_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(spend(XUSD_ASSET_ID, 100, MultiLocation::here(), bob()));
+
+		assert_eq!(
+			ExecutedMessages::get(),
+			vec![(
+				TreasuryLocation::get(),
+				Xcm(vec![
+					WithdrawAsset((xusd_location(), 100).into()),
+					DepositAsset { assets: Wild(All), max_assets: 1, beneficiary: bob() },
+				])
+			)]
+		);
+		System::assert_last_event(
+			Event::AssetSpent {
+				asset_id: XUSD_ASSET_ID,
+				amount: 100,
+				dest: MultiLocation::here(),
+				beneficiary: bob(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn spend_to_reserve_works() {
+	new_test_ext().execute_with(|| {
+		let reserve = MultiLocation::new(1, X1(Parachain(1000)));
+		assert_ok!(spend(XUSD_ASSET_ID, 100, reserve.clone(), bob()));
+
+		assert_eq!(
+			ExecutedMessages::get(),
+			vec![(
+				TreasuryLocation::get(),
+				Xcm(vec![
+					WithdrawAsset((xusd_location(), 100).into()),
+					InitiateReserveWithdraw {
+						assets: Wild(All),
+						reserve,
+						xcm: Xcm(vec![
+							BuyExecution { fees: reserve_xusd(100), weight_limit: Unlimited },
+							DepositAsset { assets: Wild(All), max_assets: 1, beneficiary: bob() },
+						]),
+					},
+				])
+			)]
+		);
+	});
+}
+
+#[test]
+fn spend_to_sibling_goes_through_reserve() {
+	new_test_ext().execute_with(|| {
+		let sibling = MultiLocation::new(1, X1(Parachain(3000)));
+		assert_ok!(spend(XUSD_ASSET_ID, 100, sibling.clone(), bob()));
+
+		assert_eq!(
+			ExecutedMessages::get(),
+			vec![(
+				TreasuryLocation::get(),
+				Xcm(vec![
+					WithdrawAsset((xusd_location(), 100).into()),
+					InitiateReserveWithdraw {
+						assets: Wild(All),
+						reserve: MultiLocation::new(1, X1(Parachain(1000))),
+						xcm: Xcm(vec![
+							BuyExecution { fees: reserve_xusd(50), weight_limit: Unlimited },
+							DepositReserveAsset {
+								assets: Wild(All),
+								max_assets: 1,
+								dest: sibling,
+								xcm: Xcm(vec![
+									BuyExecution {
+										fees: (xusd_location(), 50).into(),
+										weight_limit: Unlimited,
+									},
+									DepositAsset {
+										assets: Wild(All),
+										max_assets: 1,
+										beneficiary: bob(),
+									},
+								]),
+							},
+						]),
+					},
+				])
+			)]
+		);
+	});
+}
+
+#[test]
+fn spend_relay_asset_to_relay_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(spend(RELAY_ASSET_ID, 100, MultiLocation::parent(), bob()));
+
+		assert_eq!(
+			ExecutedMessages::get(),
+			vec![(
+				TreasuryLocation::get(),
+				Xcm(vec![
+					WithdrawAsset((MultiLocation::parent(), 100).into()),
+					InitiateReserveWithdraw {
+						assets: Wild(All),
+						reserve: MultiLocation::parent(),
+						xcm: Xcm(vec![
+							BuyExecution {
+								fees: (MultiLocation::here(), 100).into(),
+								weight_limit: Unlimited,
+							},
+							DepositAsset { assets: Wild(All), max_assets: 1, beneficiary: bob() },
+						]),
+					},
+				])
+			)]
+		);
+	});
+}
+
+#[test]
+fn spend_fails_for_unsupported_assets_and_destinations() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			spend(42, 100, MultiLocation::here(), bob()),
+			Error::<Test>::AssetIsNotRegistered
+		);
+		assert_noop!(
+			spend(LOCAL_ASSET_ID, 100, MultiLocation::here(), bob()),
+			Error::<Test>::NotReserveAsset
+		);
+		// the relay chain doesn't see the asset as we do
+		assert_noop!(
+			spend(XUSD_ASSET_ID, 100, MultiLocation::parent(), bob()),
+			Error::<Test>::UnsupportedDestination
+		);
+		assert_noop!(
+			spend(XUSD_ASSET_ID, 100, MultiLocation::new(2, X1(Parachain(1000))), bob()),
+			Error::<Test>::UnsupportedDestination
+		);
+		assert!(ExecutedMessages::get().is_empty());
+	});
+}
+
+#[test]
+fn spend_fails_if_payout_fails() {
+	new_test_ext().execute_with(|| {
+		PayoutOutcome::set(Some(XcmError::FailedToTransactAsset("")));
+
+		assert_noop!(
+			spend(XUSD_ASSET_ID, 100, MultiLocation::here(), bob()),
+			Error::<Test>::PayoutFailed
+		);
+	});
+}
+
+#[test]
+fn spend_requires_spend_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TreasuryXcm::spend(
+				RuntimeOrigin::signed(1),
+				XUSD_ASSET_ID,
+				100,
+				Box::new(MultiLocation::here().into()),
+				Box::new(bob().into()),
+			),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn spend_weight_is_bounded_by_the_longest_route() {
+	use crate::{Config, WeightInfo};
+	use frame_support::{dispatch::GetDispatchInfo, weights::Weight};
+
+	new_test_ext().execute_with(|| {
+		let call = |asset_id, dest: MultiLocation| {
+			crate::Call::<Test>::spend {
+				asset_id,
+				amount: 100,
+				dest: Box::new(dest.into()),
+				beneficiary: Box::new(bob().into()),
+			}
+			.get_dispatch_info()
+			.weight
+		};
+		// six instructions to a sibling which is not the reserve of the asset
+		let expected =
+			<Test as Config>::WeightInfo::spend().saturating_add(Weight::from_ref_time(60));
+
+		assert_eq!(call(XUSD_ASSET_ID, MultiLocation::here()), expected);
+		assert_eq!(call(XUSD_ASSET_ID, MultiLocation::new(1, X1(Parachain(2000)))), expected);
+		// unregistered assets weigh the same, without reading the registry
+		assert_eq!(call(42, MultiLocation::parent()), expected);
+	});
+}
@@ -0,0 +1,34 @@
+
+//! Weights for `pallet_treasury_xcm`
+//!
+//! The weight of the payout message through its longest route is added by the pallet, as
+//! determined by its `Weigher`. These only account for the dispatch itself.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+pub trait WeightInfo {
+	fn spend() -> Weight;
+}
+
+/// Weight functions for `pallet_treasury_xcm`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: AssetRegistry AssetIdMultiLocation (r:1 w:0)
+	fn spend() -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+	}
+}
+
+impl WeightInfo for () {
+	// Storage: AssetRegistry AssetIdMultiLocation (r:1 w:0)
+	fn spend() -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+	}
+}
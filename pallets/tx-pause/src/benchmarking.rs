@@ -0,0 +1,55 @@
+//! Benchmarking setup for pallet-tx-pause
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as TxPause;
+use frame_benchmarking::benchmarks;
+use frame_support::assert_ok;
+use frame_system::RawOrigin;
+
+fn name<T: Config>(name: &[u8]) -> PalletNameOf<T> {
+	name.to_vec().try_into().unwrap()
+}
+
+benchmarks! {
+	pause {
+		let pallet_name = name::<T>(b"System");
+		let call_name = Some(name::<T>(b"remark"));
+	}: _(RawOrigin::Root, pallet_name.clone(), call_name.clone())
+	verify {
+		assert!(PausedCalls::<T>::contains_key((pallet_name, call_name)));
+	}
+
+	unpause {
+		let pallet_name = name::<T>(b"System");
+		let call_name = Some(name::<T>(b"remark"));
+		assert_ok!(TxPause::<T>::pause(
+			RawOrigin::Root.into(),
+			pallet_name.clone(),
+			call_name.clone()
+		));
+	}: _(RawOrigin::Root, pallet_name.clone(), call_name.clone())
+	verify {
+		assert!(!PausedCalls::<T>::contains_key((pallet_name, call_name)));
+	}
+
+	enter_safe_mode {
+		let duration = T::MaxSafeModeDuration::get();
+	}: _(RawOrigin::Root, duration)
+	verify {
+		assert!(TxPause::<T>::safe_mode_until().is_some());
+	}
+
+	exit_safe_mode {
+		assert_ok!(TxPause::<T>::enter_safe_mode(
+			RawOrigin::Root.into(),
+			T::MaxSafeModeDuration::get()
+		));
+	}: _(RawOrigin::Root)
+	verify {
+		assert!(TxPause::<T>::safe_mode_until().is_none());
+	}
+
+	impl_benchmark_test_suite!(TxPause, crate::mock::new_test_ext(), crate::mock::Test);
+}
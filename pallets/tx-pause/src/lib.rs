@@ -0,0 +1,233 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Pauses calls and puts the chain in safe mode without a runtime upgrade.
+//!
+//! Governance can pause whole pallets or single calls, identified by the names reported by
+//! `GetCallMetadata`, and enter a safe mode which only lets `SafeModeCalls` through until it
+//! expires or is exited. The pallet doesn't filter anything by itself: runtimes use [`Pallet`] as
+//! (part of) their `BaseCallFilter`, so the pauses also apply to calls nested in batches,
+//! multisigs, proxies or XCM `Transact`. Calls dispatched by `Root` bypass the filter.
+//!
+//! Calls which pallets make directly into other pallets are not dispatched, and are only paused
+//! if these pallets check them against a filter, e.g. the runtime's `BaseCallFilter`.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{CallMetadata, Contains, GetCallMetadata},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::Saturating;
+	use sp_std::prelude::*;
+
+	pub type PalletNameOf<T> = BoundedVec<u8, <T as Config>::MaxNameLen>;
+	pub type CallNameOf<T> = BoundedVec<u8, <T as Config>::MaxNameLen>;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The overarching call type, whose pallet and call names are matched against the pauses.
+		type RuntimeCall: Parameter + GetCallMetadata;
+		/// The origin that's allowed to pause and unpause calls, and to enter and exit safe mode.
+		type PauseOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Calls which can't be paused, e.g. the calls of governance and of this pallet.
+		type UnpausableCalls: Contains<<Self as Config>::RuntimeCall>;
+		/// The only calls allowed while in safe mode.
+		type SafeModeCalls: Contains<<Self as Config>::RuntimeCall>;
+		/// The longest safe mode which can be entered at once.
+		#[pallet::constant]
+		type MaxSafeModeDuration: Get<Self::BlockNumber>;
+		/// The maximum length of pallet and call names.
+		#[pallet::constant]
+		type MaxNameLen: Get<u32>;
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Paused pallets (without a call name) and calls.
+	#[pallet::storage]
+	pub type PausedCalls<T: Config> =
+		StorageMap<_, Blake2_128Concat, (PalletNameOf<T>, Option<CallNameOf<T>>), ()>;
+
+	/// The block at which the safe mode expires, if the chain is in safe mode.
+	#[pallet::storage]
+	#[pallet::getter(fn safe_mode_until)]
+	pub type SafeModeUntil<T: Config> = StorageValue<_, T::BlockNumber>;
+
+	/// Why the safe mode was exited.
+	#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum ExitReason {
+		/// The safe mode expired.
+		Expired,
+		/// The safe mode was exited by `PauseOrigin`.
+		Forced,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		CallPaused { pallet_name: PalletNameOf<T>, call_name: Option<CallNameOf<T>> },
+		CallUnpaused { pallet_name: PalletNameOf<T>, call_name: Option<CallNameOf<T>> },
+		SafeModeEntered { until: T::BlockNumber },
+		SafeModeExited { reason: ExitReason },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The pallet or call is already paused
+		AlreadyPaused,
+		/// The pallet or call is not paused
+		NotPaused,
+		/// The chain is already in safe mode
+		AlreadyInSafeMode,
+		/// The chain is not in safe mode
+		NotInSafeMode,
+		/// The safe mode duration is longer than `MaxSafeModeDuration`
+		SafeModeTooLong,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			match SafeModeUntil::<T>::get() {
+				Some(until) if until <= n => {
+					SafeModeUntil::<T>::kill();
+					Self::deposit_event(Event::SafeModeExited { reason: ExitReason::Expired });
+					T::DbWeight::get().reads_writes(1, 1)
+				},
+				_ => T::DbWeight::get().reads(1),
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Pause `call_name` of `pallet_name`, or every call of `pallet_name` if `call_name` is
+		/// `None`.
+		///
+		/// Calls in `UnpausableCalls` keep working even if their pallet is paused.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::pause())]
+		pub fn pause(
+			origin: OriginFor<T>,
+			pallet_name: PalletNameOf<T>,
+			call_name: Option<CallNameOf<T>>,
+		) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+
+			let key = (pallet_name, call_name);
+			ensure!(!PausedCalls::<T>::contains_key(&key), Error::<T>::AlreadyPaused);
+
+			PausedCalls::<T>::insert(&key, ());
+
+			let (pallet_name, call_name) = key;
+			Self::deposit_event(Event::CallPaused { pallet_name, call_name });
+			Ok(())
+		}
+
+		/// Unpause a pallet or call paused with [`Pallet::pause`].
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::unpause())]
+		pub fn unpause(
+			origin: OriginFor<T>,
+			pallet_name: PalletNameOf<T>,
+			call_name: Option<CallNameOf<T>>,
+		) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+
+			let key = (pallet_name, call_name);
+			ensure!(PausedCalls::<T>::contains_key(&key), Error::<T>::NotPaused);
+
+			PausedCalls::<T>::remove(&key);
+
+			let (pallet_name, call_name) = key;
+			Self::deposit_event(Event::CallUnpaused { pallet_name, call_name });
+			Ok(())
+		}
+
+		/// Only allow `SafeModeCalls` for the next `duration` blocks.
+		#[pallet::call_index(2)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::enter_safe_mode())]
+		pub fn enter_safe_mode(origin: OriginFor<T>, duration: T::BlockNumber) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+
+			ensure!(!SafeModeUntil::<T>::exists(), Error::<T>::AlreadyInSafeMode);
+			ensure!(duration <= T::MaxSafeModeDuration::get(), Error::<T>::SafeModeTooLong);
+
+			let until = frame_system::Pallet::<T>::block_number().saturating_add(duration);
+			SafeModeUntil::<T>::put(until);
+
+			Self::deposit_event(Event::SafeModeEntered { until });
+			Ok(())
+		}
+
+		/// Exit the safe mode before it expires.
+		#[pallet::call_index(3)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::exit_safe_mode())]
+		pub fn exit_safe_mode(origin: OriginFor<T>) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+
+			ensure!(SafeModeUntil::<T>::exists(), Error::<T>::NotInSafeMode);
+
+			SafeModeUntil::<T>::kill();
+
+			Self::deposit_event(Event::SafeModeExited { reason: ExitReason::Forced });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Whether the chain is in safe mode.
+		pub fn is_safe_mode() -> bool {
+			SafeModeUntil::<T>::get()
+				.map_or(false, |until| frame_system::Pallet::<T>::block_number() < until)
+		}
+
+		/// Whether the call, or its whole pallet, is paused.
+		pub fn is_paused(pallet_name: &[u8], call_name: &[u8]) -> bool {
+			let pallet_name = match PalletNameOf::<T>::try_from(pallet_name.to_vec()) {
+				Ok(pallet_name) => pallet_name,
+				// names too long to be paused
+				Err(_) => return false,
+			};
+			let call_name = CallNameOf::<T>::try_from(call_name.to_vec()).ok();
+
+			PausedCalls::<T>::contains_key((pallet_name.clone(), None::<CallNameOf<T>>)) ||
+				call_name.map_or(false, |call_name| {
+					PausedCalls::<T>::contains_key((pallet_name, Some(call_name)))
+				})
+		}
+	}
+
+	/// Rejects calls not in `SafeModeCalls` while in safe mode, and paused calls which are not in
+	/// `UnpausableCalls`.
+	impl<T: Config> Contains<<T as Config>::RuntimeCall> for Pallet<T> {
+		fn contains(call: &<T as Config>::RuntimeCall) -> bool {
+			if Self::is_safe_mode() && !T::SafeModeCalls::contains(call) {
+				return false;
+			}
+			if T::UnpausableCalls::contains(call) {
+				return true;
+			}
+
+			let CallMetadata { pallet_name, function_name } = call.get_call_metadata();
+			!Self::is_paused(pallet_name.as_bytes(), function_name.as_bytes())
+		}
+	}
+}
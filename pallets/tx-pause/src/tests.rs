@@ -0,0 +1,171 @@
+use crate::{mock::*, Error, Event, ExitReason};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Contains, Hooks},
+};
+use sp_runtime::{traits::Dispatchable, DispatchError};
+
+fn name(name: &str) -> crate::PalletNameOf<Test> {
+	name.as_bytes().to_vec().try_into().unwrap()
+}
+
+fn remark() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::remark { remark: vec![] })
+}
+
+fn remark_with_event() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::remark_with_event { remark: vec![] })
+}
+
+#[test]
+fn pause_call_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TxPause::pause(RuntimeOrigin::root(), name("System"), Some(name("remark"))));
+
+		assert!(!TxPause::contains(&remark()));
+		assert!(TxPause::contains(&remark_with_event()));
+		assert_noop!(
+			remark().dispatch(RuntimeOrigin::signed(1)),
+			frame_system::Error::<Test>::CallFiltered
+		);
+		System::assert_last_event(
+			Event::CallPaused { pallet_name: name("System"), call_name: Some(name("remark")) }
+				.into(),
+		);
+	});
+}
+
+#[test]
+fn pause_pallet_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TxPause::pause(RuntimeOrigin::root(), name("System"), None));
+
+		assert!(!TxPause::contains(&remark()));
+		assert!(!TxPause::contains(&remark_with_event()));
+		System::assert_last_event(
+			Event::CallPaused { pallet_name: name("System"), call_name: None }.into(),
+		);
+	});
+}
+
+#[test]
+fn unpausable_calls_cannot_be_paused() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TxPause::pause(RuntimeOrigin::root(), name("TxPause"), None));
+
+		assert!(TxPause::contains(&RuntimeCall::TxPause(crate::Call::exit_safe_mode {})));
+	});
+}
+
+#[test]
+fn pause_fails_if_already_paused() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TxPause::pause(RuntimeOrigin::root(), name("System"), None));
+
+		assert_noop!(
+			TxPause::pause(RuntimeOrigin::root(), name("System"), None),
+			Error::<Test>::AlreadyPaused
+		);
+	});
+}
+
+#[test]
+fn unpause_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TxPause::pause(RuntimeOrigin::root(), name("System"), Some(name("remark"))));
+
+		assert_ok!(TxPause::unpause(RuntimeOrigin::root(), name("System"), Some(name("remark"))));
+
+		assert!(TxPause::contains(&remark()));
+		System::assert_last_event(
+			Event::CallUnpaused { pallet_name: name("System"), call_name: Some(name("remark")) }
+				.into(),
+		);
+	});
+}
+
+#[test]
+fn unpause_fails_if_not_paused() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TxPause::pause(RuntimeOrigin::root(), name("System"), None));
+
+		// pauses of pallets and calls are independent
+		assert_noop!(
+			TxPause::unpause(RuntimeOrigin::root(), name("System"), Some(name("remark"))),
+			Error::<Test>::NotPaused
+		);
+	});
+}
+
+#[test]
+fn safe_mode_only_allows_safe_mode_calls() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TxPause::enter_safe_mode(RuntimeOrigin::root(), 10));
+
+		assert!(TxPause::is_safe_mode());
+		assert!(!TxPause::contains(&remark()));
+		assert!(TxPause::contains(&RuntimeCall::TxPause(crate::Call::exit_safe_mode {})));
+		System::assert_last_event(Event::SafeModeEntered { until: 11 }.into());
+	});
+}
+
+#[test]
+fn safe_mode_expires() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TxPause::enter_safe_mode(RuntimeOrigin::root(), 10));
+
+		System::set_block_number(10);
+		TxPause::on_initialize(10);
+		assert!(TxPause::is_safe_mode());
+
+		System::set_block_number(11);
+		TxPause::on_initialize(11);
+		assert!(!TxPause::is_safe_mode());
+		assert_eq!(TxPause::safe_mode_until(), None);
+		assert!(TxPause::contains(&remark()));
+		System::assert_last_event(Event::SafeModeExited { reason: ExitReason::Expired }.into());
+	});
+}
+
+#[test]
+fn exit_safe_mode_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TxPause::enter_safe_mode(RuntimeOrigin::root(), 10));
+
+		assert_ok!(TxPause::exit_safe_mode(RuntimeOrigin::root()));
+
+		assert!(!TxPause::is_safe_mode());
+		System::assert_last_event(Event::SafeModeExited { reason: ExitReason::Forced }.into());
+		assert_noop!(TxPause::exit_safe_mode(RuntimeOrigin::root()), Error::<Test>::NotInSafeMode);
+	});
+}
+
+#[test]
+fn enter_safe_mode_fails_if_invalid() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TxPause::enter_safe_mode(RuntimeOrigin::root(), 101),
+			Error::<Test>::SafeModeTooLong
+		);
+
+		assert_ok!(TxPause::enter_safe_mode(RuntimeOrigin::root(), 100));
+		assert_noop!(
+			TxPause::enter_safe_mode(RuntimeOrigin::root(), 10),
+			Error::<Test>::AlreadyInSafeMode
+		);
+	});
+}
+
+#[test]
+fn calls_require_pause_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TxPause::pause(RuntimeOrigin::signed(1), name("System"), None),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			TxPause::enter_safe_mode(RuntimeOrigin::signed(1), 10),
+			DispatchError::BadOrigin
+		);
+	});
+}
@@ -0,0 +1,90 @@
+
+//! Weights for `pallet_tx_pause`
+//!
+//! Estimated from the storage accesses of each extrinsic, to be replaced by the output of:
+
+// ./target/release/trappist-collator
+// benchmark
+// pallet
+// --chain
+// dev
+// --pallet
+// pallet_tx_pause
+// --extrinsic
+// *
+// --steps
+// 20
+// --repeat
+// 10
+// --output
+// pallets/tx-pause/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+pub trait WeightInfo {
+	fn pause() -> Weight;
+	fn unpause() -> Weight;
+	fn enter_safe_mode() -> Weight;
+	fn exit_safe_mode() -> Weight;
+}
+
+/// Weight functions for `pallet_tx_pause`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: TxPause PausedCalls (r:1 w:1)
+	fn pause() -> Weight {
+		Weight::from_ref_time(15_000_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: TxPause PausedCalls (r:1 w:1)
+	fn unpause() -> Weight {
+		Weight::from_ref_time(15_000_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: TxPause SafeModeUntil (r:1 w:1)
+	fn enter_safe_mode() -> Weight {
+		Weight::from_ref_time(12_000_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: TxPause SafeModeUntil (r:1 w:1)
+	fn exit_safe_mode() -> Weight {
+		Weight::from_ref_time(12_000_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+}
+
+impl WeightInfo for () {
+	// Storage: TxPause PausedCalls (r:1 w:1)
+	fn pause() -> Weight {
+		Weight::from_ref_time(15_000_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	// Storage: TxPause PausedCalls (r:1 w:1)
+	fn unpause() -> Weight {
+		Weight::from_ref_time(15_000_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	// Storage: TxPause SafeModeUntil (r:1 w:1)
+	fn enter_safe_mode() -> Weight {
+		Weight::from_ref_time(12_000_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	// Storage: TxPause SafeModeUntil (r:1 w:1)
+	fn exit_safe_mode() -> Weight {
+		Weight::from_ref_time(12_000_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+}
@@ -0,0 +1,53 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// A `pallet_chess` match waiting for its opponent to join it.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct OpenMatch<Hash, AccountId, AssetId, Balance> {
+	pub match_id: Hash,
+	/// The player who created the match, and plays white.
+	pub challenger: AccountId,
+	pub opponent: AccountId,
+	pub bet_asset_id: AssetId,
+	pub bet_amount: Balance,
+}
+
+sp_api::decl_runtime_apis! {
+	/// The state of the games of `pallet_chess`, so that clients don't have to decode its storage
+	/// or implement the rules of chess.
+	pub trait ChessApi<Hash, AccountId, MatchStyle, BlockNumber, AssetId, Balance> where
+		Hash: Codec,
+		AccountId: Codec,
+		MatchStyle: Codec,
+		BlockNumber: Codec,
+		AssetId: Codec,
+		Balance: Codec,
+	{
+		/// The board of the match `match_id`, in FEN.
+		fn board(match_id: Hash) -> Option<Vec<u8>>;
+
+		/// The player to move in the match `match_id`, if it is being played.
+		fn turn(match_id: Hash) -> Option<AccountId>;
+
+		/// The number of blocks left to the player to move in the match `match_id` before they
+		/// can be timed out, if it is being played.
+		fn clock(match_id: Hash) -> Option<BlockNumber>;
+
+		/// The moves the player to move in the match `match_id` can make, in the notation of
+		/// `make_move`. Empty if the match is not being played.
+		fn legal_moves(match_id: Hash) -> Vec<Vec<u8>>;
+
+		/// The matches of time control `style` waiting for their opponent, among the next `count`
+		/// matches after `start_after`, in storage order, and the last match read if more are
+		/// left to read from it.
+		fn open_matches(
+			style: MatchStyle,
+			start_after: Option<Hash>,
+			count: u32,
+		) -> (Vec<OpenMatch<Hash, AccountId, AssetId, Balance>>, Option<Hash>);
+	}
+}
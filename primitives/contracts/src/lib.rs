@@ -0,0 +1,51 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use pallet_contracts_primitives::{Code, ContractExecResult, ContractInstantiateResult};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+use sp_weights::Weight;
+
+/// The result of a contract dry-run, along with the events the contracts pallet deposited while
+/// running it.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct DryRunResult<R, Event> {
+	pub result: R,
+	pub events: Vec<Event>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Dry-runs contracts like `pallet_contracts::ContractsApi`, but lets the caller choose
+	/// whether the debug buffer is collected and returns the decoded events of the contracts
+	/// pallet.
+	pub trait ContractsDryRunApi<AccountId, Balance, Hash, Event> where
+		AccountId: Codec,
+		Balance: Codec,
+		Hash: Codec,
+		Event: Codec,
+	{
+		/// Performs a call to a contract.
+		fn call(
+			origin: AccountId,
+			dest: AccountId,
+			value: Balance,
+			gas_limit: Option<Weight>,
+			storage_deposit_limit: Option<Balance>,
+			input_data: Vec<u8>,
+			debug: bool,
+		) -> DryRunResult<ContractExecResult<Balance>, Event>;
+
+		/// Instantiates a new contract.
+		fn instantiate(
+			origin: AccountId,
+			value: Balance,
+			gas_limit: Option<Weight>,
+			storage_deposit_limit: Option<Balance>,
+			code: Code<Hash>,
+			data: Vec<u8>,
+			salt: Vec<u8>,
+			debug: bool,
+		) -> DryRunResult<ContractInstantiateResult<AccountId, Balance>, Event>;
+	}
+}
@@ -0,0 +1,41 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// A `pallet_dex` exchange, pairing the native currency with an asset.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct PoolInfo<AssetId, Balance, AssetBalance> {
+	pub asset_id: AssetId,
+	/// The asset minted to liquidity providers.
+	pub liquidity_token_id: AssetId,
+	pub currency_reserve: Balance,
+	pub token_reserve: AssetBalance,
+	/// The total issuance of `liquidity_token_id`.
+	pub liquidity_supply: AssetBalance,
+}
+
+/// The share of the input of swaps kept by the exchange for its liquidity providers:
+/// `numerator / denominator`.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ProviderFee<Balance> {
+	pub numerator: Balance,
+	pub denominator: Balance,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Lists the exchanges of `pallet_dex`, which `DexApi` can only quote one at a time.
+	pub trait DexPoolsApi<AssetId, Balance, AssetBalance> where
+		AssetId: Codec,
+		Balance: Codec,
+		AssetBalance: Codec,
+	{
+		/// Every exchange, by ascending asset ID.
+		fn pools() -> Vec<PoolInfo<AssetId, Balance, AssetBalance>>;
+
+		/// The fee of every exchange.
+		fn provider_fee() -> ProviderFee<Balance>;
+	}
+}
@@ -0,0 +1,201 @@
+use codec::Encode;
+use frame_support::{
+	dispatch::DispatchError,
+	traits::{Contains, EnsureOrigin, Get},
+	weights::Weight,
+};
+use pallet_contracts::chain_extension::{
+	BufInBufOutState, ChainExtension, Environment, Ext, InitState, RegisteredChainExtension, RetVal,
+};
+use sp_std::{boxed::Box, marker::PhantomData};
+use xcm::{
+	latest::{ExecuteXcm, Outcome, Xcm},
+	VersionedMultiLocation, VersionedXcm,
+};
+use xcm_executor::traits::WeightBounds;
+
+type RuntimeCallOf<T> = <T as frame_system::Config>::RuntimeCall;
+
+/// Weight charged for sending a message, matching `pallet_xcm::send`.
+const SEND_WEIGHT: u64 = 100_000_000;
+
+/// Status codes returned to contracts by [`XcmExtension`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u32)]
+pub enum XcmExtensionError {
+	Success = 0,
+	/// The message could not be converted to the latest version, or it exceeds the runtime's
+	/// `MaxInstructions`.
+	InvalidMessage = 1,
+	/// The weight of the message exceeds the given maximum.
+	WeightLimitExceeded = 2,
+	/// The message was rejected by `pallet_xcm`, e.g. by its origin or execution filters, or by
+	/// the runtime's `BaseCallFilter`. The output buffer holds the SCALE encoded `DispatchError`.
+	Rejected = 3,
+	/// The message was executed only partially. The output buffer holds the SCALE encoded
+	/// `XcmError`.
+	Incomplete = 4,
+}
+
+impl From<XcmExtensionError> for RetVal {
+	fn from(error: XcmExtensionError) -> Self {
+		RetVal::Converging(error as u32)
+	}
+}
+
+/// Functions of [`XcmExtension`], identified by the lower 16 bits of the id passed to
+/// `seal_call_chain_extension`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u16)]
+pub enum XcmFunc {
+	/// `(VersionedMultiLocation, VersionedXcm<()>)`: sends the message to the given destination.
+	Send = 0,
+	/// `(VersionedXcm<RuntimeCall>, u64)`: executes the message locally, within the given
+	/// maximum weight.
+	Execute = 1,
+}
+
+impl TryFrom<u16> for XcmFunc {
+	type Error = DispatchError;
+
+	fn try_from(func_id: u16) -> Result<Self, Self::Error> {
+		match func_id {
+			0 => Ok(Self::Send),
+			1 => Ok(Self::Execute),
+			_ => Err(DispatchError::Other("Unimplemented func_id")),
+		}
+	}
+}
+
+/// Lets ink! contracts send and execute XCM messages through `pallet_xcm`.
+///
+/// Messages originate from the calling contract, converted to a location by the runtime's
+/// `SendXcmOrigin`/`ExecuteXcmOrigin` (i.e. `LocalOriginToLocation`). Their weight is
+/// determined by the runtime's `Weigher`, which also bounds the number of instructions.
+///
+/// Messages are only sent or executed if the runtime's `BaseCallFilter` allows the equivalent
+/// `pallet_xcm` call, e.g. while `send` or `execute` is not paused.
+pub struct XcmExtension<T>(PhantomData<T>);
+
+impl<T> Default for XcmExtension<T> {
+	fn default() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T: pallet_contracts::Config + pallet_xcm::Config> RegisteredChainExtension<T>
+	for XcmExtension<T>
+{
+	const ID: u16 = 3;
+}
+
+impl<T: pallet_contracts::Config + pallet_xcm::Config> ChainExtension<T> for XcmExtension<T>
+where
+	RuntimeCallOf<T>: From<pallet_xcm::Call<T>>,
+{
+	fn call<E: Ext<T = T>>(
+		&mut self,
+		env: Environment<E, InitState>,
+	) -> Result<RetVal, DispatchError> {
+		let mut env = env.buf_in_buf_out();
+		let origin: <T as frame_system::Config>::RuntimeOrigin =
+			frame_system::RawOrigin::Signed(env.ext().address().clone()).into();
+
+		// messages are unbounded, so reading them is charged per byte before they are decoded
+		let len = env.in_len();
+		env.charge_weight(
+			T::Schedule::get().host_fn_weights.input_per_byte.saturating_mul(len.into()),
+		)?;
+
+		match XcmFunc::try_from(env.func_id())? {
+			XcmFunc::Send => {
+				env.charge_weight(Weight::from_ref_time(SEND_WEIGHT))?;
+				let (dest, message): (VersionedMultiLocation, VersionedXcm<()>) =
+					env.read_as_unbounded(len)?;
+
+				// the weigher bounds the number of instructions, even though the message is not
+				// executed locally
+				let mut local_message = match Xcm::<()>::try_from(message.clone()) {
+					Ok(message) => Xcm::<RuntimeCallOf<T>>::from(message),
+					Err(()) => return Ok(XcmExtensionError::InvalidMessage.into()),
+				};
+				if T::Weigher::weight(&mut local_message).is_err() {
+					return Ok(XcmExtensionError::InvalidMessage.into());
+				}
+
+				let (dest, message) = (Box::new(dest), Box::new(message));
+				if is_filtered::<T>(pallet_xcm::Call::send {
+					dest: dest.clone(),
+					message: message.clone(),
+				}) {
+					return rejected(&mut env, frame_system::Error::<T>::CallFiltered.into());
+				}
+
+				match pallet_xcm::Pallet::<T>::send(origin, dest, message) {
+					Ok(()) => Ok(XcmExtensionError::Success.into()),
+					Err(e) => rejected(&mut env, e),
+				}
+			},
+			XcmFunc::Execute => {
+				let (message, max_weight): (VersionedXcm<RuntimeCallOf<T>>, u64) =
+					env.read_as_unbounded(len)?;
+
+				let mut message: Xcm<RuntimeCallOf<T>> = match message.try_into() {
+					Ok(message) => message,
+					Err(()) => return Ok(XcmExtensionError::InvalidMessage.into()),
+				};
+				let weight = match T::Weigher::weight(&mut message) {
+					Ok(weight) => weight,
+					Err(()) => return Ok(XcmExtensionError::InvalidMessage.into()),
+				};
+				if weight > max_weight {
+					return Ok(XcmExtensionError::WeightLimitExceeded.into());
+				}
+				let charged = env.charge_weight(Weight::from_ref_time(weight))?;
+
+				if is_filtered::<T>(pallet_xcm::Call::execute {
+					message: Box::new(VersionedXcm::from(message.clone())),
+					max_weight: Weight::from_ref_time(max_weight),
+				}) {
+					return rejected(&mut env, frame_system::Error::<T>::CallFiltered.into());
+				}
+
+				let origin_location = match T::ExecuteXcmOrigin::try_origin(origin) {
+					Ok(location) => location,
+					Err(_) => return rejected(&mut env, DispatchError::BadOrigin),
+				};
+				if !T::XcmExecuteFilter::contains(&(origin_location.clone(), message.clone())) {
+					return rejected(&mut env, pallet_xcm::Error::<T>::Filtered.into());
+				}
+
+				let outcome =
+					T::XcmExecutor::execute_xcm_in_credit(origin_location, message, weight, weight);
+				env.adjust_weight(charged, Weight::from_ref_time(outcome.weight_used()));
+
+				match outcome {
+					Outcome::Complete(_) => Ok(XcmExtensionError::Success.into()),
+					Outcome::Incomplete(_, error) | Outcome::Error(error) => {
+						env.write(&error.encode(), false, None)?;
+						Ok(XcmExtensionError::Incomplete.into())
+					},
+				}
+			},
+		}
+	}
+}
+
+/// Whether the runtime's `BaseCallFilter` rejects `call`.
+fn is_filtered<T: pallet_xcm::Config>(call: pallet_xcm::Call<T>) -> bool
+where
+	RuntimeCallOf<T>: From<pallet_xcm::Call<T>>,
+{
+	!<T as frame_system::Config>::BaseCallFilter::contains(&call.into())
+}
+
+fn rejected<E: Ext>(
+	env: &mut Environment<E, BufInBufOutState>,
+	error: DispatchError,
+) -> Result<RetVal, DispatchError> {
+	env.write(&error.encode(), false, None)?;
+	Ok(XcmExtensionError::Rejected.into())
+}
@@ -1,18 +1,34 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use codec::Encode;
 use frame_support::{
+	sp_io::hashing::blake2_256,
 	sp_runtime::SaturatedConversion,
-	traits::{fungibles::Inspect, Currency},
+	traits::{fungibles::Inspect, nonfungibles, Contains, Currency, Get},
 };
-use sp_std::{borrow::Borrow, marker::PhantomData, vec::Vec};
+use sp_std::{borrow::Borrow, marker::PhantomData, vec, vec::Vec};
 use xcm::latest::{
-	AssetId::Concrete, Fungibility::Fungible, Junctions::Here, MultiAsset, MultiLocation,
+	AssetId::Concrete,
+	AssetInstance, Error as XcmError,
+	Fungibility::{Fungible, NonFungible},
+	Instruction::{self, ClearOrigin, DepositAsset, ReserveAssetDeposited, WithdrawAsset},
+	Junction::{AccountId32, GeneralIndex, Parachain},
+	Junctions::{Here, X1, X2},
+	MultiAsset, MultiAssets, MultiLocation, NetworkId, Result as XcmResult, Xcm,
 };
 use xcm_executor::{
-	traits::{Convert, DropAssets, Error as MatchError, MatchesFungibles},
+	traits::{
+		Convert, DropAssets, Error as MatchError, FilterAssetLocation, MatchesFungibles,
+		MatchesNonFungibles, ShouldExecute, TransactAsset,
+	},
 	Assets,
 };
 
+#[cfg(feature = "chain-extension")]
+mod chain_extension;
+#[cfg(feature = "chain-extension")]
+pub use chain_extension::{XcmExtension, XcmExtensionError, XcmFunc};
+
 pub struct AsAssetMultiLocation<AssetId, AssetIdInfoGetter>(
 	PhantomData<(AssetId, AssetIdInfoGetter)>,
 );
@@ -59,6 +75,316 @@ impl<
 	}
 }
 
+pub struct AsCollectionMultiLocation<CollectionId, CollectionIdInfoGetter>(
+	PhantomData<(CollectionId, CollectionIdInfoGetter)>,
+);
+impl<CollectionId, CollectionIdInfoGetter>
+	xcm_executor::traits::Convert<MultiLocation, CollectionId>
+	for AsCollectionMultiLocation<CollectionId, CollectionIdInfoGetter>
+where
+	CollectionId: Clone,
+	CollectionIdInfoGetter: CollectionMultiLocationGetter<CollectionId>,
+{
+	fn convert_ref(
+		collection_multi_location: impl Borrow<MultiLocation>,
+	) -> Result<CollectionId, ()> {
+		CollectionIdInfoGetter::get_collection_id(collection_multi_location.borrow().clone())
+			.ok_or(())
+	}
+
+	fn reverse_ref(collection_id: impl Borrow<CollectionId>) -> Result<MultiLocation, ()> {
+		CollectionIdInfoGetter::get_collection_multi_location(collection_id.borrow().clone())
+			.ok_or(())
+	}
+}
+
+pub trait CollectionMultiLocationGetter<CollectionId> {
+	fn get_collection_multi_location(collection_id: CollectionId) -> Option<MultiLocation>;
+	fn get_collection_id(collection_multi_location: MultiLocation) -> Option<CollectionId>;
+}
+
+/// Accepts teleports of concrete assets from the origins `TrustedTeleporters` trusts for them.
+pub struct IsTrustedTeleporter<TrustedTeleporters>(PhantomData<TrustedTeleporters>);
+impl<TrustedTeleporters: TrustedTeleporterGetter> FilterAssetLocation
+	for IsTrustedTeleporter<TrustedTeleporters>
+{
+	fn filter_asset_location(asset: &MultiAsset, origin: &MultiLocation) -> bool {
+		match asset.id {
+			Concrete(ref asset_multi_location) =>
+				TrustedTeleporters::is_trusted_teleporter(asset_multi_location, origin),
+			_ => false,
+		}
+	}
+}
+
+/// Allows teleporting concrete assets to the destinations `TrustedTeleporters` trusts to teleport
+/// them back, e.g. as the `XcmTeleportFilter` of `pallet_xcm`.
+pub struct TrustedTeleportDestination<TrustedTeleporters>(PhantomData<TrustedTeleporters>);
+impl<TrustedTeleporters: TrustedTeleporterGetter> Contains<(MultiLocation, Vec<MultiAsset>)>
+	for TrustedTeleportDestination<TrustedTeleporters>
+{
+	fn contains((dest, assets): &(MultiLocation, Vec<MultiAsset>)) -> bool {
+		assets
+			.iter()
+			.all(|asset| IsTrustedTeleporter::<TrustedTeleporters>::filter_asset_location(asset, dest))
+	}
+}
+
+pub trait TrustedTeleporterGetter {
+	fn is_trusted_teleporter(
+		asset_multi_location: &MultiLocation,
+		teleporter: &MultiLocation,
+	) -> bool;
+}
+
+/// Converts an `AssetInstance::Index` into a local item id.
+pub struct AsInstanceIndex<InstanceId>(PhantomData<InstanceId>);
+impl<InstanceId> xcm_executor::traits::Convert<AssetInstance, InstanceId>
+	for AsInstanceIndex<InstanceId>
+where
+	InstanceId: Clone + TryFrom<u128> + Into<u128>,
+{
+	fn convert_ref(instance: impl Borrow<AssetInstance>) -> Result<InstanceId, ()> {
+		match instance.borrow() {
+			AssetInstance::Index(index) => InstanceId::try_from(*index).map_err(|_| ()),
+			_ => Err(()),
+		}
+	}
+
+	fn reverse_ref(instance_id: impl Borrow<InstanceId>) -> Result<AssetInstance, ()> {
+		Ok(AssetInstance::Index(instance_id.borrow().clone().into()))
+	}
+}
+
+pub struct ConvertedRegisteredClassId<ClassId, InstanceId, ConvertClassId, ConvertInstanceId>(
+	PhantomData<(ClassId, InstanceId, ConvertClassId, ConvertInstanceId)>,
+);
+impl<
+		ClassId: Clone,
+		InstanceId: Clone,
+		ConvertClassId: Convert<MultiLocation, ClassId>,
+		ConvertInstanceId: Convert<AssetInstance, InstanceId>,
+	> MatchesNonFungibles<ClassId, InstanceId>
+	for ConvertedRegisteredClassId<ClassId, InstanceId, ConvertClassId, ConvertInstanceId>
+{
+	fn matches_nonfungibles(a: &MultiAsset) -> Result<(ClassId, InstanceId), MatchError> {
+		let (instance, class) = match (&a.fun, &a.id) {
+			(NonFungible(ref instance), Concrete(ref class)) => (instance, class),
+			_ => return Err(MatchError::AssetNotFound),
+		};
+		let what = ConvertClassId::convert_ref(class).map_err(|_| MatchError::AssetNotFound)?;
+		let instance = ConvertInstanceId::convert_ref(instance)
+			.map_err(|_| MatchError::InstanceConversionFailed)?;
+		Ok((what, instance))
+	}
+}
+
+/// Transacts non-fungible items without ever burning them.
+///
+/// Withdrawn items are moved into `HoldingAccount` and deposits move them back out of it. Items
+/// which are not held yet (i.e. the first arrival of a reserve-backed item) are minted. Unlike
+/// `xcm_builder::NonFungiblesAdapter`, direct transfers require `from` to own the item.
+pub struct NonFungiblesHoldingAdapter<
+	NonFungibles,
+	Matcher,
+	AccountIdConverter,
+	AccountId,
+	HoldingAccount,
+>(PhantomData<(NonFungibles, Matcher, AccountIdConverter, AccountId, HoldingAccount)>);
+impl<
+		NonFungibles: nonfungibles::Mutate<AccountId> + nonfungibles::Transfer<AccountId>,
+		Matcher: MatchesNonFungibles<NonFungibles::CollectionId, NonFungibles::ItemId>,
+		AccountIdConverter: Convert<MultiLocation, AccountId>,
+		AccountId: Clone + Eq,
+		HoldingAccount: Get<AccountId>,
+	>
+	NonFungiblesHoldingAdapter<NonFungibles, Matcher, AccountIdConverter, AccountId, HoldingAccount>
+{
+	fn move_item(
+		collection: &NonFungibles::CollectionId,
+		item: &NonFungibles::ItemId,
+		from: &AccountId,
+		to: &AccountId,
+	) -> XcmResult {
+		let owner = NonFungibles::owner(collection, item).ok_or(MatchError::AssetNotFound)?;
+		if &owner != from {
+			return Err(XcmError::FailedToTransactAsset("ItemNotOwned"))
+		}
+		NonFungibles::transfer(collection, item, to)
+			.map_err(|e| XcmError::FailedToTransactAsset(e.into()))
+	}
+}
+impl<
+		NonFungibles: nonfungibles::Mutate<AccountId> + nonfungibles::Transfer<AccountId>,
+		Matcher: MatchesNonFungibles<NonFungibles::CollectionId, NonFungibles::ItemId>,
+		AccountIdConverter: Convert<MultiLocation, AccountId>,
+		AccountId: Clone + Eq,
+		HoldingAccount: Get<AccountId>,
+	> TransactAsset
+	for NonFungiblesHoldingAdapter<
+		NonFungibles,
+		Matcher,
+		AccountIdConverter,
+		AccountId,
+		HoldingAccount,
+	>
+{
+	fn deposit_asset(what: &MultiAsset, who: &MultiLocation) -> XcmResult {
+		let (collection, item) = Matcher::matches_nonfungibles(what)?;
+		let who = AccountIdConverter::convert_ref(who)
+			.map_err(|()| MatchError::AccountIdConversionFailed)?;
+		match NonFungibles::owner(&collection, &item) {
+			Some(_) => Self::move_item(&collection, &item, &HoldingAccount::get(), &who),
+			None => NonFungibles::mint_into(&collection, &item, &who)
+				.map_err(|e| XcmError::FailedToTransactAsset(e.into())),
+		}
+	}
+
+	fn withdraw_asset(what: &MultiAsset, who: &MultiLocation) -> Result<Assets, XcmError> {
+		let (collection, item) = Matcher::matches_nonfungibles(what)?;
+		let who = AccountIdConverter::convert_ref(who)
+			.map_err(|()| MatchError::AccountIdConversionFailed)?;
+		Self::move_item(&collection, &item, &who, &HoldingAccount::get())?;
+		Ok(what.clone().into())
+	}
+
+	fn transfer_asset(
+		what: &MultiAsset,
+		from: &MultiLocation,
+		to: &MultiLocation,
+	) -> Result<Assets, XcmError> {
+		let (collection, item) = Matcher::matches_nonfungibles(what)?;
+		let from = AccountIdConverter::convert_ref(from)
+			.map_err(|()| MatchError::AccountIdConversionFailed)?;
+		let to = AccountIdConverter::convert_ref(to)
+			.map_err(|()| MatchError::AccountIdConversionFailed)?;
+		Self::move_item(&collection, &item, &from, &to)?;
+		Ok(what.clone().into())
+	}
+}
+
+/// Allows unpaid execution of the messages of `T` which only send back items of the collections
+/// under `CollectionsPrefix`, e.g. the items of this chain returning from the chains they were
+/// reserve-transferred to, which hold no asset to pay with.
+///
+/// The message must be `WithdrawAsset` of the items, `ClearOrigin`, and `DepositAsset`s, which
+/// messages sent by accounts of `T` can't be, as their origin is descended first.
+pub struct AllowUnpaidNonFungiblesReturnFrom<T, CollectionsPrefix>(
+	PhantomData<(T, CollectionsPrefix)>,
+);
+impl<T: Contains<MultiLocation>, CollectionsPrefix: Get<MultiLocation>> ShouldExecute
+	for AllowUnpaidNonFungiblesReturnFrom<T, CollectionsPrefix>
+{
+	fn should_execute<RuntimeCall>(
+		origin: &MultiLocation,
+		message: &mut Xcm<RuntimeCall>,
+		_max_weight: u64,
+		_weight_credit: &mut u64,
+	) -> Result<(), ()> {
+		if !T::contains(origin) {
+			return Err(())
+		}
+		match message.0.as_slice() {
+			[WithdrawAsset(assets), ClearOrigin, deposits @ ..]
+				if are_items_deposited(assets, deposits, &CollectionsPrefix::get()) =>
+				Ok(()),
+			_ => Err(()),
+		}
+	}
+}
+
+/// Allows unpaid execution of the messages of `T` which only deposit derivatives of the items of
+/// its collections under `CollectionsPrefix`, e.g. the items `T` reserve-transfers to this chain,
+/// which can't pay for execution.
+///
+/// The message must be `ReserveAssetDeposited` of the items, `ClearOrigin`, and `DepositAsset`s,
+/// as sent by `DepositReserveAsset`.
+pub struct AllowUnpaidNonFungiblesDepositFrom<T, CollectionsPrefix>(
+	PhantomData<(T, CollectionsPrefix)>,
+);
+impl<T: Contains<MultiLocation>, CollectionsPrefix: Get<MultiLocation>> ShouldExecute
+	for AllowUnpaidNonFungiblesDepositFrom<T, CollectionsPrefix>
+{
+	fn should_execute<RuntimeCall>(
+		origin: &MultiLocation,
+		message: &mut Xcm<RuntimeCall>,
+		_max_weight: u64,
+		_weight_credit: &mut u64,
+	) -> Result<(), ()> {
+		if !T::contains(origin) {
+			return Err(())
+		}
+		match message.0.as_slice() {
+			[ReserveAssetDeposited(assets), ClearOrigin, deposits @ ..]
+				if are_items_deposited(assets, deposits, &CollectionsPrefix::get()) =>
+				Ok(()),
+			_ => Err(()),
+		}
+	}
+}
+
+/// Whether `assets` are only items of the collections under `prefix`, and `deposits` only
+/// `DepositAsset`s.
+fn are_items_deposited<RuntimeCall>(
+	assets: &MultiAssets,
+	deposits: &[Instruction<RuntimeCall>],
+	prefix: &MultiLocation,
+) -> bool {
+	let is_item = |asset: &MultiAsset| match asset {
+		MultiAsset { id: Concrete(location), fun: NonFungible(_) } =>
+			matches!(location.match_and_split(prefix), Some(GeneralIndex(_))),
+		_ => false,
+	};
+	!assets.is_none() &&
+		assets.inner().iter().all(is_item) &&
+		deposits.iter().all(|instruction| matches!(instruction, DepositAsset { .. }))
+}
+
+/// Converts the `AccountId32` locations of the relay chain (`(1, X1(AccountId32 { .. }))`), e.g.
+/// the origin of its `send` calls, into a local account.
+///
+/// The account is derived from the hash of the location (as `xcm_builder::Account32Hash` does),
+/// so it never collides with the account of the same key on this chain.
+pub struct HashedRelayAccountId32<AccountId>(PhantomData<AccountId>);
+impl<AccountId: From<[u8; 32]> + Clone> Convert<MultiLocation, AccountId>
+	for HashedRelayAccountId32<AccountId>
+{
+	fn convert_ref(location: impl Borrow<MultiLocation>) -> Result<AccountId, ()> {
+		match location.borrow() {
+			location @ MultiLocation { parents: 1, interior: X1(AccountId32 { .. }) } =>
+				Ok(("multiloc", location).using_encoded(blake2_256).into()),
+			_ => Err(()),
+		}
+	}
+}
+
+/// Converts the `AccountId32` locations of sibling parachains
+/// (`(1, X2(Parachain(_), AccountId32 { .. }))`), e.g. the origin of `send` calls signed by their
+/// accounts, into a local account.
+///
+/// As for [`HashedRelayAccountId32`], the account is derived from the hash of the location. The
+/// network of the `AccountId32` is ignored, as siblings don't agree on it.
+pub struct HashedSiblingAccountId32<AccountId>(PhantomData<AccountId>);
+impl<AccountId: From<[u8; 32]> + Clone> Convert<MultiLocation, AccountId>
+	for HashedSiblingAccountId32<AccountId>
+{
+	fn convert_ref(location: impl Borrow<MultiLocation>) -> Result<AccountId, ()> {
+		match location.borrow() {
+			MultiLocation {
+				parents: 1,
+				interior: X2(Parachain(para_id), AccountId32 { id, .. }),
+			} => {
+				let location = MultiLocation::new(
+					1,
+					X2(Parachain(*para_id), AccountId32 { network: NetworkId::Any, id: *id }),
+				);
+				Ok(("multiloc", location).using_encoded(blake2_256).into())
+			},
+			_ => Err(()),
+		}
+	}
+}
+
 pub struct TrappistDropAssets<
 	AssetId,
 	AssetIdInfoGetter,
@@ -124,3 +450,79 @@ impl<AssetId, AssetIdInfoGetter, AssetsPallet, BalancesPallet, XcmPallet, Accoun
 		weight
 	}
 }
+
+/// The message withdrawing `amount` of the asset at `asset_location` from the origin and
+/// transferring it to `beneficiary` on the sibling `dest`, paying the fees of each hop with the
+/// asset.
+///
+/// Assets of this chain are kept in the sovereign account of `dest`, backing the ones deposited on
+/// `dest`. Other assets are withdrawn from their reserve, which deposits them on `dest` itself or
+/// forwards them to it. `self_location` is this chain, as seen from its siblings.
+///
+/// `None` if the reserve of the asset is neither this chain, the relay chain nor a sibling.
+pub fn reserve_transfer_to_sibling<Call>(
+	asset_location: MultiLocation,
+	amount: u128,
+	dest: MultiLocation,
+	beneficiary: MultiLocation,
+	self_location: MultiLocation,
+) -> Option<xcm::latest::Xcm<Call>> {
+	use xcm::latest::prelude::*;
+
+	let assets: MultiAssets = (asset_location.clone(), amount).into();
+	let deposit = DepositAsset { assets: Wild(All), max_assets: 1, beneficiary };
+
+	if asset_location.parents == 0 {
+		let mut fees = self_location;
+		for junction in asset_location.interior.iter() {
+			fees.push_interior(junction.clone()).ok()?;
+		}
+		return Some(Xcm(vec![
+			WithdrawAsset(assets),
+			DepositReserveAsset {
+				assets: Wild(All),
+				max_assets: 1,
+				dest,
+				xcm: Xcm(vec![
+					BuyExecution { fees: (fees, amount).into(), weight_limit: Unlimited },
+					deposit,
+				]),
+			},
+		]))
+	}
+
+	// the reserve of the asset, and the asset as seen from it
+	let mut reserve_asset_location = asset_location.clone();
+	let reserve = match (asset_location.parents, reserve_asset_location.interior.take_first()) {
+		(1, Some(Parachain(para_id))) => MultiLocation::new(1, X1(Parachain(para_id))),
+		(1, None) => MultiLocation::parent(),
+		_ => return None,
+	};
+	reserve_asset_location.parents = 0;
+
+	let xcm = if dest == reserve {
+		Xcm(vec![
+			BuyExecution { fees: (reserve_asset_location, amount).into(), weight_limit: Unlimited },
+			deposit,
+		])
+	} else {
+		// siblings see the asset as we do
+		let half = amount / 2;
+		Xcm(vec![
+			BuyExecution { fees: (reserve_asset_location, half).into(), weight_limit: Unlimited },
+			DepositReserveAsset {
+				assets: Wild(All),
+				max_assets: 1,
+				dest,
+				xcm: Xcm(vec![
+					BuyExecution { fees: (asset_location, half).into(), weight_limit: Unlimited },
+					deposit,
+				]),
+			},
+		])
+	};
+	Some(Xcm(vec![
+		WithdrawAsset(assets),
+		InitiateReserveWithdraw { assets: Wild(All), reserve, xcm },
+	]))
+}
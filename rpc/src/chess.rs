@@ -0,0 +1,204 @@
+//! Serves the games of `pallet_chess`, for clients which would otherwise decode its storage and
+//! implement the rules of chess.
+//!
+//! Time controls are given by the index of their `MatchStyle` variant, i.e. `0` for bullet, `1`
+//! for blitz, `2` for rapid and `3` for daily.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use chess_primitives::ChessApi as ChessRuntimeApi;
+use codec::{Codec, Decode};
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+
+/// A match waiting for its opponent, with its bet as `NumberOrHex` as JSON numbers can't hold
+/// it losslessly.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenMatch<Hash, AccountId, AssetId> {
+	/// The ID of the match.
+	pub match_id: Hash,
+	/// The player who created the match, and plays white.
+	pub challenger: AccountId,
+	/// The player who can join the match, and plays black.
+	pub opponent: AccountId,
+	/// The asset of the bet.
+	pub bet_asset_id: AssetId,
+	/// The amount of the bet, staked by both players.
+	pub bet_amount: NumberOrHex,
+}
+
+impl<Hash, AccountId, AssetId, Balance>
+	From<chess_primitives::OpenMatch<Hash, AccountId, AssetId, Balance>>
+	for OpenMatch<Hash, AccountId, AssetId>
+where
+	Balance: Into<NumberOrHex>,
+{
+	fn from(open_match: chess_primitives::OpenMatch<Hash, AccountId, AssetId, Balance>) -> Self {
+		Self {
+			match_id: open_match.match_id,
+			challenger: open_match.challenger,
+			opponent: open_match.opponent,
+			bet_asset_id: open_match.bet_asset_id,
+			bet_amount: open_match.bet_amount.into(),
+		}
+	}
+}
+
+/// A page of the matches waiting for their opponent.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenMatches<Hash, AccountId, AssetId> {
+	/// The matches of the page waiting for their opponent.
+	pub matches: Vec<OpenMatch<Hash, AccountId, AssetId>>,
+	/// The match to read the next page after, if any.
+	pub next: Option<Hash>,
+}
+
+/// Serves the games of `pallet_chess`.
+#[rpc(client, server)]
+pub trait ChessApi<BlockHash, Hash, AccountId, BlockNumber, AssetId> {
+	/// The board of the match `match_id`, in FEN.
+	#[method(name = "chess_board")]
+	fn board(&self, match_id: Hash, at: Option<BlockHash>) -> RpcResult<Option<String>>;
+
+	/// The player to move in the match `match_id`, if it is being played.
+	#[method(name = "chess_turn")]
+	fn turn(&self, match_id: Hash, at: Option<BlockHash>) -> RpcResult<Option<AccountId>>;
+
+	/// The number of blocks left to the player to move in the match `match_id` before they can be
+	/// timed out, if it is being played.
+	#[method(name = "chess_clock")]
+	fn clock(&self, match_id: Hash, at: Option<BlockHash>) -> RpcResult<Option<BlockNumber>>;
+
+	/// The moves the player to move in the match `match_id` can make, in UCI.
+	#[method(name = "chess_legalMoves")]
+	fn legal_moves(&self, match_id: Hash, at: Option<BlockHash>) -> RpcResult<Vec<String>>;
+
+	/// The matches of time control `style` waiting for their opponent, among the next `count`
+	/// matches after `start_after`, in storage order.
+	#[method(name = "chess_openMatches")]
+	fn open_matches(
+		&self,
+		style: u8,
+		start_after: Option<Hash>,
+		count: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<OpenMatches<Hash, AccountId, AssetId>>;
+}
+
+/// Provides the games of `pallet_chess`.
+pub struct Chess<C, Block, MatchStyle, Balance> {
+	client: Arc<C>,
+	_marker: PhantomData<(Block, MatchStyle, Balance)>,
+}
+
+impl<C, Block, MatchStyle, Balance> Chess<C, Block, MatchStyle, Balance> {
+	/// Serves the games with the runtime API of `client`.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// The runtime API call failed.
+const RUNTIME_ERROR: i32 = 1;
+/// The time control is unknown.
+const INVALID_STYLE: i32 = 2;
+
+fn runtime_error(error: impl ToString) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Unable to query the games.",
+		Some(error.to_string()),
+	))
+	.into()
+}
+
+/// The `MatchStyle` of index `style`.
+fn match_style<MatchStyle: Decode>(style: u8) -> RpcResult<MatchStyle> {
+	MatchStyle::decode(&mut &[style][..]).map_err(|_| {
+		CallError::Custom(ErrorObject::owned(
+			INVALID_STYLE,
+			"Unknown time control.",
+			Some(style.to_string()),
+		))
+		.into()
+	})
+}
+
+impl<C, Block, MatchStyle, Balance, Hash, AccountId, BlockNumber, AssetId>
+	ChessApiServer<<Block as BlockT>::Hash, Hash, AccountId, BlockNumber, AssetId>
+	for Chess<C, Block, MatchStyle, Balance>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ChessRuntimeApi<Block, Hash, AccountId, MatchStyle, BlockNumber, AssetId, Balance>,
+	MatchStyle: Codec + Send + Sync + 'static,
+	Balance: Codec + Into<NumberOrHex> + Send + Sync + 'static,
+	Hash: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	AccountId: Codec + Serialize + Send + Sync + 'static,
+	BlockNumber: Codec + Serialize + Send + Sync + 'static,
+	AssetId: Codec + Serialize + Send + Sync + 'static,
+{
+	fn board(
+		&self,
+		match_id: Hash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<String>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let board = self.client.runtime_api().board(at, match_id).map_err(runtime_error)?;
+		Ok(board.map(|fen| String::from_utf8_lossy(&fen).into_owned()))
+	}
+
+	fn turn(
+		&self,
+		match_id: Hash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<AccountId>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().turn(at, match_id).map_err(runtime_error)
+	}
+
+	fn clock(
+		&self,
+		match_id: Hash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<BlockNumber>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().clock(at, match_id).map_err(runtime_error)
+	}
+
+	fn legal_moves(
+		&self,
+		match_id: Hash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<String>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let moves = self.client.runtime_api().legal_moves(at, match_id).map_err(runtime_error)?;
+		Ok(moves.iter().map(|uci| String::from_utf8_lossy(uci).into_owned()).collect())
+	}
+
+	fn open_matches(
+		&self,
+		style: u8,
+		start_after: Option<Hash>,
+		count: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<OpenMatches<Hash, AccountId, AssetId>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let (matches, next) = self
+			.client
+			.runtime_api()
+			.open_matches(at, match_style(style)?, start_after, count)
+			.map_err(runtime_error)?;
+		Ok(OpenMatches { matches: matches.into_iter().map(Into::into).collect(), next })
+	}
+}
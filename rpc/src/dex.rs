@@ -0,0 +1,120 @@
+//! Lists the exchanges of `pallet_dex`, for clients which would otherwise decode its storage.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use dex_primitives::{DexPoolsApi as DexPoolsRuntimeApi, PoolInfo, ProviderFee};
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+
+/// An exchange, with balances as `NumberOrHex` as JSON numbers can't hold them losslessly.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Pool<AssetId> {
+	/// The asset paired with the native currency.
+	pub asset_id: AssetId,
+	/// The asset minted to liquidity providers.
+	pub liquidity_token_id: AssetId,
+	/// The native currency held by the exchange.
+	pub currency_reserve: NumberOrHex,
+	/// The asset held by the exchange.
+	pub token_reserve: NumberOrHex,
+	/// The total issuance of `liquidity_token_id`.
+	pub liquidity_supply: NumberOrHex,
+}
+
+impl<AssetId, Balance, AssetBalance> From<PoolInfo<AssetId, Balance, AssetBalance>>
+	for Pool<AssetId>
+where
+	Balance: Into<NumberOrHex>,
+	AssetBalance: Into<NumberOrHex>,
+{
+	fn from(pool: PoolInfo<AssetId, Balance, AssetBalance>) -> Self {
+		Self {
+			asset_id: pool.asset_id,
+			liquidity_token_id: pool.liquidity_token_id,
+			currency_reserve: pool.currency_reserve.into(),
+			token_reserve: pool.token_reserve.into(),
+			liquidity_supply: pool.liquidity_supply.into(),
+		}
+	}
+}
+
+/// The exchanges and the fee they charge, `providerFeeNumerator / providerFeeDenominator` of
+/// the input of swaps.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Pools<AssetId> {
+	/// The numerator of the fee.
+	pub provider_fee_numerator: NumberOrHex,
+	/// The denominator of the fee.
+	pub provider_fee_denominator: NumberOrHex,
+	/// Every exchange, by ascending asset ID.
+	pub pools: Vec<Pool<AssetId>>,
+}
+
+/// Lists the exchanges of `pallet_dex`.
+#[rpc(client, server)]
+pub trait DexPoolsApi<BlockHash, AssetId> {
+	/// Every exchange, by ascending asset ID, with its reserves and liquidity token supply.
+	#[method(name = "dex_listPools")]
+	fn list_pools(&self, at: Option<BlockHash>) -> RpcResult<Pools<AssetId>>;
+}
+
+/// Provides the exchanges of `pallet_dex`.
+pub struct DexPools<C, Block, Balance, AssetBalance> {
+	client: Arc<C>,
+	_marker: PhantomData<(Block, Balance, AssetBalance)>,
+}
+
+impl<C, Block, Balance, AssetBalance> DexPools<C, Block, Balance, AssetBalance> {
+	/// Lists the exchanges with the runtime API of `client`.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// The runtime API call failed.
+const RUNTIME_ERROR: i32 = 1;
+
+fn runtime_error(error: impl ToString) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Unable to list the pools.",
+		Some(error.to_string()),
+	))
+	.into()
+}
+
+impl<C, Block, AssetId, Balance, AssetBalance> DexPoolsApiServer<<Block as BlockT>::Hash, AssetId>
+	for DexPools<C, Block, Balance, AssetBalance>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: DexPoolsRuntimeApi<Block, AssetId, Balance, AssetBalance>,
+	AssetId: Codec + Serialize + Send + Sync + 'static,
+	Balance: Codec + Into<NumberOrHex> + Send + Sync + 'static,
+	AssetBalance: Codec + Into<NumberOrHex> + Send + Sync + 'static,
+{
+	fn list_pools(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Pools<AssetId>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let ProviderFee { numerator, denominator } = api.provider_fee(at).map_err(runtime_error)?;
+		let pools = api.pools(at).map_err(runtime_error)?;
+
+		Ok(Pools {
+			provider_fee_numerator: numerator.into(),
+			provider_fee_denominator: denominator.into(),
+			pools: pools.into_iter().map(Into::into).collect(),
+		})
+	}
+}
@@ -20,6 +20,9 @@
 
 use std::sync::Arc;
 
+pub mod chess;
+pub mod dex;
+
 use parachains_common::{AccountId, Balance, Block, Index as Nonce};
 use sc_client_api::AuxStore;
 pub use sc_rpc::{DenyUnsafe, SubscriptionTaskExecutor};
@@ -62,9 +65,39 @@ where
 		trappist_runtime::Balance,
 		trappist_runtime::AssetBalance,
 	>,
+	C::Api: dex_primitives::DexPoolsApi<
+		trappist_runtime::opaque::Block,
+		trappist_runtime::AssetId,
+		trappist_runtime::Balance,
+		trappist_runtime::AssetBalance,
+	>,
+	C::Api: pallet_dex_oracle_rpc::DexOracleRuntimeApi<
+		trappist_runtime::opaque::Block,
+		trappist_runtime::AssetId,
+		trappist_runtime::BlockNumber,
+	>,
+	C::Api: pallet_chess_rating_rpc::ChessRatingRuntimeApi<
+		trappist_runtime::opaque::Block,
+		AccountId,
+		pallet_chess::MatchStyle,
+		trappist_runtime::BlockNumber,
+	>,
+	C::Api: chess_primitives::ChessApi<
+		trappist_runtime::opaque::Block,
+		trappist_runtime::Hash,
+		AccountId,
+		pallet_chess::MatchStyle,
+		trappist_runtime::BlockNumber,
+		trappist_runtime::AssetId,
+		trappist_runtime::AssetBalance,
+	>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + Sync + Send + 'static,
 {
+	use chess::{Chess, ChessApiServer};
+	use dex::{DexPools, DexPoolsApiServer};
+	use pallet_chess_rating_rpc::{ChessRating, ChessRatingApiServer};
+	use pallet_dex_oracle_rpc::{DexOracle, DexOracleApiServer};
 	use pallet_dex_rpc::{Dex, DexApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use sc_rpc::dev::{Dev, DevApiServer};
@@ -76,7 +109,19 @@ where
 	module.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
 	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
 	module.merge(Dev::new(client.clone(), deny_unsafe).into_rpc())?;
-	module.merge(Dex::new(client).into_rpc())?;
+	module.merge(Dex::new(client.clone()).into_rpc())?;
+	module.merge(
+		DexPools::<_, _, trappist_runtime::Balance, trappist_runtime::AssetBalance>::new(
+			client.clone(),
+		)
+		.into_rpc(),
+	)?;
+	module.merge(DexOracle::new(client.clone()).into_rpc())?;
+	module.merge(ChessRating::<_, _, pallet_chess::MatchStyle>::new(client.clone()).into_rpc())?;
+	module.merge(
+		Chess::<_, _, pallet_chess::MatchStyle, trappist_runtime::AssetBalance>::new(client)
+			.into_rpc(),
+	)?;
 
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
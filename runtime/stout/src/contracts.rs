@@ -1,10 +1,10 @@
 use crate::{
 	constants::currency::deposit, Balance, Balances, RandomnessCollectiveFlip, Runtime,
-	RuntimeBlockWeights, RuntimeCall, RuntimeEvent, Timestamp,
+	RuntimeBlockWeights, RuntimeCall, RuntimeEvent, System, Timestamp,
 };
 use frame_support::{
 	parameter_types,
-	traits::{ConstU32, Nothing},
+	traits::{ConstU32, Contains},
 	weights::Weight,
 };
 use pallet_contracts::{
@@ -12,11 +12,28 @@ use pallet_contracts::{
 	Config, DefaultAddressGenerator, Frame, Schedule,
 };
 pub use parachains_common::AVERAGE_ON_INITIALIZE_RATIO;
-use sp_core::ConstBool;
+use sp_core::{ConstBool, LogLevelFilter};
+use sp_std::prelude::*;
+use xcm_primitives::XcmExtension;
 
-// Prints debug output of the `contracts` pallet to stdout if the node is
-// started with `-lruntime::contracts=debug`.
-pub const CONTRACTS_DEBUG_OUTPUT: bool = true;
+/// Whether `ContractsApi` dry-runs collect the debug buffer of contracts.
+///
+/// Only enabled if the node is started with `-lruntime::contracts=debug` (or any other debug
+/// logging), so production nodes don't pay for it. `ContractsDryRunApi` lets callers choose per
+/// call instead.
+pub fn contracts_debug_output() -> bool {
+	matches!(sp_io::logging::max_level(), LogLevelFilter::Debug | LogLevelFilter::Trace)
+}
+
+/// The events deposited by the contracts pallet since the events were last reset.
+pub fn contracts_events() -> Vec<pallet_contracts::Event<Runtime>> {
+	System::read_events_no_consensus()
+		.filter_map(|record| match record.event {
+			RuntimeEvent::Contracts(event) => Some(event),
+			_ => None,
+		})
+		.collect()
+}
 
 parameter_types! {
 	pub const DepositPerItem: Balance = deposit(1, 0);
@@ -33,24 +50,52 @@ parameter_types! {
 	pub MySchedule: Schedule<Runtime> = Default::default();
 }
 
+/// Runtime calls which contracts may dispatch through `seal_call_runtime`.
+///
+/// Contracts depend on the encoding of these calls, so only dispatchables we commit to keeping
+/// stable are listed. Batches are allowed as long as every call they contain is.
+///
+/// The same calls as the Trappist runtime allows, less the swaps of `pallet_dex`, which Stout
+/// doesn't run. The filter is tested there.
+pub struct ContractsCallFilter;
+impl Contains<RuntimeCall> for ContractsCallFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		match call {
+			RuntimeCall::Balances(
+				pallet_balances::Call::transfer { .. } |
+				pallet_balances::Call::transfer_keep_alive { .. },
+			) |
+			RuntimeCall::Assets(
+				pallet_assets::Call::transfer { .. } |
+				pallet_assets::Call::transfer_keep_alive { .. },
+			) |
+			RuntimeCall::Uniques(pallet_uniques::Call::transfer { .. }) => true,
+			RuntimeCall::Utility(
+				pallet_utility::Call::batch { calls } | pallet_utility::Call::batch_all { calls },
+			) => calls.iter().all(Self::contains),
+			_ => false,
+		}
+	}
+}
+
 impl Config for Runtime {
 	type Time = Timestamp;
 	type Randomness = RandomnessCollectiveFlip;
 	type Currency = Balances;
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
-	/// The safest default is to allow no calls at all.
+	/// Only the stable calls listed in [`ContractsCallFilter`] are allowed.
 	///
 	/// Runtimes should whitelist dispatchables that are allowed to be called from contracts
 	/// and make sure they are stable. Dispatchables exposed to contracts are not allowed to
 	/// change because that would break already deployed contracts. The `Call` structure itself
 	/// is not allowed to change the indices of existing pallets, too.
-	type CallFilter = Nothing;
+	type CallFilter = ContractsCallFilter;
 	type DepositPerItem = DepositPerItem;
 	type DepositPerByte = DepositPerByte;
 	type WeightPrice = pallet_transaction_payment::Pallet<Self>;
 	type WeightInfo = SubstrateWeight<Self>;
-	type ChainExtension = ();
+	type ChainExtension = XcmExtension<Self>;
 	type DeletionQueueDepth = DeletionQueueDepth;
 	type DeletionWeightLimit = DeletionWeightLimit;
 	type Schedule = MySchedule;
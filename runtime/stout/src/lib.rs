@@ -422,10 +422,13 @@ parameter_types! {
 	pub const ValueLimit: u32 = 256;
 }
 
+pub type CollectionId = u32;
+pub type ItemId = u32;
+
 impl pallet_uniques::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type CollectionId = u32;
-	type ItemId = u32;
+	type CollectionId = CollectionId;
+	type ItemId = ItemId;
 	type Currency = Balances;
 	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
 	type CollectionDeposit = CollectionDeposit;
@@ -443,6 +446,19 @@ impl pallet_uniques::Config for Runtime {
 	type Locker = ();
 }
 
+impl pallet_asset_registry::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ReserveAssetModifierOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type Assets = Assets;
+	// no pallet of the runtime reacts to reserve assets being registered or unregistered yet
+	type OnAssetRegistered = ();
+	type OnAssetUnregistered = ();
+	type CollectionId = CollectionId;
+	type Uniques = Uniques;
+	type TeleporterModifierOrigin = EnsureRootOrHalfCouncil;
+	type WeightInfo = pallet_asset_registry::weights::SubstrateWeight<Runtime>;
+}
+
 parameter_types! {
 	pub MaximumSchedulerWeight: Weight = Weight::from_ref_time(10_000_000);
 	pub const NoPreimagePostponement: Option<u32> = Some(10);
@@ -520,6 +536,7 @@ construct_runtime!(
 		Utility: pallet_utility = 47,
 		Preimage: pallet_preimage = 48,
 		Multisig: pallet_multisig = 49,
+		AssetRegistry: pallet_asset_registry::{Pallet, Call, Storage, Event<T>} = 50,
 
 		Spambot: cumulus_ping::{Pallet, Call, Storage, Event<T>} = 99,
 	}
@@ -545,6 +562,7 @@ mod benches {
 		[pallet_uniques, Uniques]
 		[pallet_scheduler, Scheduler]
 		[pallet_utility, Utility]
+		[pallet_asset_registry, AssetRegistry]
 		[cumulus_pallet_xcmp_queue, XcmpQueue]
 	);
 }
@@ -673,7 +691,7 @@ impl_runtime_apis! {
 				gas_limit,
 				storage_deposit_limit,
 				input_data,
-				contracts::CONTRACTS_DEBUG_OUTPUT,
+				contracts::contracts_debug_output(),
 				pallet_contracts::Determinism::Deterministic,
 			)
 		}
@@ -696,7 +714,7 @@ impl_runtime_apis! {
 				code,
 				data,
 				salt,
-				contracts::CONTRACTS_DEBUG_OUTPUT,
+				contracts::contracts_debug_output(),
 			)
 		}
 
@@ -722,6 +740,71 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl contracts_primitives::ContractsDryRunApi<
+		Block,
+		AccountId,
+		Balance,
+		Hash,
+		pallet_contracts::Event<Runtime>,
+	> for Runtime {
+		fn call(
+			origin: AccountId,
+			dest: AccountId,
+			value: Balance,
+			gas_limit: Option<Weight>,
+			storage_deposit_limit: Option<Balance>,
+			input_data: Vec<u8>,
+			debug: bool,
+		) -> contracts_primitives::DryRunResult<
+			pallet_contracts_primitives::ContractExecResult<Balance>,
+			pallet_contracts::Event<Runtime>,
+		> {
+			// only report the events of this dry-run
+			System::reset_events();
+			let gas_limit = gas_limit.unwrap_or(RuntimeBlockWeights::get().max_block);
+			let result = Contracts::bare_call(
+				origin,
+				dest,
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				input_data,
+				debug,
+				pallet_contracts::Determinism::Deterministic,
+			);
+			contracts_primitives::DryRunResult { result, events: contracts::contracts_events() }
+		}
+
+		fn instantiate(
+			origin: AccountId,
+			value: Balance,
+			gas_limit: Option<Weight>,
+			storage_deposit_limit: Option<Balance>,
+			code: pallet_contracts_primitives::Code<Hash>,
+			data: Vec<u8>,
+			salt: Vec<u8>,
+			debug: bool,
+		) -> contracts_primitives::DryRunResult<
+			pallet_contracts_primitives::ContractInstantiateResult<AccountId, Balance>,
+			pallet_contracts::Event<Runtime>,
+		> {
+			// only report the events of this dry-run
+			System::reset_events();
+			let gas_limit = gas_limit.unwrap_or(RuntimeBlockWeights::get().max_block);
+			let result = Contracts::bare_instantiate(
+				origin,
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				code,
+				data,
+				salt,
+				debug,
+			);
+			contracts_primitives::DryRunResult { result, events: contracts::contracts_events() }
+		}
+	}
+
 	#[cfg(feature = "try-runtime")]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
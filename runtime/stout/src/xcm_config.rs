@@ -16,14 +16,17 @@
 use crate::constants::fee::default_fee_per_second;
 
 use super::{
-	AccountId, Assets, Balance, Balances, ParachainInfo, ParachainSystem, PolkadotXcm, Runtime,
-	RuntimeCall, RuntimeEvent, RuntimeOrigin, WeightToFee, XcmpQueue,
+	AccountId, AssetRegistry, Assets, Balance, Balances, CollectionId, ItemId, ParachainInfo,
+	ParachainSystem, PolkadotXcm, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, Uniques,
+	WeightToFee, XcmpQueue,
 };
 use frame_support::{
 	match_types, parameter_types,
 	traits::{EitherOfDiverse, Everything, Get, Nothing},
+	PalletId,
 };
 use frame_system::EnsureRoot;
+use sp_runtime::traits::AccountIdConversion;
 use sp_std::marker::PhantomData;
 
 use parachains_common::{
@@ -32,10 +35,18 @@ use parachains_common::{
 	AssetId,
 };
 use xcm_executor::traits::{FilterAssetLocation, JustTry};
+use xcm_primitives::{
+	AllowUnpaidNonFungiblesDepositFrom, AsInstanceIndex, IsTrustedTeleporter,
+	NonFungiblesHoldingAdapter, TrustedTeleportDestination,
+};
 
 use pallet_xcm::{EnsureXcm, IsMajorityOfBody, XcmPassthrough};
 use polkadot_parachain::primitives::Sibling;
-use xcm::latest::{prelude::*, Fungibility::Fungible, MultiAsset, MultiLocation};
+use xcm::latest::{
+	prelude::*,
+	Fungibility::{Fungible, NonFungible},
+	MultiAsset, MultiLocation,
+};
 
 use xcm_builder::{
 	AccountId32Aliases, AllowKnownQueryResponses, AllowSubscriptionsFrom,
@@ -110,8 +121,29 @@ pub type FungiblesTransactor = FungiblesAdapter<
 	CheckingAccount,
 >;
 
+/// Means for transacting the derivatives of Trappist's uniques items.
+/// Trappist collections are mirrored by the local collections with the same id.
+pub type TrappistNonFungiblesTransactor = NonFungiblesHoldingAdapter<
+	// Use this non-fungibles implementation:
+	Uniques,
+	// Use this collection when it is a non-fungible asset matching the given location or name:
+	ConvertedConcreteAssetId<
+		CollectionId,
+		ItemId,
+		AsPrefixedGeneralIndex<TrappistUniquesPalletLocation, CollectionId, JustTry>,
+		AsInstanceIndex<ItemId>,
+	>,
+	// Convert an XCM MultiLocation into a local account id:
+	LocationToAccountId,
+	// Our chain's account ID type (we can't get away without mentioning it explicitly):
+	AccountId,
+	// The account holding the derivative items which were sent back to Trappist.
+	UniquesHoldingAccount,
+>;
+
 /// Means for transacting assets on this chain.
-pub type AssetTransactors = (CurrencyTransactor, FungiblesTransactor);
+pub type AssetTransactors =
+	(CurrencyTransactor, FungiblesTransactor, TrappistNonFungiblesTransactor);
 
 /// This is the type we use to convert an (incoming) XCM origin into a local `Origin` instance,
 /// ready for dispatching a transaction with Xcm's `Transact`. There is an `OriginKind` which can
@@ -160,6 +192,11 @@ match_types! {
 		MultiLocation { parents: 1, interior: X1(Parachain(1000)) }
 	};
 }
+match_types! {
+	pub type Trappist: impl Contains<MultiLocation> = {
+		MultiLocation { parents: 1, interior: X1(Parachain(1836)) }
+	};
+}
 
 pub type Barrier = DenyThenTry<
 	DenyReserveTransferToRelayChain,
@@ -169,6 +206,8 @@ pub type Barrier = DenyThenTry<
 		// Parent and its exec plurality get free execution
 		AllowUnpaidExecutionFrom<ParentOrParentsExecutivePlurality>,
 		AllowUnpaidExecutionFrom<Statemine>,
+		// Trappist reserve-transfers its uniques items to us, which can't pay for execution
+		AllowUnpaidNonFungiblesDepositFrom<Trappist, TrappistUniquesPalletLocation>,
 		// Expected responses are OK.
 		AllowKnownQueryResponses<PolkadotXcm>,
 		// Subscriptions for version tracking are OK.
@@ -183,6 +222,13 @@ parameter_types! {
 	pub StatemineAssetsPalletLocation: MultiLocation =
 		MultiLocation::new(1, X2(Parachain(1000), PalletInstance(50)));
 	pub CheckingAccount: AccountId = PolkadotXcm::check_account();
+	pub TrappistLocation: MultiLocation = MultiLocation::new(1, X1(Parachain(1836)));
+	// ALWAYS ensure that the index in PalletInstance stays up-to-date with
+	// Trappist's Uniques pallet index
+	pub TrappistUniquesPalletLocation: MultiLocation =
+		MultiLocation::new(1, X2(Parachain(1836), PalletInstance(44)));
+	// Holds the derivative items which were sent back to their reserve.
+	pub UniquesHoldingAccount: AccountId = PalletId(*b"stou/nft").into_account_truncating();
 
 	pub XUsdPerSecond: (xcm::v1::AssetId, u128) = (
 		MultiLocation::new(1, X3(Parachain(1000), PalletInstance(50), GeneralIndex(1))).into(),
@@ -209,6 +255,10 @@ impl<T: Get<MultiLocation>> FilterAssetLocation for ReserveAssetsFrom<T> {
 			match asset {
 				MultiAsset { id: xcm::latest::AssetId::Concrete(asset_loc), fun: Fungible(_a) } =>
 					matches_prefix(&prefix, asset_loc),
+				MultiAsset {
+					id: xcm::latest::AssetId::Concrete(asset_loc),
+					fun: NonFungible(_instance),
+				} => matches_prefix(&prefix, asset_loc),
 				_ => false,
 			}
 	}
@@ -216,7 +266,8 @@ impl<T: Get<MultiLocation>> FilterAssetLocation for ReserveAssetsFrom<T> {
 
 //--
 
-pub type Reserves = (NativeAsset, ReserveAssetsFrom<StatemineLocation>);
+pub type Reserves =
+	(NativeAsset, ReserveAssetsFrom<StatemineLocation>, ReserveAssetsFrom<TrappistLocation>);
 
 pub struct XcmConfig;
 impl xcm_executor::Config for XcmConfig {
@@ -226,7 +277,8 @@ impl xcm_executor::Config for XcmConfig {
 	type AssetTransactor = AssetTransactors;
 	type OriginConverter = XcmOriginToTransactDispatchOrigin;
 	type IsReserve = Reserves;
-	type IsTeleporter = (); // Teleporting is disabled.
+	// Teleport trust pairs of (asset, origin) are managed by governance via AssetRegistry.
+	type IsTeleporter = IsTrustedTeleporter<AssetRegistry>;
 	type LocationInverter = LocationInverter<Ancestry>;
 	type Barrier = Barrier;
 	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
@@ -260,7 +312,8 @@ impl pallet_xcm::Config for Runtime {
 	type ExecuteXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
 	type XcmExecuteFilter = Everything;
 	type XcmExecutor = XcmExecutor<XcmConfig>;
-	type XcmTeleportFilter = Nothing;
+	// Assets are only teleported to the chains trusted to teleport them back (see `IsTeleporter`).
+	type XcmTeleportFilter = TrustedTeleportDestination<AssetRegistry>;
 	type XcmReserveTransferFilter = Everything;
 	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
 	type LocationInverter = LocationInverter<Ancestry>;
@@ -301,3 +354,29 @@ impl cumulus_ping::Config for Runtime {
 	type RuntimeCall = RuntimeCall;
 	type XcmSender = XcmRouter;
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::{assert_ok, traits::Contains};
+
+	type TeleportFilter = <Runtime as pallet_xcm::Config>::XcmTeleportFilter;
+
+	#[test]
+	fn assets_are_only_teleported_to_trusted_teleporters() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			let relay_token: MultiAsset = (Parent, 1_000).into();
+			let to_relay = (RelayLocation::get(), vec![relay_token.clone()]);
+			assert!(!TeleportFilter::contains(&to_relay));
+
+			assert_ok!(AssetRegistry::add_trusted_teleporter(
+				RuntimeOrigin::root(),
+				RelayLocation::get(),
+				RelayLocation::get(),
+			));
+			assert!(TeleportFilter::contains(&to_relay));
+			// Statemine isn't trusted to teleport the relay token back
+			assert!(!TeleportFilter::contains(&(StatemineLocation::get(), vec![relay_token])));
+		});
+	}
+}
@@ -0,0 +1,46 @@
+;; Forwards its input to `seal_call_chain_extension` and returns the status code followed by the
+;; output buffer of the extension.
+;;
+;; Input: the `u32` id of the chain extension function (SCALE encoded) followed by its payload.
+;; Output: the `u32` status code returned by the extension followed by its output.
+(module
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "seal0" "seal_call_chain_extension"
+		(func $seal_call_chain_extension (param i32 i32 i32 i32 i32) (result i32))
+	)
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 4) size of the input buffer
+	(data (i32.const 0) "\00\04")
+
+	;; [4, 1028) input buffer
+
+	;; [1028, 1032) size of the output buffer
+	(data (i32.const 1028) "\00\04")
+
+	;; [1032, 1036) status code returned by the chain extension
+
+	;; [1036, 2060) output buffer
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(call $seal_input (i32.const 4) (i32.const 0))
+		(i32.store
+			(i32.const 1032)
+			(call $seal_call_chain_extension
+				(i32.load (i32.const 4))				;; id
+				(i32.const 8)							;; input_ptr
+				(i32.sub (i32.load (i32.const 0)) (i32.const 4))	;; input_len
+				(i32.const 1036)						;; output_ptr
+				(i32.const 1028)						;; output_len_ptr
+			)
+		)
+		(call $seal_return
+			(i32.const 0)
+			(i32.const 1032)
+			(i32.add (i32.load (i32.const 1028)) (i32.const 4))
+		)
+	)
+)
@@ -0,0 +1,48 @@
+;; Instantiates the code whose hash is its input, without input data, value or salt, and returns
+;; the return code of `seal_instantiate`.
+;;
+;; Input: the code hash.
+;; Output: the `u32` return code of `seal_instantiate`.
+(module
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "seal1" "seal_instantiate"
+		(func $seal_instantiate
+			(param i32 i64 i32 i32 i32 i32 i32 i32 i32 i32 i32)
+			(result i32)
+		)
+	)
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 4) size of the input buffer
+	(data (i32.const 0) "\20")
+
+	;; [4, 36) code hash
+
+	;; [36, 52) value to transfer to the instantiated contract: 0
+
+	;; [52, 56) return code of `seal_instantiate`
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(call $seal_input (i32.const 4) (i32.const 0))
+		(i32.store
+			(i32.const 52)
+			(call $seal_instantiate
+				(i32.const 4)		;; code_hash_ptr
+				(i64.const 0)		;; gas: all the remaining gas
+				(i32.const 36)		;; value_ptr
+				(i32.const 0)		;; input_data_ptr
+				(i32.const 0)		;; input_data_len
+				(i32.const -1)		;; address_ptr: not copied
+				(i32.const 0)		;; address_len_ptr
+				(i32.const -1)		;; output_ptr: not copied
+				(i32.const 0)		;; output_len_ptr
+				(i32.const 0)		;; salt_ptr
+				(i32.const 0)		;; salt_len
+			)
+		)
+		(call $seal_return (i32.const 0) (i32.const 52) (i32.const 4))
+	)
+)
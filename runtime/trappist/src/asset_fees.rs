@@ -0,0 +1,402 @@
+use crate::{
+	AccountId, AssetBalance, AssetId, Assets, Balance, Balances, BlockNumber, Dex, DexOracle,
+	Runtime, RuntimeCall, MINUTES,
+};
+use frame_support::{
+	dispatch::{DispatchInfo, PostDispatchInfo},
+	parameter_types,
+	traits::tokens::{
+		fungibles::{Balanced, CreditOf, Inspect},
+		BalanceConversion, WithdrawConsequence,
+	},
+};
+use pallet_asset_tx_payment::{HandleCredit, OnChargeAssetTransaction};
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational_with_rounding,
+	traits::{ConvertInto, One, Zero},
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+	FixedPointNumber, FixedU128, Rounding,
+};
+use sp_std::marker::PhantomData;
+
+parameter_types! {
+	/// The window of the TWAP bounding the price of fees paid in assets tracked by `DexOracle`.
+	pub const FeePriceWindow: BlockNumber = 10 * MINUTES;
+}
+
+/// Prices native balances in an asset at the rate of its `pallet_dex` exchange: the amount of the
+/// asset needed to buy the native balance.
+///
+/// The spot rate can be moved within a block, e.g. by buying the asset right before paying fees in
+/// it. For assets tracked by `DexOracle`, the amount is at least the native balance priced at the
+/// asset's TWAP over `FeePriceWindow`, so pumping the asset doesn't make fees cheaper. Assets
+/// accepted for fees should be tracked.
+///
+/// Falls back to `pallet_assets::BalanceToAssetBalance`, i.e. the ratio of the asset's minimum
+/// balance to the existential deposit, if the asset has no exchange or it lacks liquidity.
+pub struct DexBalanceToAssetBalance;
+impl BalanceConversion<Balance, AssetId, AssetBalance> for DexBalanceToAssetBalance {
+	type Error = pallet_assets::ConversionError;
+
+	fn to_asset_balance(balance: Balance, asset_id: AssetId) -> Result<AssetBalance, Self::Error> {
+		if balance.is_zero() {
+			return Ok(Zero::zero());
+		}
+		let spot_amount =
+			Dex::get_asset_to_currency_input_amount(asset_id, balance).or_else(|_| {
+				pallet_assets::BalanceToAssetBalance::<Balances, Runtime, ConvertInto>::to_asset_balance(
+				balance, asset_id,
+			)
+			})?;
+		// the TWAP is the price of the asset in the native currency
+		let twap_amount = DexOracle::twap(asset_id, FeePriceWindow::get())
+			.filter(|price| !price.is_zero())
+			.and_then(|price| {
+				multiply_by_rational_with_rounding(
+					balance,
+					FixedU128::accuracy(),
+					price.into_inner(),
+					Rounding::Up,
+				)
+			})
+			.unwrap_or_default();
+		Ok(spot_amount.max(twap_amount))
+	}
+}
+
+/// Charges transaction fees in assets priced by [`DexBalanceToAssetBalance`].
+///
+/// Unlike `pallet_asset_tx_payment::FungiblesAdapter`, refunds are not priced again after
+/// dispatch, where the exchange may have moved (e.g. by the dispatched call itself), but at the
+/// rate the fee was withdrawn at. Refunds which can't be deposited, i.e. below the minimum balance
+/// of the asset to a payer whose account was emptied by the call, are paid to the fee recipient
+/// instead.
+pub struct DexFungiblesAdapter<HC>(PhantomData<HC>);
+
+impl<HC> OnChargeAssetTransaction<Runtime> for DexFungiblesAdapter<HC>
+where
+	HC: HandleCredit<AccountId, Assets>,
+{
+	type Balance = Balance;
+	type AssetId = AssetId;
+	/// The withdrawn asset credit, and the native fee it was priced at.
+	type LiquidityInfo = (CreditOf<AccountId, Assets>, Balance);
+
+	fn withdraw_fee(
+		who: &AccountId,
+		_call: &RuntimeCall,
+		_dispatch_info: &DispatchInfo,
+		asset_id: Self::AssetId,
+		fee: Self::Balance,
+		_tip: Self::Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		// the converted fee may be rounded down to zero
+		let min_converted_fee = if fee.is_zero() { Zero::zero() } else { One::one() };
+		let converted_fee = DexBalanceToAssetBalance::to_asset_balance(fee, asset_id)
+			.map_err(|_| TransactionValidityError::from(InvalidTransaction::Payment))?
+			.max(min_converted_fee);
+
+		if !matches!(
+			<Assets as Inspect<AccountId>>::can_withdraw(asset_id, who, converted_fee),
+			WithdrawConsequence::Success
+		) {
+			return Err(InvalidTransaction::Payment.into());
+		}
+		<Assets as Balanced<AccountId>>::withdraw(asset_id, who, converted_fee)
+			.map(|credit| (credit, fee))
+			.map_err(|_| TransactionValidityError::from(InvalidTransaction::Payment))
+	}
+
+	fn correct_and_deposit_fee(
+		who: &AccountId,
+		_dispatch_info: &DispatchInfo,
+		_post_info: &PostDispatchInfo,
+		corrected_fee: Self::Balance,
+		tip: Self::Balance,
+		(paid, fee): Self::LiquidityInfo,
+	) -> Result<(AssetBalance, AssetBalance), TransactionValidityError> {
+		let paid_fee = paid.peek();
+		let (converted_fee, converted_tip) = if fee.is_zero() {
+			(Zero::zero(), Zero::zero())
+		} else {
+			(
+				// rounded up in favour of the fee recipient
+				multiply_by_rational_with_rounding(paid_fee, corrected_fee, fee, Rounding::Up)
+					.unwrap_or(paid_fee),
+				multiply_by_rational_with_rounding(paid_fee, tip, fee, Rounding::Down)
+					.unwrap_or(Zero::zero()),
+			)
+		};
+		let min_converted_fee = if corrected_fee.is_zero() { Zero::zero() } else { One::one() };
+		let converted_fee = converted_fee.max(min_converted_fee);
+
+		let (final_fee, refund) = paid.split(converted_fee);
+		// the call may have emptied the payer's account, which can't receive less than the
+		// minimum balance of the asset
+		let final_fee = match <Assets as Balanced<AccountId>>::resolve(who, refund) {
+			Ok(()) => final_fee,
+			Err(refund) => final_fee.merge(refund).unwrap_or_else(|(final_fee, _)| final_fee),
+		};
+		let final_fee_amount = final_fee.peek();
+		HC::handle_credit(final_fee);
+
+		Ok((final_fee_amount, converted_tip))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{constants::currency::UNITS, RuntimeOrigin, System, TransactionPayment};
+	use frame_support::{
+		assert_ok,
+		dispatch::Pays,
+		traits::{GenesisBuild, Hooks},
+		weights::Weight,
+	};
+	use pallet_dex::TradeAmount;
+
+	const ALICE: AccountId = AccountId::new([1u8; 32]);
+	const BOB: AccountId = AccountId::new([2u8; 32]);
+	const ASSET_ID: AssetId = 1;
+
+	type Adapter = DexFungiblesAdapter<()>;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(ALICE, 1_000 * UNITS), (BOB, 1_000 * UNITS)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Assets::force_create(
+				RuntimeOrigin::root(),
+				ASSET_ID.into(),
+				ALICE.into(),
+				true,
+				1
+			));
+			for who in [ALICE, BOB] {
+				assert_ok!(Assets::mint(
+					RuntimeOrigin::signed(ALICE),
+					ASSET_ID.into(),
+					who.into(),
+					1_000 * UNITS
+				));
+			}
+		});
+		ext
+	}
+
+	fn create_exchange() {
+		assert_ok!(Dex::create_exchange(
+			RuntimeOrigin::signed(ALICE),
+			ASSET_ID,
+			10 * UNITS,
+			100 * UNITS
+		));
+	}
+
+	fn info(weight: u64) -> DispatchInfo {
+		DispatchInfo { weight: Weight::from_ref_time(weight), ..Default::default() }
+	}
+
+	fn post_info(actual_weight: u64) -> PostDispatchInfo {
+		PostDispatchInfo {
+			actual_weight: Some(Weight::from_ref_time(actual_weight)),
+			pays_fee: Pays::Yes,
+		}
+	}
+
+	fn withdraw_fee(
+		info: &DispatchInfo,
+		fee: Balance,
+	) -> <Adapter as OnChargeAssetTransaction<Runtime>>::LiquidityInfo {
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		Adapter::withdraw_fee(&BOB, &call, info, ASSET_ID, fee, 0).unwrap()
+	}
+
+	#[test]
+	fn fees_are_priced_by_the_exchange() {
+		new_test_ext().execute_with(|| {
+			create_exchange();
+			let fee = UNITS / 100;
+
+			let (paid, _) = withdraw_fee(&info(0), fee);
+
+			let expected = Dex::get_asset_to_currency_input_amount(ASSET_ID, fee).unwrap();
+			assert_eq!(paid.peek(), expected);
+			// the exchange holds 10 assets per native unit, plus the provider fee
+			assert!(expected > 10 * fee && expected < 11 * fee);
+			assert_eq!(Assets::balance(ASSET_ID, &BOB), 1_000 * UNITS - expected);
+		});
+	}
+
+	#[test]
+	fn fees_fall_back_to_the_minimum_balance_ratio_without_exchange() {
+		new_test_ext().execute_with(|| {
+			let fee = UNITS / 100;
+
+			let (paid, _) = withdraw_fee(&info(0), fee);
+
+			assert_eq!(
+				paid.peek(),
+				pallet_assets::BalanceToAssetBalance::<Balances, Runtime, ConvertInto>::to_asset_balance(
+					fee, ASSET_ID
+				)
+				.unwrap()
+				.max(1)
+			);
+		});
+	}
+
+	#[test]
+	fn refunds_use_the_withdrawal_rate() {
+		new_test_ext().execute_with(|| {
+			create_exchange();
+			let (weight, actual_weight) = (1_000_000_000, 500_000_000);
+			let weight_fee = |weight| {
+				TransactionPayment::next_fee_multiplier().saturating_mul_int(
+					TransactionPayment::weight_to_fee(Weight::from_ref_time(weight)),
+				)
+			};
+			let fee = UNITS / 100 + weight_fee(weight);
+			let corrected_fee = UNITS / 100 + weight_fee(actual_weight);
+			let paid = withdraw_fee(&info(weight), fee);
+			let paid_fee = paid.0.peek();
+
+			// the dispatched call moves the exchange
+			assert_ok!(Dex::asset_to_currency(
+				RuntimeOrigin::signed(ALICE),
+				ASSET_ID,
+				TradeAmount::FixedInput { input_amount: 50 * UNITS, min_output: 1 },
+				10,
+				None
+			));
+
+			let (final_fee, _) = Adapter::correct_and_deposit_fee(
+				&BOB,
+				&info(weight),
+				&post_info(actual_weight),
+				corrected_fee,
+				0,
+				paid,
+			)
+			.unwrap();
+
+			assert_eq!(
+				final_fee,
+				multiply_by_rational_with_rounding(paid_fee, corrected_fee, fee, Rounding::Up)
+					.unwrap()
+			);
+			assert_eq!(Assets::balance(ASSET_ID, &BOB), 1_000 * UNITS - final_fee);
+		});
+	}
+
+	#[test]
+	fn calls_not_paying_fees_are_refunded() {
+		new_test_ext().execute_with(|| {
+			create_exchange();
+			let paid = withdraw_fee(&info(0), UNITS / 100);
+
+			let (final_fee, tip) = Adapter::correct_and_deposit_fee(
+				&BOB,
+				&info(0),
+				&PostDispatchInfo { actual_weight: None, pays_fee: Pays::No },
+				0,
+				0,
+				paid,
+			)
+			.unwrap();
+
+			assert_eq!((final_fee, tip), (0, 0));
+			assert_eq!(Assets::balance(ASSET_ID, &BOB), 1_000 * UNITS);
+		});
+	}
+
+	#[test]
+	fn fees_of_tracked_assets_are_bounded_by_the_twap() {
+		new_test_ext().execute_with(|| {
+			create_exchange();
+			assert_ok!(DexOracle::track_asset(RuntimeOrigin::root(), ASSET_ID));
+			let now = FeePriceWindow::get() + 1;
+			for n in 1..=now {
+				System::set_block_number(n);
+				DexOracle::on_finalize(n);
+			}
+
+			// the asset is bought right before paying fees in it
+			assert_ok!(Dex::currency_to_asset(
+				RuntimeOrigin::signed(ALICE),
+				ASSET_ID,
+				TradeAmount::FixedInput { input_amount: 10 * UNITS, min_output: 1 },
+				now + 10,
+				None
+			));
+			let fee = UNITS / 100;
+			let (paid, _) = withdraw_fee(&info(0), fee);
+
+			let twap = DexOracle::twap(ASSET_ID, FeePriceWindow::get()).unwrap();
+			let expected = multiply_by_rational_with_rounding(
+				fee,
+				FixedU128::accuracy(),
+				twap.into_inner(),
+				Rounding::Up,
+			)
+			.unwrap();
+			assert!(expected > Dex::get_asset_to_currency_input_amount(ASSET_ID, fee).unwrap());
+			assert_eq!(paid.peek(), expected);
+		});
+	}
+
+	#[test]
+	fn refunds_below_the_minimum_balance_are_paid_as_fees() {
+		const DUST_ASSET_ID: AssetId = 2;
+
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::force_create(
+				RuntimeOrigin::root(),
+				DUST_ASSET_ID.into(),
+				ALICE.into(),
+				true,
+				100
+			));
+			let fee = 1_000 * crate::constants::currency::EXISTENTIAL_DEPOSIT;
+			let converted_fee =
+				DexBalanceToAssetBalance::to_asset_balance(fee, DUST_ASSET_ID).unwrap();
+			assert_ok!(Assets::mint(
+				RuntimeOrigin::signed(ALICE),
+				DUST_ASSET_ID.into(),
+				BOB.into(),
+				converted_fee + 1_000
+			));
+			let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+			let paid = Adapter::withdraw_fee(&BOB, &call, &info(0), DUST_ASSET_ID, fee, 0).unwrap();
+
+			// the dispatched call transfers the rest of the payer's balance
+			assert_ok!(Assets::transfer(
+				RuntimeOrigin::signed(BOB),
+				DUST_ASSET_ID.into(),
+				ALICE.into(),
+				1_000
+			));
+			// refunds about 10, below the minimum balance of 100
+			let (final_fee, _) = Adapter::correct_and_deposit_fee(
+				&BOB,
+				&info(0),
+				&post_info(0),
+				fee - fee / 10_000,
+				0,
+				paid,
+			)
+			.unwrap();
+
+			assert_eq!(final_fee, converted_fee);
+			assert_eq!(Assets::balance(DUST_ASSET_ID, &BOB), 0);
+		});
+	}
+}
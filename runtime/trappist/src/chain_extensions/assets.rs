@@ -0,0 +1,190 @@
+use crate::{AccountId, AssetBalance, AssetId, Assets, Runtime};
+use codec::Encode;
+use frame_support::{
+	dispatch::DispatchError,
+	traits::{
+		fungibles::{approvals, metadata, Inspect, Transfer},
+		Get,
+	},
+};
+use pallet_assets::WeightInfo;
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, RegisteredChainExtension, RetVal,
+};
+use sp_runtime::TokenError;
+use sp_std::vec::Vec;
+
+/// Status codes returned to contracts by [`AssetsExtension`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u32)]
+pub enum AssetsError {
+	Success = 0,
+	/// Account balance must be greater than or equal to the transfer amount.
+	BalanceLow = 1,
+	/// The account to alter does not exist.
+	NoAccount = 2,
+	/// The signing account has no permission to do the operation.
+	NoPermission = 3,
+	/// The given asset ID is unknown.
+	Unknown = 4,
+	/// The origin account or the asset is frozen.
+	Frozen = 5,
+	/// No approval exists that would allow the transfer.
+	Unapproved = 6,
+	/// The resulting balance would be below the minimum balance of the asset.
+	BelowMinimum = 7,
+	/// The source account would not survive the transfer.
+	WouldDie = 8,
+}
+
+impl From<AssetsError> for RetVal {
+	fn from(error: AssetsError) -> Self {
+		RetVal::Converging(error as u32)
+	}
+}
+
+impl TryFrom<DispatchError> for AssetsError {
+	type Error = DispatchError;
+
+	fn try_from(error: DispatchError) -> Result<Self, Self::Error> {
+		use pallet_assets::Error;
+		match error {
+			DispatchError::Token(TokenError::NoFunds) => Ok(Self::BalanceLow),
+			DispatchError::Token(TokenError::UnknownAsset) => Ok(Self::Unknown),
+			DispatchError::Token(TokenError::Frozen) => Ok(Self::Frozen),
+			DispatchError::Token(TokenError::BelowMinimum) => Ok(Self::BelowMinimum),
+			DispatchError::Token(TokenError::WouldDie) => Ok(Self::WouldDie),
+			e if e == Error::<Runtime>::BalanceLow.into() => Ok(Self::BalanceLow),
+			e if e == Error::<Runtime>::NoAccount.into() => Ok(Self::NoAccount),
+			e if e == Error::<Runtime>::NoPermission.into() => Ok(Self::NoPermission),
+			e if e == Error::<Runtime>::Unknown.into() => Ok(Self::Unknown),
+			e if e == Error::<Runtime>::Frozen.into() => Ok(Self::Frozen),
+			e if e == Error::<Runtime>::Unapproved.into() => Ok(Self::Unapproved),
+			e if e == Error::<Runtime>::BelowMinimum.into() => Ok(Self::BelowMinimum),
+			e if e == Error::<Runtime>::WouldDie.into() => Ok(Self::WouldDie),
+			e => Err(e),
+		}
+	}
+}
+
+/// Functions of [`AssetsExtension`], identified by the lower 16 bits of the id passed to
+/// `seal_call_chain_extension`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u16)]
+pub enum AssetsFunc {
+	/// `(AssetId, AccountId) -> Balance`
+	Balance = 0,
+	/// `(AssetId, AccountId, Balance)`: transfers from the contract to the given account.
+	Transfer = 1,
+	/// `(AssetId, AccountId, Balance)`: approves the given delegate to spend from the contract.
+	Approve = 2,
+	/// `(AssetId, AccountId, AccountId, Balance)`: transfers from the given owner, which approved
+	/// the contract, to the given destination.
+	TransferApproved = 3,
+	/// `AssetId -> (Vec<u8>, Vec<u8>, u8)`: name, symbol and decimals of the asset.
+	Metadata = 4,
+}
+
+impl TryFrom<u16> for AssetsFunc {
+	type Error = DispatchError;
+
+	fn try_from(func_id: u16) -> Result<Self, Self::Error> {
+		match func_id {
+			0 => Ok(Self::Balance),
+			1 => Ok(Self::Transfer),
+			2 => Ok(Self::Approve),
+			3 => Ok(Self::TransferApproved),
+			4 => Ok(Self::Metadata),
+			_ => Err(DispatchError::Other("Unimplemented func_id")),
+		}
+	}
+}
+
+/// Exposes `pallet_assets` to ink! contracts.
+///
+/// Transfers and approvals are made on behalf of the calling contract.
+#[derive(Default)]
+pub struct AssetsExtension;
+
+impl RegisteredChainExtension<Runtime> for AssetsExtension {
+	const ID: u16 = 1;
+}
+
+impl ChainExtension<Runtime> for AssetsExtension {
+	fn call<E: Ext<T = Runtime>>(
+		&mut self,
+		env: Environment<E, InitState>,
+	) -> Result<RetVal, DispatchError> {
+		let mut env = env.buf_in_buf_out();
+		let result = match AssetsFunc::try_from(env.func_id())? {
+			AssetsFunc::Balance => {
+				env.charge_weight(<Runtime as frame_system::Config>::DbWeight::get().reads(1))?;
+				let (asset_id, who): (AssetId, AccountId) = env.read_as()?;
+
+				let balance: AssetBalance = <Assets as Inspect<AccountId>>::balance(asset_id, &who);
+				env.write(&balance.encode(), false, None)?;
+				Ok(())
+			},
+			AssetsFunc::Transfer => {
+				env.charge_weight(<Runtime as pallet_assets::Config>::WeightInfo::transfer())?;
+				let (asset_id, target, amount): (AssetId, AccountId, AssetBalance) =
+					env.read_as()?;
+				let contract = env.ext().address().clone();
+
+				<Assets as Transfer<AccountId>>::transfer(
+					asset_id, &contract, &target, amount, false,
+				)
+				.map(|_| ())
+			},
+			AssetsFunc::Approve => {
+				env.charge_weight(
+					<Runtime as pallet_assets::Config>::WeightInfo::approve_transfer(),
+				)?;
+				let (asset_id, delegate, amount): (AssetId, AccountId, AssetBalance) =
+					env.read_as()?;
+				let contract = env.ext().address().clone();
+
+				<Assets as approvals::Mutate<AccountId>>::approve(
+					asset_id, &contract, &delegate, amount,
+				)
+			},
+			AssetsFunc::TransferApproved => {
+				env.charge_weight(
+					<Runtime as pallet_assets::Config>::WeightInfo::transfer_approved(),
+				)?;
+				let (asset_id, owner, destination, amount): (
+					AssetId,
+					AccountId,
+					AccountId,
+					AssetBalance,
+				) = env.read_as()?;
+				let contract = env.ext().address().clone();
+
+				<Assets as approvals::Mutate<AccountId>>::transfer_from(
+					asset_id,
+					&owner,
+					&contract,
+					&destination,
+					amount,
+				)
+			},
+			AssetsFunc::Metadata => {
+				env.charge_weight(<Runtime as frame_system::Config>::DbWeight::get().reads(1))?;
+				let asset_id: AssetId = env.read_as()?;
+
+				let metadata: (Vec<u8>, Vec<u8>, u8) = (
+					<Assets as metadata::Inspect<AccountId>>::name(&asset_id),
+					<Assets as metadata::Inspect<AccountId>>::symbol(&asset_id),
+					<Assets as metadata::Inspect<AccountId>>::decimals(&asset_id),
+				);
+				env.write(&metadata.encode(), false, None)?;
+				Ok(())
+			},
+		};
+
+		match result {
+			Ok(()) => Ok(AssetsError::Success.into()),
+			Err(e) => AssetsError::try_from(e).map(Into::into),
+		}
+	}
+}
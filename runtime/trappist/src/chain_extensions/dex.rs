@@ -0,0 +1,205 @@
+use crate::{
+	AssetBalance, AssetId, Balance, BlockNumber, Dex, Runtime, RuntimeCall, RuntimeOrigin,
+};
+use codec::{Decode, Encode};
+use frame_support::{dispatch::DispatchError, storage::with_storage_layer, traits::Get};
+use pallet_contracts::chain_extension::{
+	BufInBufOutState, ChainExtension, Environment, Ext, InitState, RegisteredChainExtension, RetVal,
+};
+use pallet_dex::{TradeAmount, WeightInfo};
+use sp_runtime::traits::Dispatchable;
+
+/// Status codes returned to contracts by [`DexExtension`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u32)]
+pub enum DexError {
+	Success = 0,
+	/// The swap was rejected by `pallet_dex`. The output buffer holds the SCALE encoded
+	/// `DispatchError`.
+	SwapFailed = 1,
+	/// No quote could be computed, e.g. because the exchange does not exist or lacks liquidity.
+	QuoteUnavailable = 2,
+}
+
+impl From<DexError> for RetVal {
+	fn from(error: DexError) -> Self {
+		RetVal::Converging(error as u32)
+	}
+}
+
+/// Functions of [`DexExtension`], identified by the lower 16 bits of the id passed to
+/// `seal_call_chain_extension`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u16)]
+pub enum DexFunc {
+	/// `(AssetId, TradeAmount<Balance, AssetBalance>, BlockNumber)`: swaps the contract's native
+	/// currency for the given asset before the given deadline.
+	CurrencyToAsset = 0,
+	/// `(AssetId, TradeAmount<AssetBalance, Balance>, BlockNumber)`: swaps the given asset held
+	/// by the contract for native currency before the given deadline.
+	AssetToCurrency = 1,
+	/// `(AssetId, Balance) -> AssetBalance`
+	CurrencyToAssetOutputAmount = 2,
+	/// `(AssetId, AssetBalance) -> Balance`
+	CurrencyToAssetInputAmount = 3,
+	/// `(AssetId, AssetBalance) -> Balance`
+	AssetToCurrencyOutputAmount = 4,
+	/// `(AssetId, Balance) -> AssetBalance`
+	AssetToCurrencyInputAmount = 5,
+}
+
+impl TryFrom<u16> for DexFunc {
+	type Error = DispatchError;
+
+	fn try_from(func_id: u16) -> Result<Self, Self::Error> {
+		match func_id {
+			0 => Ok(Self::CurrencyToAsset),
+			1 => Ok(Self::AssetToCurrency),
+			2 => Ok(Self::CurrencyToAssetOutputAmount),
+			3 => Ok(Self::CurrencyToAssetInputAmount),
+			4 => Ok(Self::AssetToCurrencyOutputAmount),
+			5 => Ok(Self::AssetToCurrencyInputAmount),
+			_ => Err(DispatchError::Other("Unimplemented func_id")),
+		}
+	}
+}
+
+/// Exposes `pallet_dex` swaps and the quotes behind `DexApi` to ink! contracts.
+///
+/// Swaps are made with the calling contract as trader and recipient.
+#[derive(Default)]
+pub struct DexExtension;
+
+impl RegisteredChainExtension<Runtime> for DexExtension {
+	const ID: u16 = 2;
+}
+
+impl ChainExtension<Runtime> for DexExtension {
+	fn call<E: Ext<T = Runtime>>(
+		&mut self,
+		env: Environment<E, InitState>,
+	) -> Result<RetVal, DispatchError> {
+		let mut env = env.buf_in_buf_out();
+		match DexFunc::try_from(env.func_id())? {
+			DexFunc::CurrencyToAsset => {
+				env.charge_weight(<Runtime as pallet_dex::Config>::WeightInfo::currency_to_asset())?;
+				let (asset_id, amount, deadline): (
+					AssetId,
+					TradeAmount<Balance, AssetBalance>,
+					BlockNumber,
+				) = read_input(&mut env)?;
+				let call = pallet_dex::Call::currency_to_asset {
+					asset_id,
+					amount,
+					deadline,
+					recipient: None,
+				};
+
+				swap(&mut env, call)
+			},
+			DexFunc::AssetToCurrency => {
+				env.charge_weight(<Runtime as pallet_dex::Config>::WeightInfo::asset_to_currency())?;
+				let (asset_id, amount, deadline): (
+					AssetId,
+					TradeAmount<AssetBalance, Balance>,
+					BlockNumber,
+				) = read_input(&mut env)?;
+				let call = pallet_dex::Call::asset_to_currency {
+					asset_id,
+					amount,
+					deadline,
+					recipient: None,
+				};
+
+				swap(&mut env, call)
+			},
+			DexFunc::CurrencyToAssetOutputAmount => {
+				charge_quote(&mut env)?;
+				let (asset_id, currency_amount): (AssetId, Balance) = env.read_as()?;
+				quote_result(
+					&mut env,
+					Dex::get_currency_to_asset_output_amount(asset_id, currency_amount),
+				)
+			},
+			DexFunc::CurrencyToAssetInputAmount => {
+				charge_quote(&mut env)?;
+				let (asset_id, token_amount): (AssetId, AssetBalance) = env.read_as()?;
+				quote_result(
+					&mut env,
+					Dex::get_currency_to_asset_input_amount(asset_id, token_amount),
+				)
+			},
+			DexFunc::AssetToCurrencyOutputAmount => {
+				charge_quote(&mut env)?;
+				let (asset_id, token_amount): (AssetId, AssetBalance) = env.read_as()?;
+				quote_result(
+					&mut env,
+					Dex::get_asset_to_currency_output_amount(asset_id, token_amount),
+				)
+			},
+			DexFunc::AssetToCurrencyInputAmount => {
+				charge_quote(&mut env)?;
+				let (asset_id, currency_amount): (AssetId, Balance) = env.read_as()?;
+				quote_result(
+					&mut env,
+					Dex::get_asset_to_currency_input_amount(asset_id, currency_amount),
+				)
+			},
+		}
+	}
+}
+
+/// Quotes read the exchange and the asset metadata needed to price it.
+fn charge_quote<E: Ext<T = Runtime>>(
+	env: &mut Environment<E, BufInBufOutState>,
+) -> Result<(), DispatchError> {
+	env.charge_weight(<Runtime as frame_system::Config>::DbWeight::get().reads(2))?;
+	Ok(())
+}
+
+/// Swaps take variable length `TradeAmount`s, so reading them is charged per byte before they
+/// are decoded.
+fn read_input<E: Ext<T = Runtime>, V: Decode>(
+	env: &mut Environment<E, BufInBufOutState>,
+) -> Result<V, DispatchError> {
+	let len = env.in_len();
+	env.charge_weight(
+		<Runtime as pallet_contracts::Config>::Schedule::get()
+			.host_fn_weights
+			.input_per_byte
+			.saturating_mul(len.into()),
+	)?;
+	env.read_as_unbounded(len)
+}
+
+/// Dispatches `call` as the contract, so it is subject to the `BaseCallFilter` of the runtime
+/// like the calls of `seal_call_runtime`.
+fn swap<E: Ext<T = Runtime>>(
+	env: &mut Environment<E, BufInBufOutState>,
+	call: pallet_dex::Call<Runtime>,
+) -> Result<RetVal, DispatchError> {
+	let origin = RuntimeOrigin::signed(env.ext().address().clone());
+	let result = with_storage_layer(|| {
+		RuntimeCall::Dex(call).dispatch(origin).map(|_| ()).map_err(|e| e.error)
+	});
+	match result {
+		Ok(()) => Ok(DexError::Success.into()),
+		Err(e) => {
+			env.write(&e.encode(), false, None)?;
+			Ok(DexError::SwapFailed.into())
+		},
+	}
+}
+
+fn quote_result<E: Ext<T = Runtime>, V: Encode, R>(
+	env: &mut Environment<E, BufInBufOutState>,
+	result: Result<V, R>,
+) -> Result<RetVal, DispatchError> {
+	match result {
+		Ok(amount) => {
+			env.write(&amount.encode(), false, None)?;
+			Ok(DexError::Success.into())
+		},
+		Err(_) => Ok(DexError::QuoteUnavailable.into()),
+	}
+}
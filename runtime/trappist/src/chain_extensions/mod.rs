@@ -0,0 +1,10 @@
+//! Chain extensions exposing runtime functionality to ink! contracts.
+
+mod assets;
+mod dex;
+
+pub use assets::{AssetsError, AssetsExtension, AssetsFunc};
+pub use dex::{DexError, DexExtension, DexFunc};
+
+#[cfg(test)]
+mod tests;
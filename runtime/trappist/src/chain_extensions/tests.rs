@@ -0,0 +1,486 @@
+use super::{AssetsError, AssetsExtension, AssetsFunc, DexError, DexExtension, DexFunc};
+use crate::{
+	constants::currency::UNITS, AccountId, AssetBalance, AssetId, Assets, Balance, Balances,
+	BlockNumber, Contracts, Dex, Runtime, RuntimeOrigin, System, TxPause,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_ok,
+	dispatch::DispatchError,
+	traits::{fungibles::approvals::Inspect as _, GenesisBuild},
+	weights::Weight,
+};
+use pallet_assets::WeightInfo;
+use pallet_contracts::{chain_extension::RegisteredChainExtension, Determinism};
+use pallet_contracts_primitives::{Code, ExecReturnValue};
+use pallet_dex::TradeAmount;
+use xcm::{latest::prelude::*, VersionedMultiLocation, VersionedXcm};
+use xcm_primitives::{XcmExtension, XcmExtensionError, XcmFunc};
+
+const ALICE: AccountId = AccountId::new([1u8; 32]);
+const BOB: AccountId = AccountId::new([2u8; 32]);
+const INITIAL_BALANCE: u128 = 1_000 * UNITS;
+
+const ASSET_ID: AssetId = 1;
+
+const GAS_LIMIT: Weight = Weight::from_ref_time(100_000_000_000).set_proof_size(256 * 1024);
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+	pallet_balances::GenesisConfig::<Runtime> {
+		balances: vec![(ALICE, INITIAL_BALANCE), (BOB, INITIAL_BALANCE)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Assets::force_create(
+			RuntimeOrigin::root(),
+			ASSET_ID.into(),
+			ALICE.into(),
+			true,
+			1
+		));
+	});
+	ext
+}
+
+/// Deploys the fixture contract, endowing it with enough native balance to cover deposits.
+fn deploy_fixture() -> AccountId {
+	let wasm =
+		wat::parse_file(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/chain_extension.wat"))
+			.expect("fixture should be valid wat");
+
+	Contracts::bare_instantiate(
+		ALICE,
+		UNITS,
+		GAS_LIMIT,
+		None,
+		Code::Upload(wasm),
+		vec![],
+		vec![],
+		false,
+	)
+	.result
+	.expect("fixture should instantiate")
+	.account_id
+}
+
+/// A function of one of the runtime's chain extensions.
+trait ExtensionFunc {
+	/// The id passed to `seal_call_chain_extension`: extension id in the upper 16 bits, function
+	/// id in the lower 16 bits.
+	fn id(self) -> u32;
+}
+
+impl ExtensionFunc for AssetsFunc {
+	fn id(self) -> u32 {
+		((<AssetsExtension as RegisteredChainExtension<Runtime>>::ID as u32) << 16) | self as u32
+	}
+}
+
+impl ExtensionFunc for DexFunc {
+	fn id(self) -> u32 {
+		((<DexExtension as RegisteredChainExtension<Runtime>>::ID as u32) << 16) | self as u32
+	}
+}
+
+impl ExtensionFunc for XcmFunc {
+	fn id(self) -> u32 {
+		((<XcmExtension<Runtime> as RegisteredChainExtension<Runtime>>::ID as u32) << 16) |
+			self as u32
+	}
+}
+
+/// Calls `func` through the fixture contract, returning the status code and the output of the
+/// extension.
+fn call_extension(
+	contract: &AccountId,
+	func: impl ExtensionFunc,
+	input: impl Encode,
+) -> (u32, Vec<u8>) {
+	let ExecReturnValue { data, .. } = call_extension_raw(contract, func.id(), input)
+		.result
+		.expect("extension call should not trap");
+	let status = u32::decode(&mut &data[..4]).unwrap();
+	(status, data[4..].to_vec())
+}
+
+fn call_extension_raw(
+	contract: &AccountId,
+	id: u32,
+	input: impl Encode,
+) -> pallet_contracts_primitives::ContractExecResult<u128> {
+	let mut data = id.encode();
+	data.extend(input.encode());
+
+	Contracts::bare_call(
+		ALICE,
+		contract.clone(),
+		0,
+		GAS_LIMIT,
+		None,
+		data,
+		false,
+		Determinism::Deterministic,
+	)
+}
+
+fn mint(who: &AccountId, amount: AssetBalance) {
+	assert_ok!(Assets::mint(
+		RuntimeOrigin::signed(ALICE),
+		ASSET_ID.into(),
+		who.clone().into(),
+		amount
+	));
+}
+
+#[test]
+fn balance_works() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy_fixture();
+		mint(&BOB, 100);
+
+		let (status, output) = call_extension(&contract, AssetsFunc::Balance, (ASSET_ID, BOB));
+		assert_eq!(status, AssetsError::Success as u32);
+		assert_eq!(AssetBalance::decode(&mut &output[..]).unwrap(), 100);
+
+		let (status, output) = call_extension(&contract, AssetsFunc::Balance, (2 as AssetId, BOB));
+		assert_eq!(status, AssetsError::Success as u32);
+		assert_eq!(AssetBalance::decode(&mut &output[..]).unwrap(), 0);
+	});
+}
+
+#[test]
+fn transfer_works() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy_fixture();
+		mint(&contract, 100);
+
+		let result = call_extension_raw(
+			&contract,
+			AssetsFunc::Transfer.id(),
+			(ASSET_ID, BOB, 40 as AssetBalance),
+		);
+		assert!(
+			result.gas_consumed.ref_time() >=
+				<Runtime as pallet_assets::Config>::WeightInfo::transfer().ref_time()
+		);
+		assert_eq!(u32::decode(&mut &result.result.unwrap().data[..4]).unwrap(), 0);
+
+		assert_eq!(Assets::balance(ASSET_ID, &contract), 60);
+		assert_eq!(Assets::balance(ASSET_ID, &BOB), 40);
+	});
+}
+
+#[test]
+fn transfer_more_than_balance_fails() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy_fixture();
+		mint(&contract, 100);
+
+		let (status, _) =
+			call_extension(&contract, AssetsFunc::Transfer, (ASSET_ID, BOB, 101 as AssetBalance));
+		assert_eq!(status, AssetsError::BalanceLow as u32);
+		assert_eq!(Assets::balance(ASSET_ID, &contract), 100);
+		assert_eq!(Assets::balance(ASSET_ID, &BOB), 0);
+	});
+}
+
+#[test]
+fn transfer_of_unknown_asset_fails() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy_fixture();
+
+		let (status, _) =
+			call_extension(&contract, AssetsFunc::Transfer, (2 as AssetId, BOB, 1 as AssetBalance));
+		assert_eq!(status, AssetsError::Unknown as u32);
+	});
+}
+
+#[test]
+fn approve_works() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy_fixture();
+		mint(&contract, 100);
+
+		let (status, _) =
+			call_extension(&contract, AssetsFunc::Approve, (ASSET_ID, BOB, 50 as AssetBalance));
+		assert_eq!(status, AssetsError::Success as u32);
+		assert_eq!(Assets::allowance(ASSET_ID, &contract, &BOB), 50);
+
+		assert_ok!(Assets::transfer_approved(
+			RuntimeOrigin::signed(BOB),
+			ASSET_ID.into(),
+			contract.clone().into(),
+			BOB.into(),
+			50
+		));
+		assert_eq!(Assets::balance(ASSET_ID, &BOB), 50);
+	});
+}
+
+#[test]
+fn transfer_approved_works() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy_fixture();
+		mint(&ALICE, 100);
+		assert_ok!(Assets::approve_transfer(
+			RuntimeOrigin::signed(ALICE),
+			ASSET_ID.into(),
+			contract.clone().into(),
+			50
+		));
+
+		let (status, _) = call_extension(
+			&contract,
+			AssetsFunc::TransferApproved,
+			(ASSET_ID, ALICE, BOB, 30 as AssetBalance),
+		);
+		assert_eq!(status, AssetsError::Success as u32);
+		assert_eq!(Assets::balance(ASSET_ID, &ALICE), 70);
+		assert_eq!(Assets::balance(ASSET_ID, &BOB), 30);
+		assert_eq!(Assets::allowance(ASSET_ID, &ALICE, &contract), 20);
+
+		let (status, _) = call_extension(
+			&contract,
+			AssetsFunc::TransferApproved,
+			(ASSET_ID, ALICE, BOB, 30 as AssetBalance),
+		);
+		assert_eq!(status, AssetsError::Unapproved as u32);
+		assert_eq!(Assets::balance(ASSET_ID, &BOB), 30);
+	});
+}
+
+#[test]
+fn metadata_works() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy_fixture();
+		assert_ok!(Assets::force_set_metadata(
+			RuntimeOrigin::root(),
+			ASSET_ID.into(),
+			b"Trappist USD".to_vec(),
+			b"xUSD".to_vec(),
+			6,
+			false
+		));
+
+		let (status, output) = call_extension(&contract, AssetsFunc::Metadata, ASSET_ID);
+		assert_eq!(status, AssetsError::Success as u32);
+		assert_eq!(
+			<(Vec<u8>, Vec<u8>, u8)>::decode(&mut &output[..]).unwrap(),
+			(b"Trappist USD".to_vec(), b"xUSD".to_vec(), 6)
+		);
+	});
+}
+
+#[test]
+fn unknown_func_id_traps() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy_fixture();
+
+		let id = ((<AssetsExtension as RegisteredChainExtension<Runtime>>::ID as u32) << 16) | 42;
+		assert!(call_extension_raw(&contract, id, ()).result.is_err());
+	});
+}
+
+fn create_exchange() {
+	mint(&ALICE, 100 * UNITS);
+	assert_ok!(Dex::create_exchange(
+		RuntimeOrigin::signed(ALICE),
+		ASSET_ID,
+		10 * UNITS,
+		100 * UNITS
+	));
+}
+
+#[test]
+fn dex_quotes_work() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy_fixture();
+		create_exchange();
+
+		let (status, output) =
+			call_extension(&contract, DexFunc::CurrencyToAssetOutputAmount, (ASSET_ID, UNITS));
+		assert_eq!(status, DexError::Success as u32);
+		assert_eq!(
+			AssetBalance::decode(&mut &output[..]).unwrap(),
+			Dex::get_currency_to_asset_output_amount(ASSET_ID, UNITS).unwrap()
+		);
+
+		let (status, output) =
+			call_extension(&contract, DexFunc::CurrencyToAssetInputAmount, (ASSET_ID, UNITS));
+		assert_eq!(status, DexError::Success as u32);
+		assert_eq!(
+			Balance::decode(&mut &output[..]).unwrap(),
+			Dex::get_currency_to_asset_input_amount(ASSET_ID, UNITS).unwrap()
+		);
+
+		let (status, output) =
+			call_extension(&contract, DexFunc::AssetToCurrencyOutputAmount, (ASSET_ID, UNITS));
+		assert_eq!(status, DexError::Success as u32);
+		assert_eq!(
+			Balance::decode(&mut &output[..]).unwrap(),
+			Dex::get_asset_to_currency_output_amount(ASSET_ID, UNITS).unwrap()
+		);
+
+		let (status, output) =
+			call_extension(&contract, DexFunc::AssetToCurrencyInputAmount, (ASSET_ID, UNITS));
+		assert_eq!(status, DexError::Success as u32);
+		assert_eq!(
+			AssetBalance::decode(&mut &output[..]).unwrap(),
+			Dex::get_asset_to_currency_input_amount(ASSET_ID, UNITS).unwrap()
+		);
+	});
+}
+
+#[test]
+fn dex_quote_without_exchange_is_unavailable() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy_fixture();
+
+		let (status, output) =
+			call_extension(&contract, DexFunc::CurrencyToAssetOutputAmount, (ASSET_ID, UNITS));
+		assert_eq!(status, DexError::QuoteUnavailable as u32);
+		assert!(output.is_empty());
+	});
+}
+
+#[test]
+fn dex_currency_to_asset_works() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy_fixture();
+		create_exchange();
+		let currency_amount = UNITS / 10;
+		let expected = Dex::get_currency_to_asset_output_amount(ASSET_ID, currency_amount).unwrap();
+
+		let (status, _) = call_extension(
+			&contract,
+			DexFunc::CurrencyToAsset,
+			(
+				ASSET_ID,
+				TradeAmount::<Balance, AssetBalance>::FixedInput {
+					input_amount: currency_amount,
+					min_output: expected,
+				},
+				10 as BlockNumber,
+			),
+		);
+		assert_eq!(status, DexError::Success as u32);
+		assert_eq!(Assets::balance(ASSET_ID, &contract), expected);
+	});
+}
+
+#[test]
+fn dex_asset_to_currency_works() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy_fixture();
+		create_exchange();
+		mint(&contract, UNITS);
+		let expected = Dex::get_asset_to_currency_output_amount(ASSET_ID, UNITS).unwrap();
+		let balance_before = Balances::free_balance(&contract);
+
+		let (status, _) = call_extension(
+			&contract,
+			DexFunc::AssetToCurrency,
+			(
+				ASSET_ID,
+				TradeAmount::<AssetBalance, Balance>::FixedInput {
+					input_amount: UNITS,
+					min_output: expected,
+				},
+				10 as BlockNumber,
+			),
+		);
+		assert_eq!(status, DexError::Success as u32);
+		assert_eq!(Assets::balance(ASSET_ID, &contract), 0);
+		assert_eq!(Balances::free_balance(&contract), balance_before + expected);
+	});
+}
+
+#[test]
+fn dex_failed_swap_returns_dispatch_error() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy_fixture();
+		create_exchange();
+		mint(&contract, UNITS);
+
+		// the deadline has already passed
+		let (status, output) = call_extension(
+			&contract,
+			DexFunc::AssetToCurrency,
+			(
+				ASSET_ID,
+				TradeAmount::<AssetBalance, Balance>::FixedInput {
+					input_amount: UNITS,
+					min_output: 1,
+				},
+				0 as BlockNumber,
+			),
+		);
+		assert_eq!(status, DexError::SwapFailed as u32);
+		assert!(matches!(DispatchError::decode(&mut &output[..]), Ok(DispatchError::Module(_))));
+		assert_eq!(Assets::balance(ASSET_ID, &contract), UNITS);
+	});
+}
+
+#[test]
+fn dex_swap_is_subject_to_the_call_filter() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy_fixture();
+		create_exchange();
+		mint(&contract, UNITS);
+		assert_ok!(TxPause::pause(
+			frame_system::RawOrigin::Root.into(),
+			b"Dex".to_vec().try_into().unwrap(),
+			None
+		));
+
+		let (status, output) = call_extension(
+			&contract,
+			DexFunc::AssetToCurrency,
+			(
+				ASSET_ID,
+				TradeAmount::<AssetBalance, Balance>::FixedInput {
+					input_amount: UNITS,
+					min_output: 1,
+				},
+				10 as BlockNumber,
+			),
+		);
+		assert_eq!(status, DexError::SwapFailed as u32);
+		assert_eq!(
+			DispatchError::decode(&mut &output[..]),
+			Ok(frame_system::Error::<Runtime>::CallFiltered.into())
+		);
+		assert_eq!(Assets::balance(ASSET_ID, &contract), UNITS);
+	});
+}
+
+#[test]
+fn xcm_send_is_subject_to_the_call_filter() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy_fixture();
+		assert_ok!(TxPause::pause(
+			frame_system::RawOrigin::Root.into(),
+			b"PolkadotXcm".to_vec().try_into().unwrap(),
+			Some(b"send".to_vec().try_into().unwrap())
+		));
+
+		let (status, output) = call_extension(
+			&contract,
+			XcmFunc::Send,
+			(
+				VersionedMultiLocation::from(MultiLocation::parent()),
+				VersionedXcm::<()>::from(Xcm(vec![ClearOrigin])),
+			),
+		);
+		assert_eq!(status, XcmExtensionError::Rejected as u32);
+		assert_eq!(
+			DispatchError::decode(&mut &output[..]),
+			Ok(frame_system::Error::<Runtime>::CallFiltered.into())
+		);
+	});
+}
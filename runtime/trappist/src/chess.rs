@@ -0,0 +1,157 @@
+//! The state of the games of `pallet_chess`, served by `ChessApi`.
+//!
+//! Boards are stored by `pallet_chess` in FEN, and read with the same chess library it plays
+//! moves with.
+
+use crate::{AccountId, AssetBalance, AssetId, BlockNumber, Hash, Runtime, System};
+use chess_primitives::OpenMatch;
+use cozy_chess::{Board, Color, Move};
+use frame_support::traits::Get;
+use pallet_chess::{MatchState, MatchStyle, Matches};
+use sp_runtime::traits::Saturating;
+use sp_std::prelude::*;
+
+fn parse(fen: &[u8]) -> Option<Board> {
+	sp_std::str::from_utf8(fen).ok()?.parse().ok()
+}
+
+/// The board of the match `match_id`, if it is being played.
+fn ongoing_board(match_id: Hash) -> Option<Board> {
+	let chess_match = Matches::<Runtime>::get(match_id)?;
+	match chess_match.state {
+		MatchState::OnGoing(_) => parse(&chess_match.board),
+		_ => None,
+	}
+}
+
+/// `mv` in UCI, in which `pallet_chess` takes moves.
+///
+/// Castling is written as the king taking its own rook, e.g. `e1h1`.
+fn uci(mv: Move) -> Vec<u8> {
+	let mut uci = Vec::with_capacity(5);
+	for square in [mv.from, mv.to] {
+		uci.push(b'a' + square.file() as u8);
+		uci.push(b'1' + square.rank() as u8);
+	}
+	if let Some(piece) = mv.promotion {
+		uci.push(b"pnbrqk"[piece as usize]);
+	}
+	uci
+}
+
+fn moves(board: &Board) -> Vec<Vec<u8>> {
+	let mut moves = Vec::new();
+	board.generate_moves(|piece_moves| {
+		moves.extend(piece_moves.into_iter().map(uci));
+		false
+	});
+	moves
+}
+
+pub fn board(match_id: Hash) -> Option<Vec<u8>> {
+	Matches::<Runtime>::get(match_id).map(|chess_match| chess_match.board.into_inner())
+}
+
+pub fn turn(match_id: Hash) -> Option<AccountId> {
+	let board = ongoing_board(match_id)?;
+	let chess_match = Matches::<Runtime>::get(match_id)?;
+	// the challenger plays white
+	Some(match board.side_to_move() {
+		Color::White => chess_match.challenger,
+		Color::Black => chess_match.opponent,
+	})
+}
+
+/// The blocks left of the period of the time control of the match `match_id` since its last
+/// move, after which the player to move can be timed out.
+pub fn clock(match_id: Hash) -> Option<BlockNumber> {
+	ongoing_board(match_id)?;
+	let chess_match = Matches::<Runtime>::get(match_id)?;
+	let period: BlockNumber = match chess_match.style {
+		MatchStyle::Bullet => <Runtime as pallet_chess::Config>::BulletPeriod::get(),
+		MatchStyle::Blitz => <Runtime as pallet_chess::Config>::BlitzPeriod::get(),
+		MatchStyle::Rapid => <Runtime as pallet_chess::Config>::RapidPeriod::get(),
+		MatchStyle::Daily => <Runtime as pallet_chess::Config>::DailyPeriod::get(),
+	};
+	let elapsed = System::block_number().saturating_sub(chess_match.last_move);
+	Some(period.saturating_sub(elapsed))
+}
+
+pub fn legal_moves(match_id: Hash) -> Vec<Vec<u8>> {
+	ongoing_board(match_id).map(|board| moves(&board)).unwrap_or_default()
+}
+
+/// The most matches `open_matches` reads at once.
+pub const MAX_MATCHES_READ: u32 = 1_000;
+
+/// The open matches of time control `style` among the next `count` matches after `start_after`,
+/// and the last match read if more may be left.
+///
+/// `pallet_chess` doesn't index matches by state or time control, so they are read in pages of at
+/// most `MAX_MATCHES_READ` matches, in storage order.
+pub fn open_matches(
+	style: MatchStyle,
+	start_after: Option<Hash>,
+	count: u32,
+) -> (Vec<OpenMatch<Hash, AccountId, AssetId, AssetBalance>>, Option<Hash>) {
+	let mut matches = match start_after {
+		Some(match_id) => {
+			Matches::<Runtime>::iter_from(Matches::<Runtime>::hashed_key_for(match_id))
+		},
+		None => Matches::<Runtime>::iter(),
+	};
+	let mut open_matches = Vec::new();
+	let mut last = None;
+	for _ in 0..count.clamp(1, MAX_MATCHES_READ) {
+		let (match_id, chess_match) = match matches.next() {
+			Some(entry) => entry,
+			None => return (open_matches, None),
+		};
+		last = Some(match_id);
+		if chess_match.style == style && matches!(chess_match.state, MatchState::AwaitingOpponent) {
+			open_matches.push(OpenMatch {
+				match_id,
+				challenger: chess_match.challenger,
+				opponent: chess_match.opponent,
+				bet_asset_id: chess_match.bet_asset_id,
+				bet_amount: chess_match.bet_amount,
+			});
+		}
+	}
+	(open_matches, last)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn legal_moves(fen: &str) -> Vec<Vec<u8>> {
+		moves(&parse(fen.as_bytes()).unwrap())
+	}
+
+	#[test]
+	fn legal_moves_are_in_uci() {
+		let moves = legal_moves("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+		assert_eq!(moves.len(), 20);
+		assert!(moves.contains(&b"e2e4".to_vec()));
+		assert!(moves.contains(&b"g1f3".to_vec()));
+
+		let moves = legal_moves("4k3/P7/8/8/8/8/8/4K2R w K - 0 1");
+		assert!(moves.contains(&b"a7a8q".to_vec()));
+		assert!(moves.contains(&b"a7a8n".to_vec()));
+		assert!(moves.contains(&b"e1h1".to_vec()));
+	}
+
+	#[test]
+	fn checkmated_players_have_no_legal_moves() {
+		// fool's mate
+		let moves = legal_moves("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3");
+		assert!(moves.is_empty());
+	}
+
+	#[test]
+	fn invalid_boards_are_not_parsed() {
+		assert!(parse(b"not a board").is_none());
+		assert!(parse(&[0xff]).is_none());
+	}
+}
@@ -1,21 +1,43 @@
 use crate::{
-	constants::currency::deposit, Balance, Balances, RandomnessCollectiveFlip, Runtime,
-	RuntimeBlockWeights, RuntimeCall, RuntimeEvent, Timestamp,
+	chain_extensions::{AssetsExtension, DexExtension},
+	constants::currency::deposit,
+	Balance, Balances, ContractsAllowlist, Hash, RandomnessCollectiveFlip, Runtime,
+	RuntimeBlockWeights, RuntimeCall, RuntimeEvent, System, Timestamp,
 };
 use frame_support::{
 	parameter_types,
-	traits::{ConstBool, ConstU32, Nothing},
+	storage::KeyPrefixIterator,
+	traits::{ConstBool, ConstU32, Contains},
 	weights::Weight,
+	Identity,
 };
 use pallet_contracts::{
 	weights::{SubstrateWeight, WeightInfo},
 	Config, DefaultAddressGenerator, Frame, Schedule,
 };
 pub use parachains_common::AVERAGE_ON_INITIALIZE_RATIO;
+use sp_core::LogLevelFilter;
+use sp_std::prelude::*;
+use xcm_primitives::XcmExtension;
 
-// Prints debug output of the `contracts` pallet to stdout if the node is
-// started with `-lruntime::contracts=debug`.
-pub const CONTRACTS_DEBUG_OUTPUT: bool = true;
+/// Whether `ContractsApi` dry-runs collect the debug buffer of contracts.
+///
+/// Only enabled if the node is started with `-lruntime::contracts=debug` (or any other debug
+/// logging), so production nodes don't pay for it. `ContractsDryRunApi` lets callers choose per
+/// call instead.
+pub fn contracts_debug_output() -> bool {
+	matches!(sp_io::logging::max_level(), LogLevelFilter::Debug | LogLevelFilter::Trace)
+}
+
+/// The events deposited by the contracts pallet since the events were last reset.
+pub fn contracts_events() -> Vec<pallet_contracts::Event<Runtime>> {
+	System::read_events_no_consensus()
+		.filter_map(|record| match record.event {
+			RuntimeEvent::Contracts(event) => Some(event),
+			_ => None,
+		})
+		.collect()
+}
 
 parameter_types! {
 	pub const DepositPerItem: Balance = deposit(1, 0);
@@ -32,24 +54,100 @@ parameter_types! {
 	pub MySchedule: Schedule<Runtime> = Default::default();
 }
 
+/// Runtime calls which contracts may dispatch through `seal_call_runtime`.
+///
+/// Contracts depend on the encoding of these calls, so only dispatchables we commit to keeping
+/// stable are listed. Batches are allowed as long as every call they contain is.
+pub struct ContractsCallFilter;
+impl Contains<RuntimeCall> for ContractsCallFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		match call {
+			RuntimeCall::Balances(
+				pallet_balances::Call::transfer { .. } |
+				pallet_balances::Call::transfer_keep_alive { .. },
+			) |
+			RuntimeCall::Assets(
+				pallet_assets::Call::transfer { .. } |
+				pallet_assets::Call::transfer_keep_alive { .. },
+			) |
+			RuntimeCall::Uniques(pallet_uniques::Call::transfer { .. }) |
+			RuntimeCall::Dex(
+				pallet_dex::Call::currency_to_asset { .. } |
+				pallet_dex::Call::asset_to_currency { .. },
+			) => true,
+			RuntimeCall::Utility(
+				pallet_utility::Call::batch { calls } | pallet_utility::Call::batch_all { calls },
+			) => calls.iter().all(Self::contains),
+			_ => false,
+		}
+	}
+}
+
+/// Rejects uploads and instantiations of contract code not allowed by `ContractsAllowlist`.
+///
+/// Part of the runtime's `BaseCallFilter`, so it also applies to calls nested in batches,
+/// multisigs or scheduled calls. Any other call is allowed. Contracts instantiating other
+/// contracts are not filtered, which is why `ContractsAllowlist` keeps all [`ContractsStoredCode`]
+/// approved while enforced.
+pub struct ContractsCodeFilter;
+impl Contains<RuntimeCall> for ContractsCodeFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		match call {
+			RuntimeCall::Contracts(
+				pallet_contracts::Call::upload_code { code, .. } |
+				pallet_contracts::Call::instantiate_with_code { code, .. } |
+				pallet_contracts::Call::instantiate_with_code_old_weight { code, .. },
+			) => ContractsAllowlist::is_code_allowed(code),
+			RuntimeCall::Contracts(
+				pallet_contracts::Call::instantiate { code_hash, .. } |
+				pallet_contracts::Call::instantiate_old_weight { code_hash, .. },
+			) => ContractsAllowlist::is_allowed(code_hash),
+			_ => true,
+		}
+	}
+}
+
+/// The original code uploaded to `pallet_contracts`, by code hash.
+#[frame_support::storage_alias]
+type PristineCode = StorageMap<Contracts, Identity, Hash, Vec<u8>>;
+
+/// The contract code stored by `pallet_contracts`, which contracts can instantiate.
+pub struct ContractsStoredCode;
+impl pallet_contracts_allowlist::StoredCode<Hash> for ContractsStoredCode {
+	type CodeHashes = KeyPrefixIterator<Hash>;
+
+	fn contains(code_hash: &Hash) -> bool {
+		PristineCode::contains_key(code_hash)
+	}
+
+	fn code_hashes() -> Self::CodeHashes {
+		PristineCode::iter_keys()
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn insert(code_hash: Hash) {
+		PristineCode::insert(code_hash, Vec::<u8>::new());
+	}
+}
+
 impl Config for Runtime {
 	type Time = Timestamp;
 	type Randomness = RandomnessCollectiveFlip;
 	type Currency = Balances;
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
-	/// The safest default is to allow no calls at all.
+	/// Only the stable calls listed in [`ContractsCallFilter`] are allowed.
 	///
 	/// Runtimes should whitelist dispatchables that are allowed to be called from contracts
 	/// and make sure they are stable. Dispatchables exposed to contracts are not allowed to
 	/// change because that would break already deployed contracts. The `Call` structure itself
 	/// is not allowed to change the indices of existing pallets, too.
-	type CallFilter = Nothing;
+	type CallFilter = ContractsCallFilter;
 	type DepositPerItem = DepositPerItem;
 	type DepositPerByte = DepositPerByte;
 	type WeightPrice = pallet_transaction_payment::Pallet<Self>;
 	type WeightInfo = SubstrateWeight<Self>;
-	type ChainExtension = ();
+	type ChainExtension = (AssetsExtension, DexExtension, XcmExtension<Self>);
 	type DeletionQueueDepth = DeletionQueueDepth;
 	type DeletionWeightLimit = DeletionWeightLimit;
 	type Schedule = MySchedule;
@@ -60,3 +158,253 @@ impl Config for Runtime {
 	type UnsafeUnstableInterface = ConstBool<true>;
 	type MaxDebugBufferLen = ConstU32<{ 2 * 1024 * 1024 }>;
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{constants::currency::UNITS, AccountId, Contracts, RuntimeOrigin};
+	use codec::{Decode, Encode};
+	use frame_support::{assert_noop, assert_ok, traits::GenesisBuild};
+	use pallet_contracts::Determinism;
+	use pallet_contracts_primitives::{Code, ExecReturnValue};
+	use sp_runtime::traits::{BlakeTwo256, Dispatchable, Hash as _};
+
+	const ALICE: AccountId = AccountId::new([1u8; 32]);
+	const BOB: AccountId = AccountId::new([2u8; 32]);
+
+	fn balances_transfer() -> RuntimeCall {
+		RuntimeCall::Balances(pallet_balances::Call::transfer { dest: BOB.into(), value: 1 })
+	}
+
+	fn batch(calls: Vec<RuntimeCall>) -> RuntimeCall {
+		RuntimeCall::Utility(pallet_utility::Call::batch { calls })
+	}
+
+	#[test]
+	fn call_filter_allows_listed_calls() {
+		assert!(ContractsCallFilter::contains(&balances_transfer()));
+		assert!(ContractsCallFilter::contains(&RuntimeCall::Assets(
+			pallet_assets::Call::transfer_keep_alive {
+				id: 1.into(),
+				target: BOB.into(),
+				amount: 1
+			}
+		)));
+		assert!(ContractsCallFilter::contains(&RuntimeCall::Uniques(
+			pallet_uniques::Call::transfer { collection: 1, item: 1, dest: BOB.into() }
+		)));
+		assert!(ContractsCallFilter::contains(&RuntimeCall::Dex(
+			pallet_dex::Call::currency_to_asset {
+				asset_id: 1,
+				amount: pallet_dex::TradeAmount::FixedInput { input_amount: 1, min_output: 1 },
+				deadline: 1,
+				recipient: None,
+			}
+		)));
+	}
+
+	#[test]
+	fn call_filter_rejects_unlisted_calls() {
+		assert!(!ContractsCallFilter::contains(&RuntimeCall::System(frame_system::Call::remark {
+			remark: vec![]
+		})));
+		assert!(!ContractsCallFilter::contains(&RuntimeCall::Balances(
+			pallet_balances::Call::force_transfer {
+				source: BOB.into(),
+				dest: BOB.into(),
+				value: 1
+			}
+		)));
+		assert!(!ContractsCallFilter::contains(&RuntimeCall::Assets(pallet_assets::Call::mint {
+			id: 1.into(),
+			beneficiary: BOB.into(),
+			amount: 1
+		})));
+		assert!(!ContractsCallFilter::contains(&RuntimeCall::Sudo(pallet_sudo::Call::sudo {
+			call: Box::new(balances_transfer())
+		})));
+	}
+
+	#[test]
+	fn call_filter_checks_batched_calls() {
+		let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+
+		assert!(ContractsCallFilter::contains(&batch(vec![
+			balances_transfer(),
+			balances_transfer()
+		])));
+		assert!(!ContractsCallFilter::contains(&batch(vec![balances_transfer(), remark.clone()])));
+		assert!(!ContractsCallFilter::contains(&batch(vec![batch(vec![remark])])));
+		assert!(!ContractsCallFilter::contains(&RuntimeCall::Utility(
+			pallet_utility::Call::dispatch_as {
+				as_origin: Box::new(frame_system::RawOrigin::Root.into()),
+				call: Box::new(balances_transfer()),
+			}
+		)));
+	}
+
+	fn upload_code(code: &[u8]) -> RuntimeCall {
+		RuntimeCall::Contracts(pallet_contracts::Call::upload_code {
+			code: code.to_vec(),
+			storage_deposit_limit: None,
+			determinism: pallet_contracts::Determinism::Deterministic,
+		})
+	}
+
+	fn instantiate(code: &[u8]) -> RuntimeCall {
+		RuntimeCall::Contracts(pallet_contracts::Call::instantiate {
+			value: 0,
+			gas_limit: Weight::zero(),
+			storage_deposit_limit: None,
+			code_hash: BlakeTwo256::hash(code),
+			data: vec![],
+			salt: vec![],
+		})
+	}
+
+	fn instantiate_with_code(code: &[u8]) -> RuntimeCall {
+		RuntimeCall::Contracts(pallet_contracts::Call::instantiate_with_code {
+			value: 0,
+			gas_limit: Weight::zero(),
+			storage_deposit_limit: None,
+			code: code.to_vec(),
+			data: vec![],
+			salt: vec![],
+		})
+	}
+
+	#[test]
+	fn code_filter_is_permissive_when_not_enforced() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			assert!(ContractsCodeFilter::contains(&upload_code(b"code")));
+			assert!(ContractsCodeFilter::contains(&instantiate(b"code")));
+			assert!(ContractsCodeFilter::contains(&instantiate_with_code(b"code")));
+		});
+	}
+
+	#[test]
+	fn code_filter_only_allows_approved_code_when_enforced() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			let root = || frame_system::RawOrigin::Root.into();
+			assert_ok!(ContractsAllowlist::set_enforced(root(), true, 0));
+			assert_ok!(ContractsAllowlist::approve_code_hash(root(), BlakeTwo256::hash(b"code")));
+
+			assert!(ContractsCodeFilter::contains(&upload_code(b"code")));
+			assert!(ContractsCodeFilter::contains(&instantiate(b"code")));
+			assert!(ContractsCodeFilter::contains(&instantiate_with_code(b"code")));
+
+			assert!(!ContractsCodeFilter::contains(&upload_code(b"other code")));
+			assert!(!ContractsCodeFilter::contains(&instantiate(b"other code")));
+			assert!(!ContractsCodeFilter::contains(&instantiate_with_code(b"other code")));
+
+			// calls to already instantiated contracts are not affected
+			assert!(ContractsCodeFilter::contains(&balances_transfer()));
+		});
+	}
+
+	const GAS_LIMIT: Weight = Weight::from_ref_time(100_000_000_000).set_proof_size(256 * 1024);
+
+	fn fixture(name: &str) -> Vec<u8> {
+		wat::parse_file(format!("{}/fixtures/{}.wat", env!("CARGO_MANIFEST_DIR"), name))
+			.expect("fixture should be valid wat")
+	}
+
+	/// Instantiates the code of `code_hash` from `instantiator`, returning the return code of
+	/// `seal_instantiate`.
+	fn instantiate_from_contract(instantiator: &AccountId, code_hash: Hash) -> u32 {
+		let ExecReturnValue { data, .. } = Contracts::bare_call(
+			ALICE,
+			instantiator.clone(),
+			0,
+			GAS_LIMIT,
+			None,
+			code_hash.encode(),
+			false,
+			Determinism::Deterministic,
+		)
+		.result
+		.expect("fixture should not trap");
+		u32::decode(&mut &data[..]).unwrap()
+	}
+
+	#[test]
+	fn contracts_only_instantiate_approved_code() {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_balances::GenesisConfig::<Runtime> { balances: vec![(ALICE, 1_000 * UNITS)] }
+			.assimilate_storage(&mut t)
+			.unwrap();
+
+		sp_io::TestExternalities::new(t).execute_with(|| {
+			System::set_block_number(1);
+			let root = || frame_system::RawOrigin::Root.into();
+			let instantiator_code = fixture("instantiate");
+			let callee_code = fixture("chain_extension");
+			let callee_hash = BlakeTwo256::hash(&callee_code);
+			assert_ok!(ContractsAllowlist::approve_code_hash(
+				root(),
+				BlakeTwo256::hash(&instantiator_code)
+			));
+			assert_ok!(ContractsAllowlist::approve_code_hash(root(), callee_hash));
+			assert_ok!(ContractsAllowlist::set_enforced(root(), true, 0));
+
+			let instantiator = Contracts::bare_instantiate(
+				ALICE,
+				UNITS,
+				GAS_LIMIT,
+				None,
+				Code::Upload(instantiator_code),
+				vec![],
+				vec![],
+				false,
+			)
+			.result
+			.expect("fixture should instantiate")
+			.account_id;
+			assert_ok!(Contracts::bare_upload_code(
+				ALICE,
+				callee_code,
+				None,
+				Determinism::Deterministic
+			));
+
+			// approved code is instantiated by contracts
+			assert_eq!(instantiate_from_contract(&instantiator, callee_hash), 0);
+
+			// stored code stays approved
+			assert_noop!(
+				ContractsAllowlist::revoke_code_hash(root(), callee_hash),
+				pallet_contracts_allowlist::Error::<Runtime>::CodeIsStored
+			);
+
+			// unapproved code can't be uploaded, so contracts can't instantiate it
+			let other_code = wat::parse_str(
+				r#"(module
+					(import "env" "memory" (memory 1 1))
+					(func (export "deploy"))
+					(func (export "call"))
+				)"#,
+			)
+			.unwrap();
+			assert_noop!(
+				upload_code(&other_code)
+					.dispatch(RuntimeOrigin::signed(ALICE))
+					.map_err(|e| e.error),
+				frame_system::Error::<Runtime>::CallFiltered
+			);
+			assert_ne!(instantiate_from_contract(&instantiator, BlakeTwo256::hash(&other_code)), 0);
+
+			// nor can enforcement be enabled once unapproved code was uploaded without it
+			assert_ok!(ContractsAllowlist::set_enforced(root(), false, 0));
+			assert_ok!(Contracts::bare_upload_code(
+				ALICE,
+				other_code,
+				None,
+				Determinism::Deterministic
+			));
+			assert_noop!(
+				ContractsAllowlist::set_enforced(root(), true, 3),
+				pallet_contracts_allowlist::Error::<Runtime>::UnapprovedCodeStored
+			);
+		});
+	}
+}
@@ -21,8 +21,15 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+mod asset_fees;
+mod chain_extensions;
+mod chess;
 pub mod constants;
 mod contracts;
+pub mod migrations;
+mod proxy;
+mod recurring;
+mod tx_pause;
 pub mod xcm_config;
 
 use cumulus_pallet_parachain_system::RelayNumberStrictlyIncreases;
@@ -30,9 +37,9 @@ use sp_api::impl_runtime_apis;
 use sp_core::{crypto::KeyTypeId, ConstU8, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, ConvertInto},
+	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT},
 	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult,
+	ApplyExtrinsicResult, Perbill, Permill,
 };
 
 use sp_std::prelude::*;
@@ -46,8 +53,8 @@ use frame_support::{
 	dispatch::DispatchClass,
 	parameter_types,
 	traits::{
-		AsEnsureOriginWithArg, ConstU128, ConstU16, ConstU32, ConstU64, EitherOfDiverse,
-		EqualPrivilegeOnly, Everything,
+		AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU16, ConstU32, ConstU64, EitherOfDiverse,
+		EqualPrivilegeOnly,
 	},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight},
@@ -63,7 +70,7 @@ pub use parachains_common as common;
 pub use parachains_common::{
 	impls::{AssetsToBlockAuthor, DealWithFees},
 	opaque, AccountId, AssetId, AuraId, Balance, BlockNumber, Hash, Header, Index, Signature,
-	AVERAGE_ON_INITIALIZE_RATIO, HOURS, MAXIMUM_BLOCK_WEIGHT, MINUTES, NORMAL_DISPATCH_RATIO,
+	AVERAGE_ON_INITIALIZE_RATIO, DAYS, HOURS, MAXIMUM_BLOCK_WEIGHT, MINUTES, NORMAL_DISPATCH_RATIO,
 	SLOT_DURATION,
 };
 use xcm_config::{CollatorSelectionUpdateOrigin, RelayLocation};
@@ -165,7 +172,7 @@ parameter_types! {
 
 // Configure FRAME pallets to include in runtime.
 impl frame_system::Config for Runtime {
-	type BaseCallFilter = Everything;
+	type BaseCallFilter = tx_pause::BaseCallFilter;
 	type BlockWeights = RuntimeBlockWeights;
 	type BlockLength = RuntimeBlockLength;
 	type AccountId = AccountId;
@@ -239,10 +246,7 @@ impl pallet_transaction_payment::Config for Runtime {
 impl pallet_asset_tx_payment::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Fungibles = Assets;
-	type OnChargeAssetTransaction = pallet_asset_tx_payment::FungiblesAdapter<
-		pallet_assets::BalanceToAssetBalance<Balances, Runtime, ConvertInto>,
-		AssetsToBlockAuthor<Runtime>,
-	>;
+	type OnChargeAssetTransaction = asset_fees::DexFungiblesAdapter<AssetsToBlockAuthor<Runtime>>;
 }
 
 parameter_types! {
@@ -393,6 +397,124 @@ type EnsureRootOrHalfCouncil = EitherOfDiverse<
 	pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 4>,
 >;
 
+type TechnicalCollective = pallet_collective::Instance2;
+impl pallet_collective::Config<TechnicalCollective> for Runtime {
+	type RuntimeOrigin = RuntimeOrigin;
+	type Proposal = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type MotionDuration = ConstU32<{ 24 * HOURS }>;
+	type MaxProposals = ConstU32<100>;
+	type MaxMembers = ConstU32<100>;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const LaunchPeriod: BlockNumber = 1 * DAYS;
+	pub const VotingPeriod: BlockNumber = 1 * DAYS;
+	pub const FastTrackVotingPeriod: BlockNumber = 3 * HOURS;
+	pub const EnactmentPeriod: BlockNumber = 1 * DAYS;
+	pub const CooloffPeriod: BlockNumber = 7 * DAYS;
+	pub const MinimumDeposit: Balance = 10 * UNITS;
+}
+
+impl pallet_democracy::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type EnactmentPeriod = EnactmentPeriod;
+	type LaunchPeriod = LaunchPeriod;
+	type VotingPeriod = VotingPeriod;
+	type VoteLockingPeriod = EnactmentPeriod;
+	type MinimumDeposit = MinimumDeposit;
+	/// A straight majority of the council can decide what their next motion is.
+	type ExternalOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 1, 2>;
+	/// A super-majority can have the next scheduled referendum be a straight majority-carries vote.
+	type ExternalMajorityOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 4>;
+	/// A unanimous council can have the next scheduled referendum be a straight default-carries
+	/// (NTB) vote.
+	type ExternalDefaultOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 1, 1>;
+	type SubmitOrigin = EnsureSigned<AccountId>;
+	/// Two thirds of the technical committee can have an ExternalMajority/ExternalDefault vote
+	/// be tabled immediately and with a shorter voting/enactment period.
+	type FastTrackOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 2, 3>;
+	type InstantOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 1, 1>;
+	type InstantAllowed = ConstBool<true>;
+	type FastTrackVotingPeriod = FastTrackVotingPeriod;
+	/// To cancel a proposal which has been passed, 2/3 of the council must agree to it.
+	type CancellationOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>;
+	/// To cancel a proposal before it has been passed, the technical committee must be unanimous
+	/// or Root must agree.
+	type CancelProposalOrigin = EitherOfDiverse<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 1, 1>,
+	>;
+	type BlacklistOrigin = EnsureRoot<AccountId>;
+	/// Any single technical committee member may veto a coming council proposal, however they
+	/// can only do it once and it lasts only for the cool-off period.
+	type VetoOrigin = pallet_collective::EnsureMember<AccountId, TechnicalCollective>;
+	type CooloffPeriod = CooloffPeriod;
+	type Slash = Treasury;
+	type Scheduler = Scheduler;
+	type PalletsOrigin = OriginCaller;
+	type MaxVotes = ConstU32<100>;
+	type WeightInfo = pallet_democracy::weights::SubstrateWeight<Runtime>;
+	type MaxProposals = ConstU32<100>;
+	type Preimages = Preimage;
+	type MaxDeposits = ConstU32<100>;
+	type MaxBlacklisted = ConstU32<100>;
+}
+
+parameter_types! {
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const ProposalBondMinimum: Balance = 10 * UNITS;
+	pub const SpendPeriod: BlockNumber = 1 * DAYS;
+}
+
+impl pallet_treasury::Config for Runtime {
+	type PalletId = TreasuryPalletId;
+	type Currency = Balances;
+	type ApproveOrigin = EnsureRootOrHalfCouncil;
+	type RejectOrigin = EnsureRootOrHalfCouncil;
+	type RuntimeEvent = RuntimeEvent;
+	type OnSlash = Treasury;
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type ProposalBondMaximum = ();
+	type SpendPeriod = SpendPeriod;
+	type Burn = ();
+	type BurnDestination = ();
+	type SpendFunds = ();
+	type WeightInfo = pallet_treasury::weights::SubstrateWeight<Runtime>;
+	type MaxApprovals = ConstU32<100>;
+	/// Spends go through proposals approved by the council.
+	type SpendOrigin = frame_support::traits::NeverEnsureOrigin<Balance>;
+}
+
+/// The council can also spend the reserve assets collected by the treasury, e.g. the fees paid in
+/// xUSD.
+impl pallet_treasury_xcm::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type SpendOrigin = EnsureRootOrHalfCouncil;
+	type AssetId = AssetId;
+	type AssetMultiLocationGetter = AssetRegistry;
+	type TreasuryLocation = xcm_config::TreasuryLocation;
+	type SelfLocation = xcm_config::SelfLocation;
+	type XcmExecutor = xcm_executor::XcmExecutor<xcm_config::XcmConfig>;
+	type Weigher = xcm_builder::FixedWeightBounds<
+		xcm_config::UnitWeightCost,
+		RuntimeCall,
+		xcm_config::MaxInstructions,
+	>;
+	type WeightInfo = pallet_treasury_xcm::weights::SubstrateWeight<Runtime>;
+}
+
 parameter_types! {
 	pub const BasicDeposit: Balance = deposit(1, 258);		// 258 bytes on-chain
 	pub const FieldDeposit: Balance = deposit(0, 66);  		// 66 bytes on-chain
@@ -425,12 +547,15 @@ parameter_types! {
 	pub const ValueLimit: u32 = 256;
 }
 
+pub type CollectionId = u32;
+pub type ItemId = u32;
+
 impl pallet_uniques::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type CollectionId = u32;
-	type ItemId = u32;
+	type CollectionId = CollectionId;
+	type ItemId = ItemId;
 	type Currency = Balances;
-	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type ForceOrigin = EnsureRootOrHalfCouncil;
 	type CollectionDeposit = CollectionDeposit;
 	type ItemDeposit = ItemDeposit;
 	type MetadataDepositBase = UniquesMetadataDepositBase;
@@ -447,7 +572,9 @@ impl pallet_uniques::Config for Runtime {
 }
 
 parameter_types! {
-	pub MaximumSchedulerWeight: Weight = Weight::from_ref_time(10_000_000);
+	// Referenda are enacted by the scheduler, so it must be able to dispatch heavy calls.
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) *
+		RuntimeBlockWeights::get().max_block;
 	pub const NoPreimagePostponement: Option<u32> = Some(10);
 }
 
@@ -457,7 +584,7 @@ impl pallet_scheduler::Config for Runtime {
 	type PalletsOrigin = OriginCaller;
 	type RuntimeCall = RuntimeCall;
 	type MaximumWeight = MaximumSchedulerWeight;
-	type ScheduleOrigin = frame_system::EnsureRoot<AccountId>;
+	type ScheduleOrigin = EnsureRootOrHalfCouncil;
 	type MaxScheduledPerBlock = ConstU32<512>;
 	type WeightInfo = pallet_scheduler::weights::SubstrateWeight<Runtime>;
 	type OriginPrivilegeCmp = EqualPrivilegeOnly;
@@ -480,6 +607,8 @@ impl pallet_preimage::Config for Runtime {
 
 parameter_types! {
 	pub const DexPalletId: PalletId = PalletId(*b"trap/dex");
+	pub const DexProviderFeeNumerator: Balance = 3;
+	pub const DexProviderFeeDenominator: Balance = 1000;
 }
 
 impl pallet_dex::Config for Runtime {
@@ -493,15 +622,58 @@ impl pallet_dex::Config for Runtime {
 	type Assets = Assets;
 	type AssetRegistry = Assets;
 	type WeightInfo = pallet_dex::weights::SubstrateWeight<Runtime>;
-	type ProviderFeeNumerator = ConstU128<3>;
-	type ProviderFeeDenominator = ConstU128<1000>;
+	type ProviderFeeNumerator = DexProviderFeeNumerator;
+	type ProviderFeeDenominator = DexProviderFeeDenominator;
 	type MinDeposit = ConstU128<{ UNITS }>;
 }
 
+impl pallet_dex_router::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type DexCallFilter = tx_pause::NestedCallFilter;
+	type WeightInfo = pallet_dex_router::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_dex_xcm::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type SiblingOrigin = xcm_config::SiblingOrigin;
+	type LocationToAccountId = xcm_config::LocationToAccountId;
+	type AssetLocation = xcm_config::AssetIdLocation;
+	type SelfLocation = xcm_config::SelfLocation;
+	type XcmExecutor = xcm_executor::XcmExecutor<xcm_config::XcmConfig>;
+	type Weigher = xcm_builder::FixedWeightBounds<
+		xcm_config::UnitWeightCost,
+		RuntimeCall,
+		xcm_config::MaxInstructions,
+	>;
+	type RouterCallFilter = tx_pause::NestedCallFilter;
+	type WeightInfo = pallet_dex_xcm::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_dex_oracle::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type TrackOrigin = EnsureRootOrHalfCouncil;
+	type MaxTrackedAssets = ConstU32<16>;
+	type HistoryLength = ConstU32<{ 2 * HOURS }>;
+	type WeightInfo = pallet_dex_oracle::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_contracts_allowlist::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ApproveOrigin = EnsureRootOrHalfCouncil;
+	type StoredCode = contracts::ContractsStoredCode;
+	type WeightInfo = pallet_contracts_allowlist::weights::SubstrateWeight<Runtime>;
+}
+
 impl pallet_asset_registry::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type ReserveAssetModifierOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type ReserveAssetModifierOrigin = EnsureRootOrHalfCouncil;
 	type Assets = Assets;
+	// no pallet of the runtime reacts to reserve assets being registered or unregistered yet
+	type OnAssetRegistered = ();
+	type OnAssetUnregistered = ();
+	type CollectionId = CollectionId;
+	type Uniques = Uniques;
+	type TeleporterModifierOrigin = EnsureRootOrHalfCouncil;
 	type WeightInfo = pallet_asset_registry::weights::SubstrateWeight<Runtime>;
 }
 
@@ -523,6 +695,48 @@ impl pallet_chess::Config for Runtime {
 	type IncentiveShare = IncentiveShare;
 }
 
+impl pallet_chess_xcm::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type PlayerOrigin = xcm_config::SiblingAccountOrigin;
+	type LocationToAccountId = xcm_config::LocationToAccountId;
+	type AssetLocation = xcm_config::AssetIdLocation;
+	type SelfLocation = xcm_config::SelfLocation;
+	type XcmExecutor = xcm_executor::XcmExecutor<xcm_config::XcmConfig>;
+	type Weigher = xcm_builder::FixedWeightBounds<
+		xcm_config::UnitWeightCost,
+		RuntimeCall,
+		xcm_config::MaxInstructions,
+	>;
+	type ChessCallFilter = tx_pause::NestedCallFilter;
+	type MaxOngoingMatches = ConstU32<256>;
+	type WeightInfo = pallet_chess_xcm::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_chess_rating::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type InitialRating = ConstU32<1200>;
+	type KFactor = ConstU32<32>;
+	type MaxRatingGap = ConstU32<200>;
+	type MaxSeeks = ConstU32<64>;
+	type MaxOngoingMatches = ConstU32<256>;
+	type MaxHistory = ConstU32<100>;
+	type MaxLeaderboard = ConstU32<100>;
+	type SeekTimeout = ConstU32<{ 1 * HOURS }>;
+	type ChessCallFilter = tx_pause::NestedCallFilter;
+	type WeightInfo = pallet_chess_rating::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_chess_tournament::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Assets = Assets;
+	type MaxPlayers = ConstU32<64>;
+	type MaxRounds = ConstU32<8>;
+	type MaxPrizes = ConstU32<8>;
+	type MinRoundDuration = ConstU32<HOURS>;
+	type ChessCallFilter = tx_pause::NestedCallFilter;
+	type WeightInfo = pallet_chess_tournament::weights::SubstrateWeight<Runtime>;
+}
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
 	pub enum Runtime where
@@ -565,10 +779,14 @@ construct_runtime!(
 		Uniques: pallet_uniques = 44,
 		Scheduler: pallet_scheduler = 45,
 		Preimage: pallet_preimage = 46,
+		TechnicalCommittee: pallet_collective::<Instance2> = 47,
+		Democracy: pallet_democracy = 48,
+		Treasury: pallet_treasury = 49,
 
 		// Handy utilities.
 		Utility: pallet_utility::{Pallet, Call, Event} = 50,
 		Multisig: pallet_multisig::{Pallet, Call, Storage, Event<T>} = 51,
+		Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>} = 52,
 
 		// Sudo
 		Sudo: pallet_sudo::{Pallet, Call, Config<T>, Event<T>, Storage} = 100,
@@ -576,9 +794,19 @@ construct_runtime!(
 		// Additional pallets
 		Dex: pallet_dex::{Pallet, Call, Storage, Event<T>} = 110,
 		AssetRegistry: pallet_asset_registry::{Pallet, Call, Storage, Event<T>} = 111,
+		ContractsAllowlist: pallet_contracts_allowlist::{Pallet, Call, Storage, Event<T>, Config<T>} = 112,
+		TreasuryXcm: pallet_treasury_xcm::{Pallet, Call, Event<T>} = 113,
+		TxPause: pallet_tx_pause::{Pallet, Call, Storage, Event<T>} = 114,
+		DexRouter: pallet_dex_router::{Pallet, Call, Event<T>} = 115,
+		DexXcm: pallet_dex_xcm::{Pallet, Call, Event<T>} = 116,
+		DexOracle: pallet_dex_oracle::{Pallet, Call, Storage, Event<T>} = 117,
+		Recurring: pallet_recurring::{Pallet, Call, Storage, Event<T>} = 118,
 
 		// Chess
 		Chess: pallet_chess::{Pallet, Call, Storage, Event<T>} = 120,
+		ChessXcm: pallet_chess_xcm::{Pallet, Call, Storage, Event<T>} = 121,
+		ChessRating: pallet_chess_rating::{Pallet, Call, Storage, Event<T>} = 122,
+		ChessTournament: pallet_chess_tournament::{Pallet, Call, Storage, Event<T>} = 123,
 	}
 );
 
@@ -596,13 +824,18 @@ mod benches {
 		[pallet_timestamp, Timestamp]
 		[pallet_collator_selection, CollatorSelection]
 		[pallet_contracts, Contracts]
+		[pallet_contracts_allowlist, ContractsAllowlist]
 		[pallet_collective, Council]
+		[pallet_democracy, Democracy]
 		[pallet_assets, Assets]
 		[pallet_dex, Dex]
 		[pallet_identity, Identity]
 		[pallet_multisig, Multisig]
+		[pallet_proxy, Proxy]
 		[pallet_uniques, Uniques]
 		[pallet_scheduler, Scheduler]
+		[pallet_treasury, Treasury]
+		[pallet_tx_pause, TxPause]
 		[pallet_utility, Utility]
 		[cumulus_pallet_xcmp_queue, XcmpQueue]
 	);
@@ -739,6 +972,99 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl dex_primitives::DexPoolsApi<Block, AssetId, Balance, AssetBalance> for Runtime {
+		fn pools() -> Vec<dex_primitives::PoolInfo<AssetId, Balance, AssetBalance>> {
+			use frame_support::traits::fungibles::Inspect;
+
+			let mut pools: Vec<_> = pallet_dex::Exchanges::<Runtime>::iter_values()
+				.map(|exchange| dex_primitives::PoolInfo {
+					asset_id: exchange.asset_id,
+					liquidity_token_id: exchange.liquidity_token_id,
+					currency_reserve: exchange.currency_reserve,
+					token_reserve: exchange.token_reserve,
+					liquidity_supply: Assets::total_issuance(exchange.liquidity_token_id),
+				})
+				.collect();
+			pools.sort_by_key(|pool| pool.asset_id);
+			pools
+		}
+
+		fn provider_fee() -> dex_primitives::ProviderFee<Balance> {
+			dex_primitives::ProviderFee {
+				numerator: DexProviderFeeNumerator::get(),
+				denominator: DexProviderFeeDenominator::get(),
+			}
+		}
+	}
+
+	impl pallet_dex_router_runtime_api::DexRouterApi<Block, AssetId, AssetBalance> for Runtime {
+		fn quote_exact_in(
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount_in: AssetBalance,
+		) -> Result<AssetBalance, sp_runtime::DispatchError> {
+			DexRouter::quote_exact_in(asset_in, asset_out, amount_in)
+		}
+
+		fn quote_exact_out(
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount_out: AssetBalance,
+		) -> Result<AssetBalance, sp_runtime::DispatchError> {
+			DexRouter::quote_exact_out(asset_in, asset_out, amount_out)
+		}
+	}
+
+	impl pallet_dex_oracle_runtime_api::DexOracleApi<Block, AssetId, BlockNumber> for Runtime {
+		fn twap(asset_id: AssetId, window: BlockNumber) -> Option<sp_runtime::FixedU128> {
+			DexOracle::twap(asset_id, window)
+		}
+
+		fn spot_price(asset_id: AssetId) -> Option<sp_runtime::FixedU128> {
+			DexOracle::spot_price(asset_id)
+		}
+	}
+
+	impl pallet_chess_rating_runtime_api::ChessRatingApi<Block, AccountId, pallet_chess::MatchStyle, BlockNumber> for Runtime {
+		fn rating(who: AccountId, style: pallet_chess::MatchStyle) -> u32 {
+			ChessRating::rating(&who, &style)
+		}
+
+		fn history(who: AccountId, style: pallet_chess::MatchStyle) -> Vec<(BlockNumber, u32)> {
+			ChessRating::history(who, style).into_inner()
+		}
+
+		fn leaderboard(style: pallet_chess::MatchStyle, count: u32) -> Vec<(AccountId, u32)> {
+			ChessRating::leaderboard(&style, count)
+		}
+	}
+
+	impl chess_primitives::ChessApi<Block, Hash, AccountId, pallet_chess::MatchStyle, BlockNumber, AssetId, AssetBalance> for Runtime {
+		fn board(match_id: Hash) -> Option<Vec<u8>> {
+			chess::board(match_id)
+		}
+
+		fn turn(match_id: Hash) -> Option<AccountId> {
+			chess::turn(match_id)
+		}
+
+		fn clock(match_id: Hash) -> Option<BlockNumber> {
+			chess::clock(match_id)
+		}
+
+		fn legal_moves(match_id: Hash) -> Vec<Vec<u8>> {
+			chess::legal_moves(match_id)
+		}
+
+		fn open_matches(
+			style: pallet_chess::MatchStyle,
+			start_after: Option<Hash>,
+			count: u32,
+		) -> (Vec<chess_primitives::OpenMatch<Hash, AccountId, AssetId, AssetBalance>>, Option<Hash>) {
+			chess::open_matches(style, start_after, count)
+		}
+	}
+
 
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
@@ -763,7 +1089,7 @@ impl_runtime_apis! {
 				gas_limit,
 				storage_deposit_limit,
 				input_data,
-				contracts::CONTRACTS_DEBUG_OUTPUT,
+				contracts::contracts_debug_output(),
 				pallet_contracts::Determinism::Deterministic,
 			)
 		}
@@ -786,7 +1112,7 @@ impl_runtime_apis! {
 				code,
 				data,
 				salt,
-				contracts::CONTRACTS_DEBUG_OUTPUT,
+				contracts::contracts_debug_output(),
 			)
 		}
 
@@ -812,6 +1138,71 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl contracts_primitives::ContractsDryRunApi<
+		Block,
+		AccountId,
+		Balance,
+		Hash,
+		pallet_contracts::Event<Runtime>,
+	> for Runtime {
+		fn call(
+			origin: AccountId,
+			dest: AccountId,
+			value: Balance,
+			gas_limit: Option<Weight>,
+			storage_deposit_limit: Option<Balance>,
+			input_data: Vec<u8>,
+			debug: bool,
+		) -> contracts_primitives::DryRunResult<
+			pallet_contracts_primitives::ContractExecResult<Balance>,
+			pallet_contracts::Event<Runtime>,
+		> {
+			// only report the events of this dry-run
+			System::reset_events();
+			let gas_limit = gas_limit.unwrap_or(RuntimeBlockWeights::get().max_block);
+			let result = Contracts::bare_call(
+				origin,
+				dest,
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				input_data,
+				debug,
+				pallet_contracts::Determinism::Deterministic,
+			);
+			contracts_primitives::DryRunResult { result, events: contracts::contracts_events() }
+		}
+
+		fn instantiate(
+			origin: AccountId,
+			value: Balance,
+			gas_limit: Option<Weight>,
+			storage_deposit_limit: Option<Balance>,
+			code: pallet_contracts_primitives::Code<Hash>,
+			data: Vec<u8>,
+			salt: Vec<u8>,
+			debug: bool,
+		) -> contracts_primitives::DryRunResult<
+			pallet_contracts_primitives::ContractInstantiateResult<AccountId, Balance>,
+			pallet_contracts::Event<Runtime>,
+		> {
+			// only report the events of this dry-run
+			System::reset_events();
+			let gas_limit = gas_limit.unwrap_or(RuntimeBlockWeights::get().max_block);
+			let result = Contracts::bare_instantiate(
+				origin,
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				code,
+				data,
+				salt,
+				debug,
+			);
+			contracts_primitives::DryRunResult { result, events: contracts::contracts_events() }
+		}
+	}
+
 	#[cfg(feature = "try-runtime")]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
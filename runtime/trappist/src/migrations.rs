@@ -0,0 +1,60 @@
+//! Storage migrations of the Trappist runtime.
+//!
+//! # Removing sudo
+//!
+//! Governance (`Council`, `TechnicalCommittee` and `Democracy`) runs alongside `Sudo` until it is
+//! trusted to be the only authority:
+//!
+//! 1. Sudo seeds the members of `Council` and `TechnicalCommittee` on live chains (new chains
+//!    set them in genesis).
+//! 2. Privileged origins accept root (i.e. a passed referendum) or the council, so governance
+//!    can already do everything sudo does.
+//! 3. A later runtime upgrade removes `Sudo` from `construct_runtime!` and adds [`RemoveSudo`]
+//!    to the runtime `Migrations`, deleting the sudo key from storage.
+
+use frame_support::{
+	storage::unhashed,
+	traits::{Get, OnRuntimeUpgrade},
+	weights::Weight,
+};
+
+/// Name of the sudo pallet in `construct_runtime!`, i.e. the prefix of its storage.
+const SUDO_PALLET_NAME: &[u8] = b"Sudo";
+
+/// Deletes all the storage of the `Sudo` pallet.
+///
+/// Only to be used once `Sudo` has been removed from the runtime.
+pub struct RemoveSudo<T>(sp_std::marker::PhantomData<T>);
+impl<T: frame_system::Config> OnRuntimeUpgrade for RemoveSudo<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let prefix = sp_io::hashing::twox_128(SUDO_PALLET_NAME);
+		let removed = unhashed::clear_prefix(&prefix, None, None);
+		T::DbWeight::get().reads_writes(removed.loops.into(), removed.backend.into())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{AccountId, Runtime, Sudo};
+	use frame_support::traits::GenesisBuild;
+
+	#[test]
+	fn remove_sudo_deletes_the_sudo_key() {
+		let mut storage =
+			frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_sudo::GenesisConfig::<Runtime> { key: Some(AccountId::new([1u8; 32])) }
+			.assimilate_storage(&mut storage)
+			.unwrap();
+
+		sp_io::TestExternalities::new(storage).execute_with(|| {
+			assert!(Sudo::key().is_some());
+
+			RemoveSudo::<Runtime>::on_runtime_upgrade();
+
+			assert!(Sudo::key().is_none());
+			// other pallets are left untouched
+			assert!(frame_system::BlockHash::<Runtime>::contains_key(0));
+		});
+	}
+}
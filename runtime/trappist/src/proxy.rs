@@ -0,0 +1,254 @@
+use crate::{constants::currency::deposit, Balance, Balances, Runtime, RuntimeCall, RuntimeEvent};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	parameter_types,
+	traits::{ConstU32, InstanceFilter},
+	RuntimeDebug,
+};
+use sp_runtime::traits::BlakeTwo256;
+
+parameter_types! {
+	// One storage item; key size 32, value size 8.
+	pub const ProxyDepositBase: Balance = deposit(1, 8);
+	// Additional storage item size of 33 bytes (account and proxy type).
+	pub const ProxyDepositFactor: Balance = deposit(0, 33);
+	// One storage item; key size 32, value size 8.
+	pub const AnnouncementDepositBase: Balance = deposit(1, 8);
+	// Additional storage item size of 68 bytes (account, call hash and block number).
+	pub const AnnouncementDepositFactor: Balance = deposit(0, 68);
+}
+
+/// The type used to represent the kinds of proxying allowed.
+///
+/// Accounts on the relay chain can use proxies through XCM `Transact`, as the account derived
+/// from their location by `HashedRelayAccountId32`.
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	Encode,
+	Decode,
+	RuntimeDebug,
+	MaxEncodedLen,
+	scale_info::TypeInfo,
+)]
+pub enum ProxyType {
+	/// Allows all calls.
+	Any = 0,
+	/// Allows the calls of the pallets which don't move funds. New pallets are excluded until
+	/// they are added here.
+	NonTransfer = 1,
+	/// Allows cancelling the announcements of other proxies.
+	CancelProxy = 2,
+	/// Allows playing chess.
+	Chess = 3,
+	/// Allows swapping and providing liquidity on the DEX. Swaps must pay out to the proxied
+	/// account, not to another recipient.
+	Dex = 4,
+	/// Allows managing fungible assets.
+	Assets = 5,
+	/// Allows managing non-fungible assets.
+	Uniques = 6,
+	/// Allows sending assets and messages to other chains.
+	Xcm = 7,
+}
+
+impl Default for ProxyType {
+	fn default() -> Self {
+		Self::Any
+	}
+}
+
+impl InstanceFilter<RuntimeCall> for ProxyType {
+	fn filter(&self, c: &RuntimeCall) -> bool {
+		match self {
+			ProxyType::Any => true,
+			ProxyType::NonTransfer => matches!(
+				c,
+				RuntimeCall::System { .. } |
+					RuntimeCall::Timestamp { .. } |
+					RuntimeCall::Authorship { .. } |
+					RuntimeCall::CollatorSelection { .. } |
+					RuntimeCall::Session { .. } |
+					RuntimeCall::Council { .. } |
+					RuntimeCall::Identity { .. } |
+					RuntimeCall::Scheduler { .. } |
+					RuntimeCall::Preimage { .. } |
+					RuntimeCall::TechnicalCommittee { .. } |
+					RuntimeCall::Democracy { .. } |
+					RuntimeCall::Utility { .. } |
+					RuntimeCall::Multisig { .. } |
+					RuntimeCall::Proxy { .. }
+			),
+			ProxyType::CancelProxy => matches!(
+				c,
+				RuntimeCall::Proxy(pallet_proxy::Call::reject_announcement { .. }) |
+					RuntimeCall::Utility { .. } |
+					RuntimeCall::Multisig { .. }
+			),
+			ProxyType::Chess => matches!(
+				c,
+				RuntimeCall::Chess { .. } |
+					RuntimeCall::ChessRating { .. } |
+					RuntimeCall::ChessTournament { .. } |
+					RuntimeCall::Utility { .. }
+			),
+			ProxyType::Dex => matches!(
+				c,
+				RuntimeCall::Dex(
+					pallet_dex::Call::currency_to_asset { recipient: None, .. } |
+						pallet_dex::Call::asset_to_currency { recipient: None, .. } |
+						pallet_dex::Call::add_liquidity { .. } |
+						pallet_dex::Call::remove_liquidity { .. }
+				) | RuntimeCall::DexRouter(pallet_dex_router::Call::swap { .. }) |
+					RuntimeCall::Utility { .. }
+			),
+			ProxyType::Assets => {
+				matches!(c, RuntimeCall::Assets { .. } | RuntimeCall::Utility { .. })
+			},
+			ProxyType::Uniques => {
+				matches!(c, RuntimeCall::Uniques { .. } | RuntimeCall::Utility { .. })
+			},
+			ProxyType::Xcm => {
+				matches!(c, RuntimeCall::PolkadotXcm { .. } | RuntimeCall::Utility { .. })
+			},
+		}
+	}
+
+	fn is_superset(&self, o: &Self) -> bool {
+		match (self, o) {
+			(x, y) if x == y => true,
+			(ProxyType::Any, _) => true,
+			(_, ProxyType::Any) => false,
+			(ProxyType::NonTransfer, ProxyType::CancelProxy) => true,
+			_ => false,
+		}
+	}
+}
+
+impl pallet_proxy::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type Currency = Balances;
+	type ProxyType = ProxyType;
+	type ProxyDepositBase = ProxyDepositBase;
+	type ProxyDepositFactor = ProxyDepositFactor;
+	type MaxProxies = ConstU32<32>;
+	type WeightInfo = pallet_proxy::weights::SubstrateWeight<Runtime>;
+	type MaxPending = ConstU32<32>;
+	type CallHasher = BlakeTwo256;
+	type AnnouncementDepositBase = AnnouncementDepositBase;
+	type AnnouncementDepositFactor = AnnouncementDepositFactor;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{xcm_config::LocationToAccountId, AccountId};
+	use xcm::latest::prelude::*;
+	use xcm_executor::traits::Convert;
+
+	const BOB: AccountId = AccountId::new([2u8; 32]);
+
+	fn balances_transfer() -> RuntimeCall {
+		RuntimeCall::Balances(pallet_balances::Call::transfer { dest: BOB.into(), value: 1 })
+	}
+
+	fn assets_transfer() -> RuntimeCall {
+		RuntimeCall::Assets(pallet_assets::Call::transfer {
+			id: 1.into(),
+			target: BOB.into(),
+			amount: 1,
+		})
+	}
+
+	fn batch(calls: Vec<RuntimeCall>) -> RuntimeCall {
+		RuntimeCall::Utility(pallet_utility::Call::batch { calls })
+	}
+
+	#[test]
+	fn proxy_types_filter_their_pallets() {
+		assert!(ProxyType::Any.filter(&balances_transfer()));
+		assert!(ProxyType::Assets.filter(&assets_transfer()));
+		assert!(!ProxyType::Assets.filter(&balances_transfer()));
+		assert!(!ProxyType::Chess.filter(&assets_transfer()));
+		assert!(!ProxyType::Dex.filter(&assets_transfer()));
+		assert!(ProxyType::Uniques.filter(&RuntimeCall::Uniques(pallet_uniques::Call::transfer {
+			collection: 1,
+			item: 1,
+			dest: BOB.into()
+		})));
+
+		assert!(!ProxyType::NonTransfer.filter(&balances_transfer()));
+		assert!(!ProxyType::NonTransfer.filter(&assets_transfer()));
+		assert!(ProxyType::NonTransfer
+			.filter(&RuntimeCall::System(frame_system::Call::remark { remark: vec![] })));
+	}
+
+	#[test]
+	fn non_transfer_excludes_pallets_which_move_funds() {
+		let recurring_cancel = RuntimeCall::Recurring(pallet_recurring::Call::cancel { id: 0 });
+		let tournament_register =
+			RuntimeCall::ChessTournament(pallet_chess_tournament::Call::register { id: 0 });
+		let rating_join = RuntimeCall::ChessRating(pallet_chess_rating::Call::join_match {
+			match_id: Default::default(),
+		});
+
+		assert!(!ProxyType::NonTransfer.filter(&recurring_cancel));
+		assert!(!ProxyType::NonTransfer.filter(&tournament_register));
+		assert!(!ProxyType::NonTransfer.filter(&rating_join));
+		assert!(!ProxyType::NonTransfer
+			.filter(&RuntimeCall::Sudo(pallet_sudo::Call::set_key { new: BOB.into() })));
+
+		assert!(ProxyType::Chess.filter(&tournament_register));
+		assert!(ProxyType::Chess.filter(&rating_join));
+		assert!(!ProxyType::Chess.filter(&recurring_cancel));
+	}
+
+	#[test]
+	fn dex_proxies_only_swap_to_the_proxied_account() {
+		let swap = |recipient| {
+			RuntimeCall::Dex(pallet_dex::Call::currency_to_asset {
+				asset_id: 1,
+				amount: pallet_dex::TradeAmount::FixedInput { input_amount: 1, min_output: 1 },
+				deadline: 1,
+				recipient,
+			})
+		};
+
+		assert!(ProxyType::Dex.filter(&swap(None)));
+		assert!(!ProxyType::Dex.filter(&swap(Some(BOB))));
+	}
+
+	#[test]
+	fn batches_are_allowed_as_their_calls_are_filtered_on_dispatch() {
+		assert!(ProxyType::Assets.filter(&batch(vec![assets_transfer()])));
+		assert!(ProxyType::Xcm.filter(&batch(vec![])));
+	}
+
+	#[test]
+	fn proxy_types_superset() {
+		assert!(ProxyType::Any.is_superset(&ProxyType::Dex));
+		assert!(ProxyType::NonTransfer.is_superset(&ProxyType::CancelProxy));
+		assert!(ProxyType::Dex.is_superset(&ProxyType::Dex));
+		assert!(!ProxyType::Dex.is_superset(&ProxyType::Any));
+		assert!(!ProxyType::NonTransfer.is_superset(&ProxyType::Dex));
+	}
+
+	#[test]
+	fn relay_accounts_have_a_local_account_for_transact() {
+		let relay_account = MultiLocation::new(1, X1(AccountId32 { network: Any, id: [2u8; 32] }));
+
+		let account = LocationToAccountId::convert_ref(&relay_account).unwrap();
+		assert_ne!(account, BOB);
+		assert_eq!(LocationToAccountId::convert_ref(&relay_account), Ok(account));
+		assert!(LocationToAccountId::convert_ref(MultiLocation::new(
+			2,
+			X1(AccountId32 { network: Any, id: [2u8; 32] })
+		))
+		.is_err());
+	}
+}
@@ -0,0 +1,106 @@
+use crate::{
+	constants::currency::deposit, Balance, Balances, BlockNumber, OriginCaller, Preimage, Runtime,
+	RuntimeBlockWeights, RuntimeCall, RuntimeEvent, Scheduler, HOURS,
+};
+use frame_support::{
+	parameter_types,
+	traits::{ConstU32, Contains, Get},
+	weights::Weight,
+};
+use sp_runtime::Perbill;
+
+parameter_types! {
+	pub const RecurringPaymentDeposit: Balance = deposit(2, 64);
+	pub const RecurringByteDeposit: Balance = deposit(0, 1);
+	/// Leaves half of the agenda of the `Scheduler` to other tasks, e.g. enacting referenda.
+	pub MaxRecurringPayments: u32 =
+		<Runtime as pallet_scheduler::Config>::MaxScheduledPerBlock::get() / 2;
+	pub const MinRecurringPeriod: BlockNumber = HOURS;
+	pub MaxRecurringCallWeight: Weight = Perbill::from_percent(5) *
+		RuntimeBlockWeights::get().max_block;
+}
+
+/// The calls which can be made recurring: transfers, DEX swaps and outbound XCM reserve
+/// transfers.
+///
+/// The swaps of `Dex` and `DexRouter` fail after their deadline, so recurring swaps need a
+/// deadline past their last execution.
+pub struct RecurringCalls;
+impl Contains<RuntimeCall> for RecurringCalls {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(
+			call,
+			RuntimeCall::Balances(
+				pallet_balances::Call::transfer { .. } |
+					pallet_balances::Call::transfer_keep_alive { .. }
+			) | RuntimeCall::Assets(
+				pallet_assets::Call::transfer { .. } |
+					pallet_assets::Call::transfer_keep_alive { .. }
+			) | RuntimeCall::Dex(
+				pallet_dex::Call::currency_to_asset { .. } |
+					pallet_dex::Call::asset_to_currency { .. }
+			) | RuntimeCall::DexRouter(pallet_dex_router::Call::swap { .. }) |
+				RuntimeCall::PolkadotXcm(
+					pallet_xcm::Call::reserve_transfer_assets { .. } |
+						pallet_xcm::Call::limited_reserve_transfer_assets { .. }
+				)
+		)
+	}
+}
+
+impl pallet_recurring::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type PalletsOrigin = OriginCaller;
+	type Scheduler = Scheduler;
+	type Preimages = Preimage;
+	type Currency = Balances;
+	type RecurringCalls = RecurringCalls;
+	type PaymentDeposit = RecurringPaymentDeposit;
+	type ByteDeposit = RecurringByteDeposit;
+	type MaxPaymentsPerAccount = ConstU32<16>;
+	type MaxPayments = MaxRecurringPayments;
+	type MinPeriod = MinRecurringPeriod;
+	type MaxCallWeight = MaxRecurringCallWeight;
+	type WeightInfo = pallet_recurring::weights::SubstrateWeight<Runtime>;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::AccountId;
+
+	const BOB: AccountId = AccountId::new([2u8; 32]);
+
+	#[test]
+	fn transfers_and_swaps_can_be_recurring() {
+		assert!(RecurringCalls::contains(&RuntimeCall::Balances(
+			pallet_balances::Call::transfer_keep_alive { dest: BOB.into(), value: 1 }
+		)));
+		assert!(RecurringCalls::contains(&RuntimeCall::Assets(pallet_assets::Call::transfer {
+			id: 1.into(),
+			target: BOB.into(),
+			amount: 1
+		})));
+		assert!(RecurringCalls::contains(&RuntimeCall::DexRouter(pallet_dex_router::Call::swap {
+			asset_in: 1,
+			asset_out: 2,
+			amount_in: 1,
+			min_amount_out: 1,
+			deadline: BlockNumber::MAX,
+		})));
+	}
+
+	#[test]
+	fn other_calls_cannot_be_recurring() {
+		assert!(!RecurringCalls::contains(&RuntimeCall::System(frame_system::Call::remark {
+			remark: vec![]
+		})));
+		assert!(!RecurringCalls::contains(&RuntimeCall::Utility(pallet_utility::Call::batch {
+			calls: vec![]
+		})));
+		assert!(!RecurringCalls::contains(&RuntimeCall::Recurring(
+			pallet_recurring::Call::cancel { id: 0 }
+		)));
+	}
+}
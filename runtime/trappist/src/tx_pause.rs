@@ -0,0 +1,257 @@
+use crate::{
+	contracts::ContractsCodeFilter, BlockNumber, EnsureRootOrHalfCouncil, Runtime, RuntimeCall,
+	RuntimeEvent, TxPause, DAYS,
+};
+use frame_support::{
+	parameter_types,
+	traits::{ConstU32, Contains},
+};
+
+parameter_types! {
+	pub const MaxSafeModeDuration: BlockNumber = 7 * DAYS;
+}
+
+/// Calls which can't be paused, as the chain (or governance) stops working without them.
+///
+/// Also allowed while in safe mode.
+pub struct UnpausableCalls;
+impl Contains<RuntimeCall> for UnpausableCalls {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(
+			call,
+			// inherents
+			RuntimeCall::ParachainSystem { .. } |
+				RuntimeCall::Timestamp { .. } |
+				// governance, e.g. runtime upgrades. The calls of `System` which anyone can make,
+				// such as remarks, can be paused.
+				RuntimeCall::System(
+					frame_system::Call::set_code { .. } |
+						frame_system::Call::set_code_without_checks { .. } |
+						frame_system::Call::set_heap_pages { .. } |
+						frame_system::Call::set_storage { .. } |
+						frame_system::Call::kill_storage { .. } |
+						frame_system::Call::kill_prefix { .. }
+				) |
+				RuntimeCall::Council { .. } |
+				RuntimeCall::TechnicalCommittee { .. } |
+				RuntimeCall::Democracy { .. } |
+				RuntimeCall::Scheduler { .. } |
+				RuntimeCall::Preimage { .. } |
+				RuntimeCall::Sudo { .. } |
+				RuntimeCall::TxPause { .. }
+		)
+	}
+}
+
+/// The only calls allowed while in safe mode: the unpausable calls and the servicing of
+/// overweight inbound XCM messages.
+///
+/// Assets received over XCM are not calls, so they keep being deposited. Calls sent in `Transact`
+/// are filtered like any other call.
+pub struct SafeModeCalls;
+impl Contains<RuntimeCall> for SafeModeCalls {
+	fn contains(call: &RuntimeCall) -> bool {
+		UnpausableCalls::contains(call) ||
+			matches!(call, RuntimeCall::XcmpQueue { .. } | RuntimeCall::DmpQueue { .. })
+	}
+}
+
+/// The runtime's `BaseCallFilter`: rejects paused calls, calls not allowed in safe mode and
+/// contract code not allowed by `ContractsAllowlist`.
+pub struct BaseCallFilter;
+impl Contains<RuntimeCall> for BaseCallFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		TxPause::contains(call) && ContractsCodeFilter::contains(call)
+	}
+}
+
+/// Applies the `BaseCallFilter` to the calls pallets make directly on behalf of their callers.
+/// These calls are not dispatched, so pauses would not apply to them otherwise.
+pub struct NestedCallFilter;
+impl<Call: Clone + Into<RuntimeCall>> Contains<Call> for NestedCallFilter {
+	fn contains(call: &Call) -> bool {
+		BaseCallFilter::contains(&call.clone().into())
+	}
+}
+
+impl pallet_tx_pause::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type PauseOrigin = EnsureRootOrHalfCouncil;
+	type UnpausableCalls = UnpausableCalls;
+	type SafeModeCalls = SafeModeCalls;
+	type MaxSafeModeDuration = MaxSafeModeDuration;
+	type MaxNameLen = ConstU32<64>;
+	type WeightInfo = pallet_tx_pause::weights::SubstrateWeight<Runtime>;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::AccountId;
+	use frame_support::{assert_noop, assert_ok};
+	use sp_runtime::traits::Dispatchable;
+
+	const BOB: AccountId = AccountId::new([2u8; 32]);
+
+	fn root() -> crate::RuntimeOrigin {
+		frame_system::RawOrigin::Root.into()
+	}
+
+	fn name(name: &str) -> pallet_tx_pause::PalletNameOf<Runtime> {
+		name.as_bytes().to_vec().try_into().unwrap()
+	}
+
+	fn balances_transfer() -> RuntimeCall {
+		RuntimeCall::Balances(pallet_balances::Call::transfer { dest: BOB.into(), value: 1 })
+	}
+
+	fn timestamp_set() -> RuntimeCall {
+		RuntimeCall::Timestamp(pallet_timestamp::Call::set { now: 0 })
+	}
+
+	#[test]
+	fn paused_calls_are_filtered() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			assert_ok!(TxPause::pause(root(), name("Balances"), Some(name("transfer"))));
+
+			assert!(!BaseCallFilter::contains(&balances_transfer()));
+			assert!(BaseCallFilter::contains(&RuntimeCall::Balances(
+				pallet_balances::Call::transfer_keep_alive { dest: BOB.into(), value: 1 }
+			)));
+
+			// nested calls are filtered on dispatch
+			let batch = RuntimeCall::Utility(pallet_utility::Call::batch_all {
+				calls: vec![balances_transfer()],
+			});
+			assert!(BaseCallFilter::contains(&batch));
+			assert_noop!(
+				batch.dispatch(crate::RuntimeOrigin::signed(BOB)).map_err(|e| e.error),
+				frame_system::Error::<Runtime>::CallFiltered
+			);
+		});
+	}
+
+	#[test]
+	fn nested_calls_are_filtered() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			let swap = pallet_dex::Call::<Runtime>::currency_to_asset {
+				asset_id: 1,
+				amount: pallet_dex::TradeAmount::FixedInput { input_amount: 1, min_output: 1 },
+				deadline: 0,
+				recipient: None,
+			};
+			let join_match =
+				pallet_chess::Call::<Runtime>::join_match { match_id: Default::default() };
+			assert!(NestedCallFilter::contains(&swap));
+			assert!(NestedCallFilter::contains(&join_match));
+
+			assert_ok!(TxPause::pause(root(), name("Dex"), None));
+			assert_ok!(TxPause::pause(root(), name("Chess"), Some(name("join_match"))));
+
+			assert!(!NestedCallFilter::contains(&swap));
+			assert!(!NestedCallFilter::contains(&join_match));
+		});
+	}
+
+	#[test]
+	fn recurring_payments_are_filtered_on_execution() {
+		use crate::{Balances, Recurring, System};
+		use frame_support::traits::Currency;
+
+		sp_io::TestExternalities::default().execute_with(|| {
+			// events are not deposited on the genesis block
+			System::set_block_number(1);
+			Balances::make_free_balance_be(&BOB, 1_000 * crate::constants::currency::UNITS);
+			assert_ok!(Recurring::create(
+				crate::RuntimeOrigin::signed(BOB),
+				Box::new(RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive {
+					dest: BOB.into(),
+					value: 1
+				})),
+				crate::HOURS,
+				None,
+			));
+
+			// executions are dispatched by `Root`, but their calls as signed by their owner
+			assert_ok!(TxPause::pause(root(), name("Balances"), None));
+			assert_ok!(Recurring::execute(root(), 0));
+
+			System::assert_last_event(
+				pallet_recurring::Event::PaymentFailed {
+					id: 0,
+					error: frame_system::Error::<Runtime>::CallFiltered.into(),
+				}
+				.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn inherents_and_governance_cannot_be_paused() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			assert_ok!(TxPause::pause(root(), name("Timestamp"), None));
+			assert_ok!(TxPause::pause(root(), name("TxPause"), None));
+
+			assert!(BaseCallFilter::contains(&timestamp_set()));
+			assert!(BaseCallFilter::contains(&RuntimeCall::TxPause(
+				pallet_tx_pause::Call::exit_safe_mode {}
+			)));
+		});
+	}
+
+	#[test]
+	fn only_the_root_calls_of_system_are_unpausable() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+			let set_code = RuntimeCall::System(frame_system::Call::set_code { code: vec![] });
+
+			assert_ok!(TxPause::pause(root(), name("System"), None));
+			assert!(!BaseCallFilter::contains(&remark));
+			assert!(BaseCallFilter::contains(&set_code));
+
+			assert_ok!(TxPause::unpause(root(), name("System"), None));
+			assert_ok!(TxPause::enter_safe_mode(root(), 10));
+			assert!(!BaseCallFilter::contains(&remark));
+			assert!(BaseCallFilter::contains(&set_code));
+		});
+	}
+
+	#[test]
+	fn safe_mode_only_allows_safe_mode_calls() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			assert_ok!(TxPause::enter_safe_mode(root(), 10));
+
+			assert!(!BaseCallFilter::contains(&balances_transfer()));
+			assert!(BaseCallFilter::contains(&timestamp_set()));
+			assert!(BaseCallFilter::contains(&RuntimeCall::Council(
+				pallet_collective::Call::vote {
+					proposal: Default::default(),
+					index: 0,
+					approve: true
+				}
+			)));
+			assert!(BaseCallFilter::contains(&RuntimeCall::XcmpQueue(
+				cumulus_pallet_xcmp_queue::Call::suspend_xcm_execution {}
+			)));
+
+			assert_ok!(TxPause::exit_safe_mode(root()));
+			assert!(BaseCallFilter::contains(&balances_transfer()));
+		});
+	}
+
+	#[test]
+	fn contracts_code_is_still_filtered() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			assert_ok!(crate::ContractsAllowlist::set_enforced(root(), true, 0));
+
+			assert!(!BaseCallFilter::contains(&RuntimeCall::Contracts(
+				pallet_contracts::Call::upload_code {
+					code: b"code".to_vec(),
+					storage_deposit_limit: None,
+					determinism: pallet_contracts::Determinism::Deterministic,
+				}
+			)));
+		});
+	}
+}
@@ -13,31 +13,43 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::constants::fee::default_fee_per_second;
+use crate::constants::{currency::EXISTENTIAL_DEPOSIT, fee::default_fee_per_second};
 
 use super::{
-	AccountId, AssetRegistry, Assets, Balance, Balances, ParachainInfo, ParachainSystem,
-	PolkadotXcm, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, WeightToFee, XcmpQueue,
+	AccountId, AssetRegistry, Assets, Balance, Balances, CollectionId, ItemId, ParachainInfo,
+	ParachainSystem, PolkadotXcm, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin,
+	TreasuryPalletId, Uniques, WeightToFee, XcmpQueue,
 };
 use frame_support::{
 	match_types, parameter_types,
-	traits::{EitherOfDiverse, Everything, Get, Nothing, PalletInfoAccess},
+	traits::{Contains, EitherOfDiverse, Everything, Get, Nothing, PalletInfoAccess},
+	PalletId,
 };
 use frame_system::EnsureRoot;
-use sp_std::marker::PhantomData;
+use sp_runtime::traits::AccountIdConversion;
+use sp_std::{borrow::Borrow, marker::PhantomData, prelude::*};
 
 use parachains_common::{
 	impls::DealWithFees,
 	xcm_config::{DenyReserveTransferToRelayChain, DenyThenTry},
 	AssetId,
 };
-use xcm_executor::traits::{FilterAssetLocation, JustTry};
-use xcm_primitives::{AsAssetMultiLocation, ConvertedRegisteredAssetId, TrappistDropAssets};
+use xcm_executor::traits::{Convert, FilterAssetLocation, JustTry, TransactAsset};
+use xcm_primitives::{
+	AllowUnpaidNonFungiblesReturnFrom, AsAssetMultiLocation, AsCollectionMultiLocation,
+	AsInstanceIndex, ConvertedRegisteredAssetId, ConvertedRegisteredClassId,
+	HashedRelayAccountId32, HashedSiblingAccountId32, IsTrustedTeleporter,
+	NonFungiblesHoldingAdapter, TrappistDropAssets,
+};
 
 // use super::xcm_primitives::{AbsoluteReserveProvider, MultiNativeAsset};
 use pallet_xcm::{EnsureXcm, IsMajorityOfBody, XcmPassthrough};
 use polkadot_parachain::primitives::Sibling;
-use xcm::latest::{prelude::*, Fungibility::Fungible, MultiAsset, MultiLocation};
+use xcm::latest::{
+	prelude::*,
+	Fungibility::{Fungible, NonFungible},
+	MultiAsset, MultiLocation,
+};
 
 use xcm_builder::{
 	AccountId32Aliases, AllowKnownQueryResponses, AllowSubscriptionsFrom,
@@ -46,7 +58,7 @@ use xcm_builder::{
 	FixedWeightBounds, FungiblesAdapter, IsConcrete, LocationInverter, NativeAsset,
 	ParentAsSuperuser, ParentIsPreset, RelayChainAsNative, SiblingParachainAsNative,
 	SiblingParachainConvertsVia, SignedAccountId32AsNative, SignedToAccountId32,
-	SovereignSignedViaLocation, TakeWeightCredit, UsingComponents,
+	SovereignSignedViaLocation, TakeRevenue, TakeWeightCredit, UsingComponents,
 };
 use xcm_executor::XcmExecutor;
 
@@ -59,7 +71,23 @@ parameter_types! {
 	pub SelfReserve: MultiLocation = MultiLocation { parents:0, interior: Here };
 	pub AssetsPalletLocation: MultiLocation =
 		PalletInstance(<Assets as PalletInfoAccess>::index() as u8).into();
+	pub UniquesPalletLocation: MultiLocation =
+		PalletInstance(<Uniques as PalletInfoAccess>::index() as u8).into();
 	pub CheckingAccount: AccountId = PolkadotXcm::check_account();
+	// The relay token, teleported to and from the relay chain, is kept in `Assets` under this id.
+	// The chain specs create it in genesis as a sufficient asset owned by `TreasuryAccount`, so
+	// that no one can mint it. Chains started without it must create it with `Assets::force_create`
+	// before the relay chain is trusted to teleport it.
+	pub const RelayTokenAssetId: AssetId = 20;
+	// As for the native currency, 1/10 of the existential deposit of the relay chain.
+	pub const RelayTokenMinBalance: Balance = EXISTENTIAL_DEPOSIT;
+	// Holds the uniques items which are currently living on other chains.
+	pub UniquesHoldingAccount: AccountId = PalletId(*b"trap/nft").into_account_truncating();
+	pub TreasuryAccount: AccountId = TreasuryPalletId::get().into_account_truncating();
+	pub TreasuryLocation: MultiLocation = AccountId32 {
+		network: NetworkId::Any,
+		id: TreasuryAccount::get().into(),
+	}.into();
 	pub const ExecutiveBody: BodyId = BodyId::Executive;
 }
 
@@ -79,6 +107,12 @@ pub type LocationToAccountId = (
 	SiblingParachainConvertsVia<Sibling, AccountId>,
 	// Straight up local `AccountId32` origins just alias directly to `AccountId`.
 	AccountId32Aliases<RelayNetwork, AccountId>,
+	// Relay chain `AccountId32` origins convert to an account derived from their location, which
+	// lets them `Transact` through proxies.
+	HashedRelayAccountId32<AccountId>,
+	// Sibling chain `AccountId32` origins convert to an account derived from their location, which
+	// holds their stakes in `pallet_chess_xcm` matches.
+	HashedSiblingAccountId32<AccountId>,
 );
 
 /// Means for transacting the native currency on this chain.
@@ -116,6 +150,43 @@ pub type LocalFungiblesTransactor = FungiblesAdapter<
 	CheckingAccount,
 >;
 
+/// Converts the relay token into `RelayTokenAssetId`, and back.
+pub struct RelayTokenAsAssetId;
+impl Convert<MultiLocation, AssetId> for RelayTokenAsAssetId {
+	fn convert_ref(location: impl Borrow<MultiLocation>) -> Result<AssetId, ()> {
+		if location.borrow() == &RelayLocation::get() {
+			Ok(RelayTokenAssetId::get())
+		} else {
+			Err(())
+		}
+	}
+
+	fn reverse_ref(asset_id: impl Borrow<AssetId>) -> Result<MultiLocation, ()> {
+		if asset_id.borrow() == &RelayTokenAssetId::get() {
+			Ok(RelayLocation::get())
+		} else {
+			Err(())
+		}
+	}
+}
+
+/// Means for transacting the relay token, which is teleported to and from the relay chain.
+pub type RelayTokenTransactor = FungiblesAdapter<
+	// Use this fungibles implementation:
+	Assets,
+	// Use this currency when it is the relay token:
+	ConvertedConcreteAssetId<AssetId, Balance, RelayTokenAsAssetId, JustTry>,
+	// Convert an XCM MultiLocation into a local account id:
+	LocationToAccountId,
+	// Our chain's account ID type (we can't get away without mentioning it explicitly):
+	AccountId,
+	// We don't track any teleports of the relay token: the relay chain issues it, and tracks its
+	// teleports in its own checking account.
+	Nothing,
+	// Unused, as no teleports are tracked.
+	CheckingAccount,
+>;
+
 /// Means for transacting reserved fungible assets.
 /// AsAssetMultiLocation uses pallet_asset_registry to convert between AssetId and MultiLocation.
 pub type ReservedFungiblesTransactor = FungiblesAdapter<
@@ -139,9 +210,57 @@ pub type ReservedFungiblesTransactor = FungiblesAdapter<
 	CheckingAccount,
 >;
 
+/// Means for transacting the non-fungible items minted on this chain.
+/// Items sent to other chains are kept in `UniquesHoldingAccount` until they come back.
+pub type LocalNonFungiblesTransactor = NonFungiblesHoldingAdapter<
+	// Use this non-fungibles implementation:
+	Uniques,
+	// Use this collection when it is a non-fungible asset matching the given location or name:
+	ConvertedConcreteAssetId<
+		CollectionId,
+		ItemId,
+		AsPrefixedGeneralIndex<UniquesPalletLocation, CollectionId, JustTry>,
+		AsInstanceIndex<ItemId>,
+	>,
+	// Convert an XCM MultiLocation into a local account id:
+	LocationToAccountId,
+	// Our chain's account ID type (we can't get away without mentioning it explicitly):
+	AccountId,
+	// The account holding the items which left this chain.
+	UniquesHoldingAccount,
+>;
+
+/// Means for transacting reserved non-fungible assets.
+/// AsCollectionMultiLocation uses pallet_asset_registry to convert between CollectionId and
+/// MultiLocation.
+pub type ReservedNonFungiblesTransactor = NonFungiblesHoldingAdapter<
+	// Use this non-fungibles implementation:
+	Uniques,
+	// Use this collection when it is a registered non-fungible asset matching the given location
+	// Collections not found in AssetRegistry will not be used
+	ConvertedRegisteredClassId<
+		CollectionId,
+		ItemId,
+		AsCollectionMultiLocation<CollectionId, AssetRegistry>,
+		AsInstanceIndex<ItemId>,
+	>,
+	// Convert an XCM MultiLocation into a local account id:
+	LocationToAccountId,
+	// Our chain's account ID type (we can't get away without mentioning it explicitly):
+	AccountId,
+	// The account holding the derivative items which were sent back to their reserve.
+	UniquesHoldingAccount,
+>;
+
 /// Means for transacting assets on this chain.
-pub type AssetTransactors =
-	(LocalAssetTransactor, ReservedFungiblesTransactor, LocalFungiblesTransactor);
+pub type AssetTransactors = (
+	LocalAssetTransactor,
+	RelayTokenTransactor,
+	ReservedFungiblesTransactor,
+	LocalFungiblesTransactor,
+	ReservedNonFungiblesTransactor,
+	LocalNonFungiblesTransactor,
+);
 
 /// This is the type we use to convert an (incoming) XCM origin into a local `Origin` instance,
 /// ready for dispatching a transaction with Xcm's `Transact`. There is an `OriginKind` which can
@@ -185,11 +304,26 @@ match_types! {
 		MultiLocation { parents: 1, interior: X1(_) }
 	};
 }
+match_types! {
+	pub type Siblings: impl Contains<MultiLocation> = {
+		MultiLocation { parents: 1, interior: X1(Parachain(_)) }
+	};
+}
+match_types! {
+	pub type SiblingAccounts: impl Contains<MultiLocation> = {
+		MultiLocation { parents: 1, interior: X2(Parachain(_), AccountId32 { .. }) }
+	};
+}
 match_types! {
 	pub type Statemine: impl Contains<MultiLocation> = {
 		MultiLocation { parents: 1, interior: X1(Parachain(1000)) }
 	};
 }
+match_types! {
+	pub type Stout: impl Contains<MultiLocation> = {
+		MultiLocation { parents: 1, interior: X1(Parachain(3000)) }
+	};
+}
 
 pub type Barrier = DenyThenTry<
 	DenyReserveTransferToRelayChain,
@@ -199,6 +333,8 @@ pub type Barrier = DenyThenTry<
 		// Parent and its exec plurality get free execution
 		AllowUnpaidExecutionFrom<ParentOrParentsExecutivePlurality>,
 		AllowUnpaidExecutionFrom<Statemine>,
+		// Stout sends our uniques items back, which can't pay for execution
+		AllowUnpaidNonFungiblesReturnFrom<Stout, UniquesPalletLocation>,
 		// Expected responses are OK.
 		AllowKnownQueryResponses<PolkadotXcm>,
 		// Subscriptions for version tracking are OK.
@@ -217,6 +353,9 @@ parameter_types! {
 		MultiLocation::new(1, X3(Parachain(1000), PalletInstance(50), GeneralIndex(1))).into(),
 		default_fee_per_second() * 10
 	);
+
+	pub RelayTokenPerSecond: (xcm::v1::AssetId, u128) =
+		(MultiLocation::parent().into(), default_fee_per_second());
 }
 
 //- From PR https://github.com/paritytech/cumulus/pull/936
@@ -238,6 +377,10 @@ impl<T: Get<MultiLocation>> FilterAssetLocation for ReserveAssetsFrom<T> {
 			match asset {
 				MultiAsset { id: xcm::latest::AssetId::Concrete(asset_loc), fun: Fungible(_a) } =>
 					matches_prefix(&prefix, asset_loc),
+				MultiAsset {
+					id: xcm::latest::AssetId::Concrete(asset_loc),
+					fun: NonFungible(_instance),
+				} => matches_prefix(&prefix, asset_loc),
 				_ => false,
 			}
 	}
@@ -245,7 +388,63 @@ impl<T: Get<MultiLocation>> FilterAssetLocation for ReserveAssetsFrom<T> {
 
 //--
 
-pub type Reserves = (NativeAsset, ReserveAssetsFrom<StatemineLocation>);
+/// Accepts the native assets of sibling parachains as reserve-transferred from them, but not the
+/// relay token, which is only teleported.
+pub struct SiblingNativeAsset;
+impl FilterAssetLocation for SiblingNativeAsset {
+	fn filter_asset_location(asset: &MultiAsset, origin: &MultiLocation) -> bool {
+		origin != &RelayLocation::get() && NativeAsset::filter_asset_location(asset, origin)
+	}
+}
+
+pub type Reserves = (SiblingNativeAsset, ReserveAssetsFrom<StatemineLocation>);
+
+/// Only the relay token is teleported out of this chain.
+pub struct OnlyRelayToken;
+impl Contains<(MultiLocation, Vec<MultiAsset>)> for OnlyRelayToken {
+	fn contains((_, assets): &(MultiLocation, Vec<MultiAsset>)) -> bool {
+		assets.iter().all(|asset| match asset {
+			MultiAsset { id: Concrete(location), fun: Fungible(_) } =>
+				location == &RelayLocation::get(),
+			_ => false,
+		})
+	}
+}
+
+parameter_types! {
+	// This chain, as seen from its siblings.
+	pub SelfLocation: MultiLocation =
+		MultiLocation::new(1, X1(Parachain(ParachainInfo::parachain_id().into())));
+}
+
+/// The XCM origin of sibling parachains, which `Transact` the swaps of `pallet_dex_xcm`.
+pub type SiblingOrigin = EnsureXcm<Siblings>;
+
+/// The XCM origin of accounts of sibling parachains, which `Transact` the matches of
+/// `pallet_chess_xcm`.
+pub type SiblingAccountOrigin = EnsureXcm<SiblingAccounts>;
+
+/// Converts between the `MultiLocation` of fungible assets and their `AssetId`, for both the
+/// reserve assets of `AssetRegistry` and the assets of this chain.
+pub type AssetIdLocation = (
+	AsAssetMultiLocation<AssetId, AssetRegistry>,
+	AsPrefixedGeneralIndex<AssetsPalletLocation, AssetId, JustTry>,
+);
+
+/// Deposits the fees paid in reserve assets into the treasury, instead of burning them.
+pub struct ToTreasury;
+impl TakeRevenue for ToTreasury {
+	fn take_revenue(revenue: MultiAsset) {
+		if let Err(error) = AssetTransactors::deposit_asset(&revenue, &TreasuryLocation::get()) {
+			log::warn!(
+				target: "xcm::take_revenue",
+				"failed to deposit {:?} into the treasury: {:?}",
+				revenue,
+				error
+			);
+		}
+	}
+}
 
 pub struct XcmConfig;
 impl xcm_executor::Config for XcmConfig {
@@ -254,12 +453,15 @@ impl xcm_executor::Config for XcmConfig {
 	type AssetTransactor = AssetTransactors;
 	type OriginConverter = XcmOriginToTransactDispatchOrigin;
 	type IsReserve = Reserves;
-	type IsTeleporter = (); // Teleporting is disabled.
+	// Teleport trust pairs of (asset, origin) are managed by governance via AssetRegistry. The
+	// relay token is teleported, and never accepted as a reserve asset (see `Reserves`).
+	type IsTeleporter = IsTrustedTeleporter<AssetRegistry>;
 	type LocationInverter = LocationInverter<Ancestry>;
 	type Barrier = Barrier;
 	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
 	type Trader = (
-		FixedRateOfFungible<XUsdPerSecond, ()>,
+		FixedRateOfFungible<XUsdPerSecond, ToTreasury>,
+		FixedRateOfFungible<RelayTokenPerSecond, ToTreasury>,
 		UsingComponents<WeightToFee, SelfReserve, AccountId, Balances, DealWithFees<Runtime>>,
 	);
 	type ResponseHandler = PolkadotXcm;
@@ -289,7 +491,7 @@ impl pallet_xcm::Config for Runtime {
 	type ExecuteXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
 	type XcmExecuteFilter = Everything;
 	type XcmExecutor = XcmExecutor<XcmConfig>;
-	type XcmTeleportFilter = Nothing;
+	type XcmTeleportFilter = OnlyRelayToken;
 	type XcmReserveTransferFilter = Everything;
 	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
 	type LocationInverter = LocationInverter<Ancestry>;
@@ -67,7 +67,7 @@ decl_test_parachain! {
 	}
 }
 
-const TRAPPIST_PARA_ID: u32 = 2000;
+const TRAPPIST_PARA_ID: u32 = 1836;
 decl_test_parachain! {
 	// The trappist parachain
 	pub struct Trappist {
@@ -91,6 +91,20 @@ decl_test_parachain! {
 				.assimilate_storage(&mut t)
 				.unwrap();
 
+			// the relay token, as created by the trappist chain specs
+			pallet_assets::GenesisConfig::<Runtime> {
+				assets: vec![(
+					trappist_runtime::xcm_config::RelayTokenAssetId::get(),
+					trappist_runtime::xcm_config::TreasuryAccount::get(),
+					true,
+					trappist_runtime::xcm_config::RelayTokenMinBalance::get(),
+				)],
+				accounts: vec![],
+				metadata: vec![],
+			}
+			.assimilate_storage(&mut t)
+			.unwrap();
+
 			let mut ext = sp_io::TestExternalities::new(t);
 			ext.execute_with(|| {
 				sp_tracing::try_init_simple();
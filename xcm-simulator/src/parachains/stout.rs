@@ -23,13 +23,14 @@ use stout_runtime::{
 	},
 	xcm_config::{
 		Barrier, CollatorSelectionUpdateOrigin, FungiblesTransactor, LocationToAccountId,
-		MaxInstructions, RelayLocation, RelayNetwork, Reserves, SelfReserve, UnitWeightCost,
-		XUsdPerSecond,
+		MaxInstructions, RelayLocation, RelayNetwork, Reserves, SelfReserve,
+		TrappistNonFungiblesTransactor, UnitWeightCost, XUsdPerSecond,
 	},
-	BlockNumber, DealWithFees, Hash, Header, Index, Period, PotId, RuntimeBlockLength,
-	RuntimeBlockWeights, Session, UnitBody, Version,
+	AttributeDepositBase, BlockNumber, CollectionDeposit, DealWithFees, DepositPerByte, Hash,
+	Header, Index, ItemDeposit, KeyLimit, Period, PotId, RuntimeBlockLength, RuntimeBlockWeights,
+	Session, StringLimit, UniquesMetadataDepositBase, UnitBody, ValueLimit, Version,
 };
-pub use stout_runtime::{AccountId, AssetId, Balance};
+pub use stout_runtime::{AccountId, AssetId, Balance, CollectionId, ItemId};
 use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{AsEnsureOriginWithArg, EitherOfDiverse, Everything, Nothing},
@@ -107,6 +108,27 @@ impl pallet_assets::Config for Runtime {
 	type CallbackHandle = ();
 }
 
+impl pallet_uniques::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type CollectionId = CollectionId;
+	type ItemId = ItemId;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type CollectionDeposit = CollectionDeposit;
+	type ItemDeposit = ItemDeposit;
+	type MetadataDepositBase = UniquesMetadataDepositBase;
+	type AttributeDepositBase = AttributeDepositBase;
+	type DepositPerByte = DepositPerByte;
+	type StringLimit = StringLimit;
+	type KeyLimit = KeyLimit;
+	type ValueLimit = ValueLimit;
+	type WeightInfo = pallet_uniques::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type Helper = ();
+	type CreateOrigin = AsEnsureOriginWithArg<frame_system::EnsureSigned<AccountId>>;
+	type Locker = ();
+}
+
 impl pallet_balances::Config for Runtime {
 	type Balance = Balance;
 	type DustRemoval = ();
@@ -168,7 +190,8 @@ parameter_types! {
 	pub Ancestry: MultiLocation = Parachain(MsgQueue::parachain_id().into()).into();
 }
 
-pub type AssetTransactors = (CurrencyTransactor, FungiblesTransactor);
+pub type AssetTransactors =
+	(CurrencyTransactor, FungiblesTransactor, TrappistNonFungiblesTransactor);
 pub type CurrencyTransactor =
 	CurrencyAdapter<Balances, IsConcrete<RelayLocation>, LocationToAccountId, AccountId, ()>;
 pub type XcmOriginToTransactDispatchOrigin = (
@@ -219,5 +242,6 @@ construct_runtime!(
 		CumulusXcm: cumulus_pallet_xcm::{Pallet, Event<T>, Origin} = 32,
 		Sudo: pallet_sudo = 40,
 		Assets: pallet_assets = 43,
+		Uniques: pallet_uniques = 45,
 	}
 );
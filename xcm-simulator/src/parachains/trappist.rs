@@ -18,28 +18,34 @@
 
 use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{AsEnsureOriginWithArg, EitherOfDiverse, Everything, Nothing},
-	weights::constants::RocksDbWeight,
+	traits::{AsEnsureOriginWithArg, ConstBool, EitherOfDiverse, Everything, Nothing},
+	weights::{constants::RocksDbWeight, Weight},
 };
 use frame_system::EnsureRoot;
+use pallet_contracts::{DefaultAddressGenerator, Frame, Schedule};
 use pallet_xcm::{EnsureXcm, IsMajorityOfBody, XcmPassthrough};
 use polkadot_runtime_common::BlockHashCount;
-use sp_core::{ConstU128, ConstU16, ConstU32};
+use sp_core::{ConstU128, ConstU16, ConstU32, ConstU64};
 use sp_runtime::traits::{AccountIdLookup, BlakeTwo256};
 use sp_std::prelude::*;
-pub use trappist_runtime::{constants::currency::EXISTENTIAL_DEPOSIT, AccountId, AssetId, Balance};
+pub use trappist_runtime::{
+	constants::currency::EXISTENTIAL_DEPOSIT, AccountId, AssetId, Balance, CollectionId, ItemId,
+};
 use trappist_runtime::{
 	constants::{
 		currency::{CENTS, UNITS},
 		fee::WeightToFee,
 	},
 	xcm_config::{
-		Barrier, CollatorSelectionUpdateOrigin, LocalFungiblesTransactor, LocationToAccountId,
-		MaxInstructions, RelayLocation, RelayNetwork, ReservedFungiblesTransactor, Reserves,
-		SelfReserve, UnitWeightCost, XUsdPerSecond,
+		Barrier, CollatorSelectionUpdateOrigin, LocalFungiblesTransactor,
+		LocalNonFungiblesTransactor, LocationToAccountId, MaxInstructions, OnlyRelayToken,
+		RelayLocation, RelayNetwork, RelayTokenPerSecond, RelayTokenTransactor,
+		ReservedFungiblesTransactor, ReservedNonFungiblesTransactor, Reserves, SelfReserve,
+		UnitWeightCost, XUsdPerSecond,
 	},
-	BlockNumber, DealWithFees, Hash, Header, Index, Period, PotId, RuntimeBlockLength,
-	RuntimeBlockWeights, Session, UnitBody, Version,
+	AttributeDepositBase, BlockNumber, CollectionDeposit, DealWithFees, DepositPerByte, Hash,
+	Header, Index, ItemDeposit, KeyLimit, Period, PotId, RuntimeBlockLength, RuntimeBlockWeights,
+	Session, StringLimit, UniquesMetadataDepositBase, UnitBody, ValueLimit, Version,
 };
 use xcm::latest::prelude::*;
 use xcm_builder::{
@@ -47,8 +53,8 @@ use xcm_builder::{
 	LocationInverter, ParentAsSuperuser, RelayChainAsNative, SiblingParachainAsNative,
 	SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation, UsingComponents,
 };
-use xcm_executor::{Config, XcmExecutor};
-use xcm_primitives::TrappistDropAssets;
+use xcm_executor::{traits::Convert, Config, XcmExecutor};
+use xcm_primitives::{IsTrustedTeleporter, TrappistDropAssets, XcmExtension};
 
 impl frame_system::Config for Runtime {
 	type BaseCallFilter = Everything;
@@ -112,9 +118,35 @@ impl pallet_asset_registry::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type ReserveAssetModifierOrigin = frame_system::EnsureRoot<Self::AccountId>;
 	type Assets = Assets;
+	type OnAssetRegistered = ();
+	type OnAssetUnregistered = ();
+	type CollectionId = CollectionId;
+	type Uniques = Uniques;
+	type TeleporterModifierOrigin = frame_system::EnsureRoot<Self::AccountId>;
 	type WeightInfo = pallet_asset_registry::weights::SubstrateWeight<Runtime>;
 }
 
+impl pallet_uniques::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type CollectionId = CollectionId;
+	type ItemId = ItemId;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type CollectionDeposit = CollectionDeposit;
+	type ItemDeposit = ItemDeposit;
+	type MetadataDepositBase = UniquesMetadataDepositBase;
+	type AttributeDepositBase = AttributeDepositBase;
+	type DepositPerByte = DepositPerByte;
+	type StringLimit = StringLimit;
+	type KeyLimit = KeyLimit;
+	type ValueLimit = ValueLimit;
+	type WeightInfo = pallet_uniques::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type Helper = ();
+	type CreateOrigin = AsEnsureOriginWithArg<frame_system::EnsureSigned<AccountId>>;
+	type Locker = ();
+}
+
 impl pallet_balances::Config for Runtime {
 	type Balance = Balance;
 	type DustRemoval = ();
@@ -142,6 +174,43 @@ impl pallet_collator_selection::Config for Runtime {
 	type WeightInfo = pallet_collator_selection::weights::SubstrateWeight<Runtime>;
 }
 
+impl pallet_timestamp::Config for Runtime {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<1>;
+	type WeightInfo = ();
+}
+
+impl pallet_randomness_collective_flip::Config for Runtime {}
+
+parameter_types! {
+	pub ContractsSchedule: Schedule<Runtime> = Default::default();
+	pub const DeletionWeightLimit: Weight = Weight::from_ref_time(500_000_000_000);
+}
+
+impl pallet_contracts::Config for Runtime {
+	type Time = Timestamp;
+	type Randomness = RandomnessCollectiveFlip;
+	type Currency = Balances;
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type CallFilter = Nothing;
+	type DepositPerItem = ConstU128<1>;
+	type DepositPerByte = ConstU128<1>;
+	type WeightPrice = ();
+	type WeightInfo = pallet_contracts::weights::SubstrateWeight<Runtime>;
+	type ChainExtension = XcmExtension<Self>;
+	type DeletionQueueDepth = ConstU32<128>;
+	type DeletionWeightLimit = DeletionWeightLimit;
+	type Schedule = ContractsSchedule;
+	type CallStack = [Frame<Self>; 31];
+	type AddressGenerator = DefaultAddressGenerator;
+	type MaxCodeLen = ConstU32<{ 128 * 1024 }>;
+	type MaxStorageKeyLen = ConstU32<128>;
+	type UnsafeUnstableInterface = ConstBool<true>;
+	type MaxDebugBufferLen = ConstU32<{ 2 * 1024 * 1024 }>;
+}
+
 impl pallet_sudo::Config for Runtime {
 	type RuntimeCall = RuntimeCall;
 	type RuntimeEvent = RuntimeEvent;
@@ -156,7 +225,7 @@ impl pallet_xcm::Config for Runtime {
 	type ExecuteXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
 	type XcmExecuteFilter = Everything;
 	type XcmExecutor = XcmExecutor<XcmConfig>;
-	type XcmTeleportFilter = Nothing;
+	type XcmTeleportFilter = OnlyRelayToken;
 	type XcmReserveTransferFilter = Everything;
 	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
 	type LocationInverter = LocationInverter<Ancestry>;
@@ -176,8 +245,14 @@ parameter_types! {
 	pub Ancestry: MultiLocation = Parachain(MsgQueue::parachain_id().into()).into();
 }
 
-pub type AssetTransactors =
-	(LocalAssetTransactor, ReservedFungiblesTransactor, LocalFungiblesTransactor);
+pub type AssetTransactors = (
+	LocalAssetTransactor,
+	RelayTokenTransactor,
+	ReservedFungiblesTransactor,
+	LocalFungiblesTransactor,
+	ReservedNonFungiblesTransactor,
+	LocalNonFungiblesTransactor,
+);
 pub type LocalAssetTransactor =
 	CurrencyAdapter<Balances, IsConcrete<SelfReserve>, LocationToAccountId, AccountId, ()>;
 pub type XcmOriginToTransactDispatchOrigin = (
@@ -197,12 +272,13 @@ impl Config for XcmConfig {
 	type AssetTransactor = AssetTransactors;
 	type OriginConverter = XcmOriginToTransactDispatchOrigin;
 	type IsReserve = Reserves;
-	type IsTeleporter = ();
+	type IsTeleporter = IsTrustedTeleporter<AssetRegistry>;
 	type LocationInverter = LocationInverter<Ancestry>;
 	type Barrier = Barrier;
 	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
 	type Trader = (
 		FixedRateOfFungible<XUsdPerSecond, ()>,
+		FixedRateOfFungible<RelayTokenPerSecond, ()>,
 		UsingComponents<WeightToFee, SelfReserve, AccountId, Balances, DealWithFees<Runtime>>,
 	);
 	type ResponseHandler = PolkadotXcm;
@@ -224,11 +300,19 @@ construct_runtime!(
 		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		MsgQueue: super::mock_msg_queue::{Pallet, Storage, Event<T>},
+		RandomnessCollectiveFlip: pallet_randomness_collective_flip::{Pallet, Storage},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 		CollatorSelection: pallet_collator_selection::{Pallet, Call, Storage, Event<T>, Config<T>} = 21,
 		PolkadotXcm: pallet_xcm::{Pallet, Call, Event<T>, Origin} = 31,
 		CumulusXcm: cumulus_pallet_xcm::{Pallet, Event<T>, Origin} = 32,
 		Sudo: pallet_sudo = 40,
+		Contracts: pallet_contracts = 41,
 		Assets: pallet_assets = 43,
+		Uniques: pallet_uniques = 44,
 		AssetRegistry: pallet_asset_registry::{Pallet, Call, Storage, Event<T>} = 101,
 	}
 );
+
+pub(crate) fn sovereign_account(para_id: u32) -> AccountId {
+	LocationToAccountId::convert_ref(MultiLocation::new(1, X1(Parachain(para_id)))).unwrap()
+}
@@ -16,7 +16,7 @@
 
 //! Relay chain runtime mock.
 
-use crate::{relay_chain, ASSET_RESERVE_PARA_ID};
+use crate::{relay_chain, ASSET_RESERVE_PARA_ID, TRAPPIST_PARA_ID};
 use frame_support::{
 	construct_runtime, match_types, parameter_types,
 	traits::{Everything, Nothing},
@@ -119,6 +119,8 @@ parameter_types! {
 	pub const Rococo: MultiAssetFilter = Wild(AllOf { fun: WildFungible, id: Concrete(RocLocation::get()) });
 	pub const Statemine: MultiLocation = Parachain(ASSET_RESERVE_PARA_ID).into();
 	pub const RococoForStatemine: (MultiAssetFilter, MultiLocation) = (Rococo::get(), Statemine::get());
+	pub const Trappist: MultiLocation = Parachain(TRAPPIST_PARA_ID).into();
+	pub const RococoForTrappist: (MultiAssetFilter, MultiLocation) = (Rococo::get(), Trappist::get());
 }
 
 match_types! {
@@ -134,7 +136,8 @@ pub type Barrier = (
 	AllowKnownQueryResponses<XcmPallet>,
 	AllowSubscriptionsFrom<OnlyParachains>,
 );
-pub type TrustedTeleporters = (xcm_builder::Case<RococoForStatemine>,);
+pub type TrustedTeleporters =
+	(xcm_builder::Case<RococoForStatemine>, xcm_builder::Case<RococoForTrappist>);
 pub type XcmRouter = super::RelayChainXcmRouter;
 
 pub struct XcmConfig;
@@ -5,8 +5,10 @@ use frame_support::{
 	pallet_prelude::{DispatchResult, DispatchResultWithPostInfo},
 	traits::PalletInfoAccess,
 };
+use pallet_contracts::chain_extension::RegisteredChainExtension;
 use std::sync::Once;
 use xcm::prelude::*;
+use xcm_primitives::{XcmExtension, XcmFunc};
 
 mod misc;
 mod xcm_asset_trap;
@@ -117,3 +119,60 @@ fn register_reserve_asset_on_trappist(
 		})),
 	)
 }
+
+const CONTRACTS_GAS_LIMIT: frame_support::weights::Weight =
+	frame_support::weights::Weight::from_ref_time(100_000_000_000).set_proof_size(256 * 1024);
+
+// Deploys a contract on trappist which forwards its input to the chain extension and returns the
+// status code followed by the output of the extension
+fn deploy_chain_extension_contract_on_trappist(origin: trappist::AccountId) -> trappist::AccountId {
+	let wasm = wat::parse_file(concat!(
+		env!("CARGO_MANIFEST_DIR"),
+		"/../runtime/trappist/fixtures/chain_extension.wat"
+	))
+	.unwrap();
+
+	trappist::Contracts::bare_instantiate(
+		origin,
+		trappist::EXISTENTIAL_DEPOSIT,
+		CONTRACTS_GAS_LIMIT,
+		None,
+		pallet_contracts_primitives::Code::Upload(wasm),
+		vec![],
+		vec![],
+		false,
+	)
+	.result
+	.unwrap()
+	.account_id
+}
+
+// Calls the given function of the XCM chain extension through the contract, returning its
+// status code and output
+fn call_xcm_extension_on_trappist(
+	origin: trappist::AccountId,
+	contract: trappist::AccountId,
+	func: XcmFunc,
+	input: impl Encode,
+) -> (u32, Vec<u8>) {
+	let extension_id =
+		<XcmExtension<trappist::Runtime> as RegisteredChainExtension<trappist::Runtime>>::ID;
+	let id = ((extension_id as u32) << 16) | func as u32;
+	let mut data = id.encode();
+	data.extend(input.encode());
+
+	let output = trappist::Contracts::bare_call(
+		origin,
+		contract,
+		0,
+		CONTRACTS_GAS_LIMIT,
+		None,
+		data,
+		false,
+		pallet_contracts::Determinism::Deterministic,
+	)
+	.result
+	.unwrap()
+	.data;
+	(u32::from_le_bytes(output[..4].try_into().unwrap()), output[4..].to_vec())
+}
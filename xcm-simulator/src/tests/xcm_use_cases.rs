@@ -1,6 +1,7 @@
 use crate::tests::*;
 use frame_support::{assert_ok, pallet_prelude::DispatchResult, traits::PalletInfoAccess};
 use thousands::Separable;
+use xcm_primitives::{XcmExtensionError, XcmFunc};
 use xcm_simulator::TestExt;
 
 #[allow(non_upper_case_globals)]
@@ -9,6 +10,8 @@ const xUSD: u32 = 1;
 const txUSD: u32 = 10;
 #[allow(non_upper_case_globals)]
 const pxUSD: u32 = xUSD; // Must match asset reserve identifier as no asset registry available in stout runtime
+#[allow(non_upper_case_globals)]
+const tROC: u32 = 20; // Must match `RelayTokenAssetId` of the trappist runtime, created in genesis
 
 // Teleports some amount of the native asset of the relay chain to the asset reserve parachain
 // (DMP)
@@ -125,6 +128,132 @@ fn teleport_native_asset_from_asset_reserve_parachain_to_relay_chain() {
 	});
 }
 
+// Teleports some amount of the native asset of the relay chain to the trappist parachain (DMP)
+#[test]
+fn teleport_native_asset_from_relay_chain_to_trappist_parachain() {
+	init_tracing();
+
+	MockNet::reset();
+
+	const AMOUNT: u128 = 1_000_000_000;
+
+	Trappist::execute_with(|| {
+		// Trust the relay chain to teleport its native asset to Trappist
+		trust_relay_chain_teleports_on_trappist();
+	});
+
+	Relay::execute_with(|| {
+		// Teleport, ensuring relay chain total issuance remains constant
+		let total_issuance = relay_chain::Balances::total_issuance();
+		assert_ok!(relay_chain::XcmPallet::teleport_assets(
+			relay_chain::RuntimeOrigin::signed(ALICE),
+			Box::new(Parachain(TRAPPIST_PARA_ID).into().into()),
+			Box::new(X1(AccountId32 { network: Any, id: ALICE.into() }).into().into()),
+			Box::new((Here, AMOUNT).into()),
+			0
+		));
+		assert_eq!(relay_chain::Balances::total_issuance(), total_issuance);
+
+		// Ensure teleport amount 'checked out' to check account
+		assert_eq!(relay_chain::Balances::free_balance(&relay_chain::check_account()), AMOUNT);
+	});
+
+	const EST_FEES: u128 = 200_000_000;
+	Trappist::execute_with(|| {
+		// Ensure receiver balance and total issuance of the relay token increased by teleport
+		// amount, less fees
+		let current_balance = trappist::Assets::balance(tROC, &ALICE);
+		assert_balance(current_balance, AMOUNT, EST_FEES);
+		assert_eq!(trappist::Assets::total_supply(tROC), current_balance);
+	});
+}
+
+// Teleports some amount of the native asset of the relay chain from the trappist parachain back
+// to the relay chain (UMP)
+#[test]
+fn teleport_native_asset_from_trappist_parachain_to_relay_chain() {
+	init_tracing();
+
+	MockNet::reset();
+
+	const AMOUNT: u128 = 1_000_000_000;
+	let mut beneficiary_balance = 0;
+
+	Trappist::execute_with(|| {
+		// Trust the relay chain to teleport its native asset to Trappist
+		trust_relay_chain_teleports_on_trappist();
+	});
+
+	Relay::execute_with(|| {
+		// Teleport some amount to trappist so there are tokens to teleport back
+		assert_ok!(relay_chain::XcmPallet::teleport_assets(
+			relay_chain::RuntimeOrigin::signed(ALICE),
+			Box::new(Parachain(TRAPPIST_PARA_ID).into().into()),
+			Box::new(X1(AccountId32 { network: Any, id: ALICE.into() }).into().into()),
+			Box::new((Here, AMOUNT).into()),
+			0
+		));
+
+		// Check beneficiary balance
+		beneficiary_balance = relay_chain::Balances::free_balance(&ALICE);
+	});
+
+	Trappist::execute_with(|| {
+		// Check sender balance & total issuance of the relay token on trappist before teleporting
+		let sender_balance = trappist::Assets::balance(tROC, &ALICE);
+		let total_issuance = trappist::Assets::total_supply(tROC);
+		assert_ok!(trappist::PolkadotXcm::teleport_assets(
+			trappist::RuntimeOrigin::signed(ALICE),
+			Box::new(Parent.into()),
+			Box::new(X1(AccountId32 { network: Any, id: ALICE.into() }).into().into()),
+			Box::new((Parent, AMOUNT / 2).into()),
+			0
+		));
+
+		// Ensure sender balance and total issuance (of the relay token on trappist) decreased by
+		// teleport amount
+		assert_eq!(trappist::Assets::balance(tROC, &ALICE), sender_balance - AMOUNT / 2);
+		assert_eq!(trappist::Assets::total_supply(tROC), total_issuance - AMOUNT / 2);
+	});
+
+	const EST_FEES: u128 = 2_500_000;
+	Relay::execute_with(|| {
+		// Ensure receiver balance increased by teleport amount and the check account was used
+		let current_balance = relay_chain::Balances::free_balance(&ALICE);
+		assert_balance(current_balance, beneficiary_balance + AMOUNT / 2, EST_FEES);
+		assert_eq!(
+			relay_chain::Balances::free_balance(&relay_chain::check_account()),
+			AMOUNT - AMOUNT / 2
+		);
+	});
+}
+
+// The relay token is only teleported between the relay chain and the trappist parachain, never
+// reserve-transferred
+#[test]
+fn relay_token_is_only_teleported_to_and_from_trappist_parachain() {
+	use frame_support::traits::Contains;
+	use trappist_runtime::xcm_config::{OnlyRelayToken, Reserves};
+	use xcm_executor::traits::FilterAssetLocation;
+
+	MockNet::reset();
+
+	let relay_token: MultiAsset = (Parent, 1_000).into();
+	let stout: MultiLocation = (Parent, Parachain(STOUT_PARA_ID)).into();
+
+	Trappist::execute_with(|| {
+		assert!(!Reserves::filter_asset_location(&relay_token, &Parent.into()));
+		// the native assets of siblings are still reserve-transferred from them
+		assert!(Reserves::filter_asset_location(&(stout.clone(), 1_000).into(), &stout));
+
+		assert!(OnlyRelayToken::contains(&(Parent.into(), vec![relay_token.clone()])));
+		assert!(!OnlyRelayToken::contains(&(
+			Parent.into(),
+			vec![relay_token, (Here, 1_000).into()]
+		)));
+	});
+}
+
 // Initiates a reserve-transfer of some asset on the asset reserve parachain to the trappist
 // parachain (HRMP)
 #[test]
@@ -357,6 +486,19 @@ fn two_hop_reserve_transfer_from_trappist_parachain_to_tertiary_parachain() {
 	});
 }
 
+// Trusts the relay chain to teleport its native asset, created in genesis, to trappist
+fn trust_relay_chain_teleports_on_trappist() {
+	assert_ok!(trappist::Sudo::sudo(
+		trappist::RuntimeOrigin::signed(ALICE),
+		Box::new(trappist::RuntimeCall::AssetRegistry(pallet_asset_registry::Call::<
+			trappist::Runtime,
+		>::add_trusted_teleporter {
+			asset_multi_location: Parent.into(),
+			teleporter: Parent.into(),
+		})),
+	));
+}
+
 fn create_derivative_asset_on_tertiary_parachain(
 	id: stout::AssetId,
 	admin: stout::AccountId,
@@ -364,3 +506,393 @@ fn create_derivative_asset_on_tertiary_parachain(
 ) -> DispatchResult {
 	stout::Assets::create(stout::RuntimeOrigin::signed(ALICE), id.into(), admin.into(), min_balance)
 }
+
+// Reserve-transfers a uniques item minted on trappist to some tertiary (stout) parachain (HRMP)
+// and sends it back again
+#[test]
+fn reserve_transfer_nft_from_trappist_parachain_to_tertiary_parachain_and_back() {
+	init_tracing();
+
+	MockNet::reset();
+
+	const COLLECTION: u32 = 0;
+	const ITEM: u32 = 42;
+	const MAX_WEIGHT: u128 = 1_000_000_000 * 3; // 1,000,000,000 per instruction
+
+	let nft = |location: MultiLocation| -> MultiAsset { (location, Index(ITEM as u128)).into() };
+	let beneficiary: MultiLocation = X1(AccountId32 { network: Any, id: ALICE.into() }).into();
+
+	Stout::execute_with(|| {
+		// Create the collection mirroring the trappist one on tertiary parachain
+		assert_ok!(stout::Sudo::sudo(
+			stout::RuntimeOrigin::signed(ALICE),
+			Box::new(stout::RuntimeCall::Uniques(
+				pallet_uniques::Call::<stout::Runtime>::force_create {
+					collection: COLLECTION,
+					owner: ALICE.into(),
+					free_holding: true,
+				}
+			)),
+		));
+	});
+
+	Trappist::execute_with(|| {
+		// Create collection and mint item on Trappist Parachain
+		assert_ok!(trappist::Sudo::sudo(
+			trappist::RuntimeOrigin::signed(ALICE),
+			Box::new(trappist::RuntimeCall::Uniques(
+				pallet_uniques::Call::<trappist::Runtime>::force_create {
+					collection: COLLECTION,
+					owner: ALICE.into(),
+					free_holding: true,
+				}
+			)),
+		));
+		assert_ok!(trappist::Uniques::mint(
+			trappist::RuntimeOrigin::signed(ALICE),
+			COLLECTION,
+			ITEM,
+			ALICE.into()
+		));
+
+		// Trappist parachain should be able to reserve-transfer the item to Tertiary Parachain
+		assert_ok!(trappist::PolkadotXcm::execute(
+			trappist::RuntimeOrigin::signed(ALICE),
+			Box::new(VersionedXcm::from(Xcm(vec![
+				WithdrawAsset(
+					nft(X2(
+						PalletInstance(trappist::Uniques::index() as u8),
+						GeneralIndex(COLLECTION as u128)
+					)
+					.into())
+					.into()
+				),
+				DepositReserveAsset {
+					assets: Wild(All),
+					max_assets: 1,
+					dest: (Parent, Parachain(STOUT_PARA_ID)).into(),
+					xcm: Xcm(vec![DepositAsset {
+						assets: Wild(All),
+						max_assets: 1,
+						beneficiary: beneficiary.clone()
+					}])
+				},
+			]))),
+			MAX_WEIGHT as u64
+		));
+
+		// Check item moved to the sovereign account of Tertiary Parachain
+		assert_eq!(
+			trappist::Uniques::owner(COLLECTION, ITEM),
+			Some(trappist::sovereign_account(STOUT_PARA_ID))
+		);
+	});
+
+	Stout::execute_with(|| {
+		// Ensure beneficiary received the derivative item
+		assert_eq!(stout::Uniques::owner(COLLECTION, ITEM), Some(ALICE));
+
+		// Tertiary parachain should be able to send the item back to its reserve
+		assert_ok!(stout::PolkadotXcm::execute(
+			stout::RuntimeOrigin::signed(ALICE),
+			Box::new(VersionedXcm::from(Xcm(vec![
+				WithdrawAsset(
+					nft((
+						Parent,
+						X3(
+							Parachain(TRAPPIST_PARA_ID),
+							PalletInstance(trappist::Uniques::index() as u8),
+							GeneralIndex(COLLECTION as u128)
+						)
+					)
+						.into())
+					.into()
+				),
+				InitiateReserveWithdraw {
+					assets: Wild(All),
+					reserve: (Parent, Parachain(TRAPPIST_PARA_ID)).into(),
+					xcm: Xcm(vec![DepositAsset {
+						assets: Wild(All),
+						max_assets: 1,
+						beneficiary: beneficiary.clone()
+					}])
+				},
+			]))),
+			MAX_WEIGHT as u64
+		));
+
+		// Check derivative item is no longer owned by the beneficiary
+		assert_ne!(stout::Uniques::owner(COLLECTION, ITEM), Some(ALICE));
+	});
+
+	Trappist::execute_with(|| {
+		// Ensure beneficiary received the item back
+		assert_eq!(trappist::Uniques::owner(COLLECTION, ITEM), Some(ALICE));
+	});
+}
+
+// Stout only gets free execution on trappist for sending trappist uniques items back
+#[test]
+fn only_nft_returns_from_tertiary_parachain_are_unpaid_on_trappist() {
+	use trappist_runtime::xcm_config::Barrier;
+	use xcm_executor::traits::ShouldExecute;
+
+	MockNet::reset();
+
+	let stout: MultiLocation = (Parent, Parachain(STOUT_PARA_ID)).into();
+	let beneficiary: MultiLocation = X1(AccountId32 { network: Any, id: ALICE.into() }).into();
+	let deposit = DepositAsset { assets: Wild(All), max_assets: 1, beneficiary };
+
+	Trappist::execute_with(|| {
+		let nft: MultiAsset =
+			(X2(PalletInstance(trappist::Uniques::index() as u8), GeneralIndex(0)), Index(42))
+				.into();
+		let should_execute = |instructions: Vec<Instruction<trappist::RuntimeCall>>| {
+			Barrier::should_execute(&stout, &mut Xcm(instructions), 1_000_000_000, &mut 0)
+		};
+
+		assert_eq!(
+			should_execute(vec![WithdrawAsset(nft.clone().into()), ClearOrigin, deposit.clone()]),
+			Ok(())
+		);
+		// sent by an account of stout
+		assert_eq!(
+			should_execute(vec![
+				DescendOrigin(X1(AccountId32 { network: Any, id: ALICE.into() })),
+				WithdrawAsset(nft.clone().into()),
+				ClearOrigin,
+				deposit.clone(),
+			]),
+			Err(())
+		);
+		// not an item of trappist
+		assert_eq!(
+			should_execute(vec![
+				WithdrawAsset((Parent, 1_000).into()),
+				ClearOrigin,
+				deposit.clone(),
+			]),
+			Err(())
+		);
+		assert_eq!(
+			should_execute(vec![
+				WithdrawAsset(nft.into()),
+				ClearOrigin,
+				Transact {
+					origin_type: OriginKind::SovereignAccount,
+					require_weight_at_most: 1_000_000,
+					call: trappist::RuntimeCall::System(frame_system::Call::remark {
+						remark: vec![]
+					})
+					.encode()
+					.into(),
+				},
+			]),
+			Err(())
+		);
+	});
+}
+
+// Trappist only gets free execution on stout for depositing trappist uniques items
+#[test]
+fn only_nft_deposits_from_trappist_parachain_are_unpaid_on_tertiary_parachain() {
+	use stout_runtime::xcm_config::Barrier;
+	use xcm_executor::traits::ShouldExecute;
+
+	MockNet::reset();
+
+	let trappist: MultiLocation = (Parent, Parachain(TRAPPIST_PARA_ID)).into();
+	let beneficiary: MultiLocation = X1(AccountId32 { network: Any, id: ALICE.into() }).into();
+	let deposit = DepositAsset { assets: Wild(All), max_assets: 1, beneficiary };
+
+	Stout::execute_with(|| {
+		let nft: MultiAsset = (
+			(
+				Parent,
+				X3(
+					Parachain(TRAPPIST_PARA_ID),
+					PalletInstance(trappist::Uniques::index() as u8),
+					GeneralIndex(0),
+				),
+			),
+			Index(42),
+		)
+			.into();
+		let should_execute = |instructions: Vec<Instruction<stout::RuntimeCall>>| {
+			Barrier::should_execute(&trappist, &mut Xcm(instructions), 1_000_000_000, &mut 0)
+		};
+
+		assert_eq!(
+			should_execute(vec![
+				ReserveAssetDeposited(nft.clone().into()),
+				ClearOrigin,
+				deposit.clone()
+			]),
+			Ok(())
+		);
+		// not an item of trappist
+		assert_eq!(
+			should_execute(vec![
+				ReserveAssetDeposited((Parent, 1_000).into()),
+				ClearOrigin,
+				deposit.clone(),
+			]),
+			Err(())
+		);
+		assert_eq!(
+			should_execute(vec![Transact {
+				origin_type: OriginKind::SovereignAccount,
+				require_weight_at_most: 1_000_000,
+				call: stout::RuntimeCall::System(frame_system::Call::remark { remark: vec![] })
+					.encode()
+					.into(),
+			}]),
+			Err(())
+		);
+		assert_eq!(
+			should_execute(vec![
+				ReserveAssetDeposited(nft.into()),
+				ClearOrigin,
+				Transact {
+					origin_type: OriginKind::SovereignAccount,
+					require_weight_at_most: 1_000_000,
+					call: stout::RuntimeCall::System(frame_system::Call::remark { remark: vec![] })
+						.encode()
+						.into(),
+				},
+			]),
+			Err(())
+		);
+	});
+}
+
+// A contract on Trappist reserve-transfers a derivative asset it holds back to the asset reserve
+// parachain through the XCM chain extension
+#[test]
+fn reserve_transfer_asset_from_contract_on_trappist_parachain_to_asset_reserve_parachain() {
+	init_tracing();
+
+	MockNet::reset();
+
+	const ASSET_MIN_BALANCE: asset_reserve::Balance = 1_000_000_000;
+	const AMOUNT: u128 = 100_000_000_000;
+
+	AssetReserve::execute_with(|| {
+		// Create and mint fungible asset on Reserve Parachain
+		assert_ok!(create_asset_on_asset_reserve(xUSD, ALICE, ASSET_MIN_BALANCE));
+		assert_ok!(mint_asset_on_asset_reserve(xUSD, ALICE, AMOUNT * 2));
+
+		// Touch parachain account
+		assert_ok!(asset_reserve::Assets::transfer(
+			asset_reserve::RuntimeOrigin::signed(ALICE),
+			xUSD.into(),
+			asset_reserve::sovereign_account(TRAPPIST_PARA_ID).into(),
+			AMOUNT
+		));
+	});
+
+	Relay::execute_with(|| {
+		// Declare xUSD (on Reserve Parachain) as self-sufficient via Relay Chain
+		paras_sudo_wrapper_sudo_queue_downward_xcm(asset_reserve::RuntimeCall::Assets(
+			pallet_assets::Call::<asset_reserve::Runtime>::force_asset_status {
+				id: xUSD,
+				owner: ALICE.into(),
+				issuer: ALICE.into(),
+				admin: ALICE.into(),
+				freezer: ALICE.into(),
+				min_balance: ASSET_MIN_BALANCE,
+				is_sufficient: true,
+				is_frozen: false,
+			},
+		));
+	});
+
+	const MAX_WEIGHT: u128 = 1_000_000_000 * 2; // 1,000,000,000 per instruction
+	const EXECUTION_COST: u128 = 65_000_000_000;
+
+	Trappist::execute_with(|| {
+		// Create derivative asset on Trappist Parachain and map it to xUSD via Asset Registry
+		assert_ok!(create_derivative_asset_on_trappist(txUSD, ALICE.into(), ASSET_MIN_BALANCE));
+		assert_ok!(register_reserve_asset_on_trappist(ALICE, txUSD, xUSD));
+
+		// Deploy contract and mint derivative asset to it
+		let contract = deploy_chain_extension_contract_on_trappist(ALICE);
+		assert_ok!(trappist::Assets::mint(
+			trappist::RuntimeOrigin::signed(ALICE),
+			txUSD.into(),
+			contract.clone().into(),
+			AMOUNT
+		));
+
+		let message = VersionedXcm::<trappist::RuntimeCall>::from(Xcm(vec![
+			WithdrawAsset(
+				(
+					(
+						Parent,
+						X3(
+							Parachain(ASSET_RESERVE_PARA_ID),
+							PalletInstance(asset_reserve::Assets::index() as u8),
+							GeneralIndex(xUSD as u128),
+						),
+					),
+					AMOUNT,
+				)
+					.into(),
+			),
+			InitiateReserveWithdraw {
+				assets: Wild(All),
+				reserve: (Parent, Parachain(ASSET_RESERVE_PARA_ID)).into(),
+				xcm: Xcm(vec![
+					BuyExecution {
+						fees: (
+							X2(
+								PalletInstance(asset_reserve::Assets::index() as u8),
+								GeneralIndex(xUSD as u128),
+							),
+							EXECUTION_COST,
+						)
+							.into(),
+						weight_limit: Unlimited,
+					},
+					DepositAsset {
+						assets: Wild(All),
+						max_assets: 1,
+						beneficiary: X1(AccountId32 { network: Any, id: BOB.into() }).into(),
+					},
+				]),
+			},
+		]));
+
+		// Messages exceeding the given maximum weight are not executed
+		let (status, _) = call_xcm_extension_on_trappist(
+			ALICE,
+			contract.clone(),
+			XcmFunc::Execute,
+			(message.clone(), MAX_WEIGHT as u64 - 1),
+		);
+		assert_eq!(status, XcmExtensionError::WeightLimitExceeded as u32);
+		assert_eq!(trappist::Assets::balance(txUSD, &contract), AMOUNT);
+
+		// Contract should be able to reserve-transfer the asset it holds
+		let (status, _) = call_xcm_extension_on_trappist(
+			ALICE,
+			contract.clone(),
+			XcmFunc::Execute,
+			(message, MAX_WEIGHT as u64),
+		);
+		assert_eq!(status, XcmExtensionError::Success as u32);
+		assert_eq!(trappist::Assets::balance(txUSD, &contract), 0);
+	});
+
+	AssetReserve::execute_with(|| {
+		// Ensure beneficiary received amount, less fees
+		assert_balance(asset_reserve::Assets::balance(xUSD, &BOB), AMOUNT, EXECUTION_COST);
+		assert_eq!(
+			asset_reserve::Assets::balance(
+				xUSD,
+				&asset_reserve::sovereign_account(TRAPPIST_PARA_ID)
+			),
+			0
+		);
+	});
+}